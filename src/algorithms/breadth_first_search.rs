@@ -0,0 +1,550 @@
+use hashbrown::hash_map::Entry;
+use hashbrown::HashMap;
+
+use graphblas_sparse_linear_algebra::value_types::sparse_matrix::SparseMatrix;
+use graphblas_sparse_linear_algebra::value_types::sparse_vector::{
+    GetVectorElementList, SetVectorElement, SparseVector, VectorElement,
+};
+
+use crate::error::GraphComputingError;
+use crate::graph::edge::adjacency_matrix::AdjacencyMatrix;
+use crate::graph::edge::boolean_matrix_operations::{
+    multiply_structure_vector_excluding, structure_vector_from_indices, to_structure_matrix,
+};
+use crate::graph::edge::{
+    DirectedEdgeDefinedByKeys, EdgeTypeIndex, EdgeTypeKeyAndIndexConversion, EdgeTypeRef,
+};
+use crate::graph::graph::{ElementIndex, Graph, GraphTrait};
+use crate::graph::vertex::{VertexIndex, VertexKeyAndIndexConversion, VertexKeyRef};
+use crate::operations::selection::vertex_selection::VertexSelection;
+
+/// The direction in which edges are followed, relative to the vertex they are followed from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TraversalDirection {
+    Outgoing,
+    Incoming,
+    Both,
+}
+
+impl TraversalDirection {
+    // Whether to follow the edges of the transposed adjacency matrix
+    fn transpositions(self) -> &'static [bool] {
+        match self {
+            TraversalDirection::Outgoing => &[false],
+            TraversalDirection::Incoming => &[true],
+            TraversalDirection::Both => &[false, true],
+        }
+    }
+}
+
+pub trait BreadthFirstSearch {
+    /// Visits all vertices reachable from vertex_key by edges of edge_types, followed in direction.
+    fn breadth_first_search_from_vertex_key(
+        &self,
+        vertex_key: &VertexKeyRef,
+        edge_types: &[&EdgeTypeRef],
+        direction: TraversalDirection,
+    ) -> Result<BreadthFirstSearchResult<'_>, GraphComputingError>;
+
+    /// Visits all vertices reachable from any vertex in vertex_selection, which all have level 0.
+    fn breadth_first_search_from_vertex_selection(
+        &self,
+        vertex_selection: &VertexSelection,
+        edge_types: &[&EdgeTypeRef],
+        direction: TraversalDirection,
+    ) -> Result<BreadthFirstSearchResult<'_>, GraphComputingError>;
+}
+
+impl BreadthFirstSearch for Graph {
+    fn breadth_first_search_from_vertex_key(
+        &self,
+        vertex_key: &VertexKeyRef,
+        edge_types: &[&EdgeTypeRef],
+        direction: TraversalDirection,
+    ) -> Result<BreadthFirstSearchResult<'_>, GraphComputingError> {
+        self.breadth_first_search(
+            vec![self.existing_vertex_index(vertex_key)?],
+            edge_types,
+            direction,
+        )
+    }
+
+    fn breadth_first_search_from_vertex_selection(
+        &self,
+        vertex_selection: &VertexSelection,
+        edge_types: &[&EdgeTypeRef],
+        direction: TraversalDirection,
+    ) -> Result<BreadthFirstSearchResult<'_>, GraphComputingError> {
+        let start_vertex_indices = vertex_selection
+            .vertex_indices_ref()?
+            .into_iter()
+            .map(|vertex_index| vertex_index.index())
+            .collect();
+        self.breadth_first_search(start_vertex_indices, edge_types, direction)
+    }
+}
+
+// The edges followed by a breadth-first search, converted once for repeated searches over the same edges
+pub(crate) struct TraversedEdges<'g> {
+    adjacency_matrices: Vec<(EdgeTypeIndex, &'g AdjacencyMatrix, SparseMatrix<u8>)>,
+    direction: TraversalDirection,
+}
+
+// The edge by which a vertex was first reached
+#[derive(Clone, Copy, Debug)]
+struct ParentEdge {
+    parent_vertex_index: ElementIndex,
+    edge_type_index: EdgeTypeIndex,
+    is_reversed: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct BreadthFirstSearchResult<'g> {
+    graph: &'g Graph,
+    levels: HashMap<ElementIndex, usize>,
+    parent_edges: HashMap<ElementIndex, ParentEdge>,
+}
+
+impl<'g> BreadthFirstSearchResult<'g> {
+    /// The number of edges on a shortest path from the start vertices, None if vertex_key was not reached.
+    pub fn level(&self, vertex_key: &VertexKeyRef) -> Option<usize> {
+        let vertex_index = self
+            .graph
+            .vertex_key_to_vertex_index_map_ref()
+            .get(vertex_key)?;
+        self.levels.get(vertex_index.index_ref()).copied()
+    }
+
+    /// The vertex from which vertex_key was first reached. None for start vertices and vertices that were not reached.
+    pub fn parent(
+        &self,
+        vertex_key: &VertexKeyRef,
+    ) -> Result<Option<&'g VertexKeyRef>, GraphComputingError> {
+        match self.parent_edge_by_key(vertex_key) {
+            None => Ok(None),
+            Some(parent_edge) => Ok(Some(self.vertex_key_ref(parent_edge.parent_vertex_index)?)),
+        }
+    }
+
+    /// The edge by which vertex_key was first reached, in the direction it is stored in the graph.
+    /// An edge followed in the incoming direction therefore starts at vertex_key.
+    pub fn parent_edge(
+        &self,
+        vertex_key: &VertexKeyRef,
+    ) -> Result<Option<DirectedEdgeDefinedByKeys>, GraphComputingError> {
+        let parent_edge = match self.parent_edge_by_key(vertex_key) {
+            None => return Ok(None),
+            Some(parent_edge) => parent_edge,
+        };
+        let parent_vertex_key = self.vertex_key_ref(parent_edge.parent_vertex_index)?;
+        let edge_type = self
+            .graph
+            .edge_type_index_to_edge_type_ref(parent_edge.edge_type_index)?;
+        let (from_vertex_key, to_vertex_key) = if parent_edge.is_reversed {
+            (vertex_key, parent_vertex_key)
+        } else {
+            (parent_vertex_key, vertex_key)
+        };
+        Ok(Some(DirectedEdgeDefinedByKeys::new(
+            from_vertex_key.to_owned(),
+            edge_type.to_owned(),
+            to_vertex_key.to_owned(),
+        )))
+    }
+
+    /// All reached vertices with their level, ordered by level.
+    pub fn levels(&self) -> Result<Vec<(&'g VertexKeyRef, usize)>, GraphComputingError> {
+        let mut levels = Vec::with_capacity(self.levels.len());
+        for (&vertex_index, &level) in self.levels.iter() {
+            levels.push((self.vertex_key_ref(vertex_index)?, level));
+        }
+        levels.sort_by(|(vertex_key_a, level_a), (vertex_key_b, level_b)| {
+            level_a.cmp(level_b).then(vertex_key_a.cmp(vertex_key_b))
+        });
+        Ok(levels)
+    }
+
+    pub fn vertices_at_level(
+        &self,
+        level: usize,
+    ) -> Result<VertexSelection<'g>, GraphComputingError> {
+        self.select_vertices(|&vertex_level| vertex_level == level)
+    }
+
+    pub fn reached_vertices(&self) -> Result<VertexSelection<'g>, GraphComputingError> {
+        self.select_vertices(|_| true)
+    }
+
+    pub(crate) fn level_by_index(&self, vertex_index: ElementIndex) -> Option<usize> {
+        self.levels.get(&vertex_index).copied()
+    }
+
+    /// The parent vertex index and the type of the edge by which vertex_index was first reached.
+    pub(crate) fn parent_by_index(
+        &self,
+        vertex_index: ElementIndex,
+    ) -> Option<(ElementIndex, EdgeTypeIndex)> {
+        self.parent_edges
+            .get(&vertex_index)
+            .map(|parent_edge| (parent_edge.parent_vertex_index, parent_edge.edge_type_index))
+    }
+
+    pub(crate) fn reached_vertex_indices(&self) -> Vec<ElementIndex> {
+        self.levels.keys().copied().collect()
+    }
+
+    pub(crate) fn vertex_indices_at_level(&self, level: usize) -> Vec<ElementIndex> {
+        self.levels
+            .iter()
+            .filter(|(_, &vertex_level)| vertex_level == level)
+            .map(|(&vertex_index, _)| vertex_index)
+            .collect()
+    }
+
+    fn parent_edge_by_key(&self, vertex_key: &VertexKeyRef) -> Option<&ParentEdge> {
+        let vertex_index = self
+            .graph
+            .vertex_key_to_vertex_index_map_ref()
+            .get(vertex_key)?;
+        self.parent_edges.get(vertex_index.index_ref())
+    }
+
+    fn vertex_key_ref(
+        &self,
+        vertex_index: ElementIndex,
+    ) -> Result<&'g VertexKeyRef, GraphComputingError> {
+        self.graph
+            .vertex_index_to_vertex_key_ref(VertexIndex::new(vertex_index))
+    }
+
+    fn select_vertices(
+        &self,
+        is_selected_level: impl Fn(&usize) -> bool,
+    ) -> Result<VertexSelection<'g>, GraphComputingError> {
+        let mut vertex_mask = SparseVector::<bool>::new(
+            self.graph.graphblas_context_ref(),
+            &self.graph.vertex_capacity()?,
+        )?;
+        for (&vertex_index, level) in self.levels.iter() {
+            if is_selected_level(level) {
+                vertex_mask.set_element(VectorElement::from_pair(vertex_index, true))?;
+            }
+        }
+        VertexSelection::new(self.graph, vertex_mask)
+    }
+}
+
+impl Graph {
+    pub(crate) fn traversed_edges(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+        direction: TraversalDirection,
+    ) -> Result<TraversedEdges<'_>, GraphComputingError> {
+        let mut adjacency_matrices = Vec::with_capacity(edge_types.len());
+        for edge_type in edge_types {
+            let adjacency_matrix = self.get_edge_adjacency_matrix_ref(edge_type)?;
+            adjacency_matrices.push((
+                *self.edge_type_ref_to_edge_type_index_ref(edge_type)?,
+                adjacency_matrix,
+                to_structure_matrix(adjacency_matrix.as_sparse_matrix())?,
+            ));
+        }
+        Ok(TraversedEdges {
+            adjacency_matrices,
+            direction,
+        })
+    }
+
+    fn breadth_first_search(
+        &self,
+        start_vertex_indices: Vec<ElementIndex>,
+        edge_types: &[&EdgeTypeRef],
+        direction: TraversalDirection,
+    ) -> Result<BreadthFirstSearchResult<'_>, GraphComputingError> {
+        let traversed_edges = self.traversed_edges(edge_types, direction)?;
+        self.breadth_first_search_over(start_vertex_indices, &traversed_edges, None)
+    }
+
+    /// The vertices within k hops of the start vertices, and the vertices at exactly k hops.
+    /// Each hop is a vector-matrix product masked by the complement of the vertices reached so far.
+    pub(crate) fn k_hop_neighbourhood(
+        &self,
+        start_vertex_indices: Vec<ElementIndex>,
+        traversed_edges: &TraversedEdges,
+        k: usize,
+    ) -> Result<(SparseVector<bool>, SparseVector<bool>), GraphComputingError> {
+        let vertex_capacity = self.vertex_capacity()?;
+        let mut reached_vertices =
+            SparseVector::<bool>::new(self.graphblas_context_ref(), &vertex_capacity)?;
+        for vertex_index in start_vertex_indices.iter() {
+            reached_vertices.set_element(VectorElement::from_pair(*vertex_index, true))?;
+        }
+
+        let mut frontier = start_vertex_indices;
+        for _ in 0..k {
+            if frontier.is_empty() {
+                break;
+            }
+            let frontier_vector = structure_vector_from_indices(
+                self.graphblas_context_ref(),
+                vertex_capacity,
+                frontier,
+            )?;
+            let mut next_frontier = Vec::new();
+            for (_, _, structure_matrix) in traversed_edges.adjacency_matrices.iter() {
+                for &is_reversed in traversed_edges.direction.transpositions() {
+                    for &vertex_index in multiply_structure_vector_excluding(
+                        &frontier_vector,
+                        structure_matrix,
+                        is_reversed,
+                        &reached_vertices,
+                    )?
+                    .get_element_list()?
+                    .indices_ref()
+                    {
+                        reached_vertices
+                            .set_element(VectorElement::from_pair(vertex_index, true))?;
+                        next_frontier.push(vertex_index);
+                    }
+                }
+            }
+            frontier = next_frontier;
+        }
+
+        let mut vertices_at_k_hops =
+            SparseVector::<bool>::new(self.graphblas_context_ref(), &vertex_capacity)?;
+        for vertex_index in frontier {
+            vertices_at_k_hops.set_element(VectorElement::from_pair(vertex_index, true))?;
+        }
+        Ok((reached_vertices, vertices_at_k_hops))
+    }
+
+    // Each level is reached by one masked vector-matrix multiplication per edge type and direction.
+    // The complement of the visited vertices masks the product, so visited vertices are never reached again.
+    // Vertices stored in excluded_vertices are treated as visited, and are therefore never reached.
+    pub(crate) fn breadth_first_search_over(
+        &self,
+        start_vertex_indices: Vec<ElementIndex>,
+        traversed_edges: &TraversedEdges,
+        excluded_vertices: Option<&SparseVector<bool>>,
+    ) -> Result<BreadthFirstSearchResult<'_>, GraphComputingError> {
+        let vertex_capacity = self.vertex_capacity()?;
+        let mut levels = HashMap::new();
+        let mut parent_edges = HashMap::new();
+        let mut visited_vertices = match excluded_vertices {
+            Some(excluded_vertices) => excluded_vertices.clone(),
+            None => SparseVector::<bool>::new(self.graphblas_context_ref(), &vertex_capacity)?,
+        };
+
+        let mut frontier = Vec::new();
+        for vertex_index in start_vertex_indices {
+            if levels.insert(vertex_index, 0).is_none() {
+                visited_vertices.set_element(VectorElement::from_pair(vertex_index, true))?;
+                frontier.push(vertex_index);
+            }
+        }
+
+        let mut level = 0;
+        while !frontier.is_empty() {
+            level += 1;
+            let frontier_vector = structure_vector_from_indices(
+                self.graphblas_context_ref(),
+                vertex_capacity,
+                frontier.clone(),
+            )?;
+
+            let mut next_frontier = Vec::new();
+            for (edge_type_index, adjacency_matrix, structure_matrix) in
+                traversed_edges.adjacency_matrices.iter()
+            {
+                for &is_reversed in traversed_edges.direction.transpositions() {
+                    let reached_vertex_indices = multiply_structure_vector_excluding(
+                        &frontier_vector,
+                        structure_matrix,
+                        is_reversed,
+                        &visited_vertices,
+                    )?
+                    .get_element_list()?
+                    .indices_ref()
+                    .to_vec();
+                    if reached_vertex_indices.is_empty() {
+                        continue;
+                    }
+
+                    // Only the edges into the reached vertices qualify as parent edges
+                    let candidate_parent_edges = if is_reversed {
+                        adjacency_matrix
+                            .get_edge_coordinates_between(&reached_vertex_indices, &frontier)?
+                    } else {
+                        adjacency_matrix
+                            .get_edge_coordinates_between(&frontier, &reached_vertex_indices)?
+                    };
+                    for edge_coordinate in candidate_parent_edges {
+                        let (parent_vertex_index, vertex_index) = if is_reversed {
+                            (edge_coordinate.column_index(), edge_coordinate.row_index())
+                        } else {
+                            (edge_coordinate.row_index(), edge_coordinate.column_index())
+                        };
+                        if let Entry::Vacant(entry) = parent_edges.entry(vertex_index) {
+                            entry.insert(ParentEdge {
+                                parent_vertex_index,
+                                edge_type_index: *edge_type_index,
+                                is_reversed,
+                            });
+                            levels.insert(vertex_index, level);
+                            next_frontier.push(vertex_index);
+                        }
+                    }
+                }
+            }
+
+            for &vertex_index in next_frontier.iter() {
+                visited_vertices.set_element(VectorElement::from_pair(vertex_index, true))?;
+            }
+            frontier = next_frontier;
+        }
+
+        Ok(BreadthFirstSearchResult {
+            graph: self,
+            levels,
+            parent_edges,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::{GraphComputingErrorType, UserErrorType};
+    use crate::operations::add_edge::AddEdge;
+    use crate::operations::select_vertex::SelectVertex;
+    use crate::tests::{edge, graph_with_vertices};
+
+    // a -t-> b -t-> d -t-> e -x-> f, a -t-> c -t-> d, g
+    fn graph_with_two_edge_types() -> Graph {
+        let mut graph = graph_with_vertices(&["a", "b", "c", "d", "e", "f", "g"]);
+        graph
+            .add_edge_and_edge_type_using_keys(edge("a", "t", "b"))
+            .unwrap();
+        for (from_vertex, to_vertex) in [("a", "c"), ("b", "d"), ("c", "d"), ("d", "e")] {
+            graph
+                .add_edge_using_keys(edge(from_vertex, "t", to_vertex))
+                .unwrap();
+        }
+        graph
+            .add_edge_and_edge_type_using_keys(edge("e", "x", "f"))
+            .unwrap();
+        graph
+    }
+
+    #[test]
+    fn levels_and_parents_of_outgoing_edges() {
+        let graph = graph_with_two_edge_types();
+        let result = graph
+            .breadth_first_search_from_vertex_key("a", &["t"], TraversalDirection::Outgoing)
+            .unwrap();
+
+        assert_eq!(
+            result.levels().unwrap(),
+            vec![("a", 0), ("b", 1), ("c", 1), ("d", 2), ("e", 3)]
+        );
+        assert_eq!(result.level("f"), None);
+        assert_eq!(result.parent("a").unwrap(), None);
+        assert_eq!(result.parent("d").unwrap(), Some("b"));
+        assert_eq!(result.parent_edge("e").unwrap(), Some(edge("d", "t", "e")));
+        assert_eq!(
+            result
+                .vertices_at_level(1)
+                .unwrap()
+                .vertex_keys_ref()
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn follow_multiple_edge_types() {
+        let graph = graph_with_two_edge_types();
+        let result = graph
+            .breadth_first_search_from_vertex_key("a", &["t", "x"], TraversalDirection::Outgoing)
+            .unwrap();
+
+        assert_eq!(result.level("f"), Some(4));
+        assert_eq!(result.parent_edge("f").unwrap(), Some(edge("e", "x", "f")));
+        assert_eq!(
+            result
+                .reached_vertices()
+                .unwrap()
+                .vertex_keys_ref()
+                .unwrap()
+                .len(),
+            6
+        );
+    }
+
+    #[test]
+    fn follow_incoming_edges() {
+        let graph = graph_with_two_edge_types();
+        let result = graph
+            .breadth_first_search_from_vertex_key("e", &["t"], TraversalDirection::Incoming)
+            .unwrap();
+
+        assert_eq!(
+            result.levels().unwrap(),
+            vec![("e", 0), ("d", 1), ("b", 2), ("c", 2), ("a", 3)]
+        );
+        assert_eq!(result.parent("b").unwrap(), Some("d"));
+        assert_eq!(result.parent_edge("b").unwrap(), Some(edge("b", "t", "d")));
+    }
+
+    #[test]
+    fn follow_edges_in_both_directions_from_selection() {
+        let graph = graph_with_two_edge_types();
+        let mut vertex_selection = graph.select_vertex_by_key("b").unwrap();
+        vertex_selection
+            .vertex_mask_mut_ref()
+            .set_element(VectorElement::from_pair(
+                graph
+                    .vertex_key_ref_to_vertex_index_ref("f")
+                    .unwrap()
+                    .index(),
+                true,
+            ))
+            .unwrap();
+        let result = graph
+            .breadth_first_search_from_vertex_selection(
+                &vertex_selection,
+                &["t", "x"],
+                TraversalDirection::Both,
+            )
+            .unwrap();
+
+        assert_eq!(
+            result.levels().unwrap(),
+            vec![("b", 0), ("f", 0), ("a", 1), ("d", 1), ("e", 1), ("c", 2)]
+        );
+        assert_eq!(result.parent_edge("e").unwrap(), Some(edge("e", "x", "f")));
+        assert_eq!(result.level("g"), None);
+    }
+
+    #[test]
+    fn unknown_edge_type_or_vertex() {
+        let graph = graph_with_two_edge_types();
+        assert_eq!(
+            graph
+                .breadth_first_search_from_vertex_key("a", &["y"], TraversalDirection::Outgoing)
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::EdgeTypeDoesNotExist)
+        );
+        assert_eq!(
+            graph
+                .breadth_first_search_from_vertex_key("z", &["t"], TraversalDirection::Outgoing)
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::VertexKeyNotFound)
+        );
+    }
+}
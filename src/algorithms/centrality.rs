@@ -0,0 +1,485 @@
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+use hashbrown::{HashMap, HashSet};
+
+use graphblas_sparse_linear_algebra::value_types::sparse_vector::{
+    GetVectorElementList, SetVectorElement, SparseVector, VectorElement,
+};
+
+use crate::algorithms::breadth_first_search::TraversalDirection;
+use crate::error::GraphComputingError;
+use crate::graph::edge::adjacency_matrix::AdjacencyMatrix;
+use crate::graph::edge::boolean_matrix_operations;
+use crate::graph::edge::{EdgeTypeRef, EdgeWeightType};
+use crate::graph::graph::{ElementIndex, Graph, GraphTrait};
+use crate::graph::vertex::{VertexIndex, VertexKeyAndIndexConversion, VertexKeyRef, VertexValue};
+use crate::operations::selection::vertex_selection::VertexSelection;
+
+pub type CentralityScore = f64;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PageRankParameters {
+    /// The probability of following an edge rather than jumping to a random vertex
+    pub damping_factor: f64,
+    /// Iteration stops when the scores change by less than tolerance in total
+    pub tolerance: f64,
+    pub maximum_number_of_iterations: usize,
+}
+
+impl Default for PageRankParameters {
+    fn default() -> Self {
+        Self {
+            damping_factor: 0.85,
+            tolerance: 1e-9,
+            maximum_number_of_iterations: 100,
+        }
+    }
+}
+
+/// Every centrality measure treats the edges of all edge_types as a single relation:
+/// several edges between the same two vertices count once.
+pub trait Centrality {
+    /// The scores sum to 1. A vertex without outgoing edges distributes its score evenly over all vertices.
+    fn page_rank(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+        parameters: &PageRankParameters,
+    ) -> Result<CentralityScores<'_>, GraphComputingError>;
+
+    /// The number of vertices connected to a vertex in direction, divided by the number of other vertices.
+    /// With TraversalDirection::Both, the incoming and outgoing degree are added.
+    fn degree_centrality(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+        direction: TraversalDirection,
+    ) -> Result<CentralityScores<'_>, GraphComputingError>;
+
+    /// For each vertex, the sum over all pairs of other vertices of the fraction of shortest directed paths
+    /// between the pair that pass through the vertex. Paths are measured in number of edges, scores are not normalized.
+    fn betweenness_centrality(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<CentralityScores<'_>, GraphComputingError>;
+}
+
+impl Centrality for Graph {
+    // Each iteration is the plus-times product of the scores and the row-normalized adjacency matrix,
+    // i.e. the matrix-vector product of the transposed transition matrix and the scores.
+    fn page_rank(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+        parameters: &PageRankParameters,
+    ) -> Result<CentralityScores<'_>, GraphComputingError> {
+        let vertex_indices = self.valid_vertex_indices()?;
+        let adjacency_matrix = self.adjacency_matrix_over(edge_types)?;
+        let transition_matrix = boolean_matrix_operations::to_row_normalized_matrix(
+            adjacency_matrix.as_sparse_matrix(),
+        )?;
+        let vertices_with_outgoing_edges: HashSet<ElementIndex> = adjacency_matrix
+            .out_degrees()?
+            .get_element_list()?
+            .indices_ref()
+            .iter()
+            .copied()
+            .collect();
+        let dangling_vertex_indices: Vec<ElementIndex> = vertex_indices
+            .iter()
+            .copied()
+            .filter(|vertex_index| !vertices_with_outgoing_edges.contains(vertex_index))
+            .collect();
+        let number_of_vertices = vertex_indices.len() as f64;
+
+        let mut scores: HashMap<ElementIndex, CentralityScore> = vertex_indices
+            .iter()
+            .map(|&vertex_index| (vertex_index, 1.0 / number_of_vertices))
+            .collect();
+        for _ in 0..parameters.maximum_number_of_iterations {
+            let dangling_score: CentralityScore = dangling_vertex_indices
+                .iter()
+                .map(|vertex_index| scores[vertex_index])
+                .sum();
+            let (score_indices, score_values) = scores.iter().unzip();
+            let propagated_scores = boolean_matrix_operations::multiply_score_vector(
+                &boolean_matrix_operations::score_vector_from_elements(
+                    self.graphblas_context_ref(),
+                    self.vertex_capacity()?,
+                    score_indices,
+                    score_values,
+                )?,
+                &transition_matrix,
+            )?
+            .get_element_list()?;
+            let propagated_scores: HashMap<ElementIndex, CentralityScore> = propagated_scores
+                .indices_ref()
+                .iter()
+                .copied()
+                .zip(propagated_scores.values_ref().iter().copied())
+                .collect();
+
+            let base_score = (1.0 - parameters.damping_factor) / number_of_vertices
+                + parameters.damping_factor * dangling_score / number_of_vertices;
+            let mut change = 0.0;
+            for (vertex_index, score) in scores.iter_mut() {
+                let next_score = base_score
+                    + parameters.damping_factor
+                        * propagated_scores.get(vertex_index).unwrap_or(&0.0);
+                change += (next_score - *score).abs();
+                *score = next_score;
+            }
+            if change < parameters.tolerance {
+                break;
+            }
+        }
+        Ok(CentralityScores {
+            graph: self,
+            scores,
+        })
+    }
+
+    fn degree_centrality(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+        direction: TraversalDirection,
+    ) -> Result<CentralityScores<'_>, GraphComputingError> {
+        let vertex_indices = self.valid_vertex_indices()?;
+        let adjacency_matrix = self.adjacency_matrix_over(edge_types)?;
+        let mut degree_vectors = Vec::new();
+        if let TraversalDirection::Outgoing | TraversalDirection::Both = direction {
+            degree_vectors.push(adjacency_matrix.out_degrees()?);
+        }
+        if let TraversalDirection::Incoming | TraversalDirection::Both = direction {
+            degree_vectors.push(adjacency_matrix.in_degrees()?);
+        }
+
+        let mut degrees: HashMap<ElementIndex, u64> = vertex_indices
+            .iter()
+            .map(|&vertex_index| (vertex_index, 0))
+            .collect();
+        for degree_vector in degree_vectors {
+            let degree_elements = degree_vector.get_element_list()?;
+            for (vertex_index, degree) in degree_elements
+                .indices_ref()
+                .iter()
+                .zip(degree_elements.values_ref().iter())
+            {
+                *degrees.get_mut(vertex_index).unwrap() += degree;
+            }
+        }
+
+        let number_of_other_vertices = vertex_indices.len().saturating_sub(1).max(1) as f64;
+        Ok(CentralityScores {
+            graph: self,
+            scores: degrees
+                .into_iter()
+                .map(|(vertex_index, degree)| {
+                    (vertex_index, degree as f64 / number_of_other_vertices)
+                })
+                .collect(),
+        })
+    }
+
+    // Brandes' algorithm: a breadth-first search from every vertex counts the shortest paths to each vertex,
+    // after which the dependencies are accumulated in order of decreasing distance.
+    fn betweenness_centrality(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<CentralityScores<'_>, GraphComputingError> {
+        let vertex_indices = self.valid_vertex_indices()?;
+        let successors = self.successors_over(edge_types)?;
+        let mut scores: HashMap<ElementIndex, CentralityScore> = vertex_indices
+            .iter()
+            .map(|&vertex_index| (vertex_index, 0.0))
+            .collect();
+
+        for &source_vertex_index in vertex_indices.iter() {
+            let mut visit_order = Vec::new();
+            let mut predecessors: HashMap<ElementIndex, Vec<ElementIndex>> = HashMap::new();
+            let mut number_of_shortest_paths: HashMap<ElementIndex, f64> = HashMap::new();
+            let mut distances: HashMap<ElementIndex, usize> = HashMap::new();
+            number_of_shortest_paths.insert(source_vertex_index, 1.0);
+            distances.insert(source_vertex_index, 0);
+
+            let mut queue = VecDeque::from([source_vertex_index]);
+            while let Some(vertex_index) = queue.pop_front() {
+                visit_order.push(vertex_index);
+                let distance = distances[&vertex_index];
+                for &successor_vertex_index in successors.get(&vertex_index).into_iter().flatten() {
+                    if !distances.contains_key(&successor_vertex_index) {
+                        distances.insert(successor_vertex_index, distance + 1);
+                        queue.push_back(successor_vertex_index);
+                    }
+                    if distances[&successor_vertex_index] == distance + 1 {
+                        *number_of_shortest_paths
+                            .entry(successor_vertex_index)
+                            .or_default() += number_of_shortest_paths[&vertex_index];
+                        predecessors
+                            .entry(successor_vertex_index)
+                            .or_default()
+                            .push(vertex_index);
+                    }
+                }
+            }
+
+            let mut dependencies: HashMap<ElementIndex, f64> = HashMap::new();
+            for &vertex_index in visit_order.iter().rev() {
+                let dependency = dependencies.get(&vertex_index).copied().unwrap_or(0.0);
+                for &predecessor_vertex_index in
+                    predecessors.get(&vertex_index).into_iter().flatten()
+                {
+                    *dependencies.entry(predecessor_vertex_index).or_default() +=
+                        number_of_shortest_paths[&predecessor_vertex_index]
+                            / number_of_shortest_paths[&vertex_index]
+                            * (1.0 + dependency);
+                }
+                if vertex_index != source_vertex_index {
+                    *scores.get_mut(&vertex_index).unwrap() += dependency;
+                }
+            }
+        }
+        Ok(CentralityScores {
+            graph: self,
+            scores,
+        })
+    }
+}
+
+impl Graph {
+    // The edges of all edge_types in a single boolean adjacency matrix.
+    fn adjacency_matrix_over(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<AdjacencyMatrix, GraphComputingError> {
+        let mut adjacency_matrix = AdjacencyMatrix::new(
+            self.graphblas_context_ref(),
+            edge_types.join(", "),
+            self.vertex_capacity()?,
+            EdgeWeightType::Boolean,
+        )?;
+        for edge_type in edge_types {
+            adjacency_matrix.add_edges(
+                self.get_edge_adjacency_matrix_ref(edge_type)?
+                    .as_sparse_matrix(),
+            )?;
+        }
+        Ok(adjacency_matrix)
+    }
+
+    // The distinct vertices that each vertex has an edge of any of edge_types to, in ascending order.
+    // Vertices without outgoing edges are not in the map.
+    pub(crate) fn successors_over(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<HashMap<ElementIndex, Vec<ElementIndex>>, GraphComputingError> {
+        let mut successors: HashMap<ElementIndex, HashSet<ElementIndex>> = HashMap::new();
+        for edge_type in edge_types {
+            for coordinate in self
+                .get_edge_adjacency_matrix_ref(edge_type)?
+                .get_edge_coordinates()?
+            {
+                successors
+                    .entry(coordinate.row_index())
+                    .or_default()
+                    .insert(coordinate.column_index());
+            }
+        }
+        Ok(successors
+            .into_iter()
+            .map(|(vertex_index, vertex_successors)| {
+                let mut vertex_successors: Vec<ElementIndex> =
+                    vertex_successors.into_iter().collect();
+                vertex_successors.sort_unstable();
+                (vertex_index, vertex_successors)
+            })
+            .collect())
+    }
+}
+
+/// A score for every vertex in the graph.
+#[derive(Clone, Debug)]
+pub struct CentralityScores<'g> {
+    graph: &'g Graph,
+    scores: HashMap<ElementIndex, CentralityScore>,
+}
+
+impl<'g> CentralityScores<'g> {
+    /// None if there is no vertex with vertex_key.
+    pub fn score(&self, vertex_key: &VertexKeyRef) -> Option<CentralityScore> {
+        let vertex_index = self
+            .graph
+            .vertex_key_to_vertex_index_map_ref()
+            .get(vertex_key)?;
+        self.scores.get(vertex_index.index_ref()).copied()
+    }
+
+    /// The score of every vertex, from the highest to the lowest score. Equal scores are ordered by vertex key.
+    pub fn ranked(&self) -> Result<Vec<(&'g VertexKeyRef, CentralityScore)>, GraphComputingError> {
+        let mut ranked_scores = Vec::with_capacity(self.scores.len());
+        for (&vertex_index, &score) in self.scores.iter() {
+            ranked_scores.push((
+                self.graph
+                    .vertex_index_to_vertex_key_ref(VertexIndex::new(vertex_index))?,
+                score,
+            ));
+        }
+        ranked_scores.sort_by(|(vertex_key_a, score_a), (vertex_key_b, score_b)| {
+            score_b
+                .partial_cmp(score_a)
+                .unwrap_or(Ordering::Equal)
+                .then(vertex_key_a.cmp(vertex_key_b))
+        });
+        Ok(ranked_scores)
+    }
+
+    /// Selects the number_of_vertices highest ranked vertices.
+    pub fn select_highest_ranked(
+        &self,
+        number_of_vertices: usize,
+    ) -> Result<VertexSelection<'g>, GraphComputingError> {
+        let mut vertex_mask = SparseVector::<bool>::new(
+            self.graph.graphblas_context_ref(),
+            &self.graph.vertex_capacity()?,
+        )?;
+        for (vertex_key, _) in self.ranked()?.into_iter().take(number_of_vertices) {
+            let vertex_index = self.graph.existing_vertex_index(vertex_key)?;
+            vertex_mask.set_element(VectorElement::from_pair(vertex_index, true))?;
+        }
+        VertexSelection::new(self.graph, vertex_mask)
+    }
+
+    /// The index of every vertex with its score as value, ordered by vertex index.
+    /// Write the scores back with UpdateVertex::update_vertex_value_by_index(), which keeps the properties of the vertices.
+    pub fn to_vertex_indices_with_score_values(&self) -> Vec<(VertexIndex, VertexValue)> {
+        let mut vertex_values: Vec<(VertexIndex, VertexValue)> = self
+            .scores
+            .iter()
+            .map(|(&vertex_index, &score)| (VertexIndex::new(vertex_index), score.into()))
+            .collect();
+        vertex_values.sort_unstable_by_key(|(vertex_index, _)| vertex_index.index());
+        vertex_values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::{GraphComputingErrorType, UserErrorType};
+    use crate::operations::add_edge::AddEdge;
+    use crate::operations::read_vertex_value::ReadVertexValue;
+    use crate::operations::update_vertex::UpdateVertex;
+    use crate::tests::{edge, graph_with_vertices};
+
+    // a, b and c reference d, d references e, a also refers to d by "cites"
+    fn specification_graph() -> Graph {
+        let mut graph = graph_with_vertices(&["a", "b", "c", "d", "e"]);
+        graph
+            .add_edge_and_edge_type_using_keys(edge("a", "references", "d"))
+            .unwrap();
+        for (from_vertex, to_vertex) in [("b", "d"), ("c", "d"), ("d", "e")] {
+            graph
+                .add_edge_using_keys(edge(from_vertex, "references", to_vertex))
+                .unwrap();
+        }
+        graph
+            .add_edge_and_edge_type_using_keys(edge("a", "cites", "d"))
+            .unwrap();
+        graph
+    }
+
+    fn assert_close(score: Option<CentralityScore>, expected_score: CentralityScore) {
+        assert!((score.unwrap() - expected_score).abs() < 1e-6);
+    }
+
+    #[test]
+    fn page_rank_of_referenced_vertices() {
+        let graph = specification_graph();
+        let scores = graph
+            .page_rank(&["references", "cites"], &PageRankParameters::default())
+            .unwrap();
+
+        let total_score: f64 = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|vertex_key| scores.score(vertex_key).unwrap())
+            .sum();
+        assert_close(Some(total_score), 1.0);
+        let ranked_vertex_keys: Vec<&str> = scores
+            .ranked()
+            .unwrap()
+            .into_iter()
+            .map(|(vertex_key, _)| vertex_key)
+            .collect();
+        assert_eq!(ranked_vertex_keys, vec!["e", "d", "a", "b", "c"]);
+        assert_eq!(scores.score("z"), None);
+
+        let mut selected_vertex_keys: Vec<String> = scores
+            .select_highest_ranked(2)
+            .unwrap()
+            .vertex_keys_ref()
+            .unwrap()
+            .into_iter()
+            .map(String::from)
+            .collect();
+        selected_vertex_keys.sort();
+        assert_eq!(selected_vertex_keys, vec!["d", "e"]);
+    }
+
+    #[test]
+    fn degree_centrality_by_direction() {
+        let graph = specification_graph();
+        let scores = graph
+            .degree_centrality(&["references", "cites"], TraversalDirection::Incoming)
+            .unwrap();
+        assert_close(scores.score("d"), 0.75);
+        assert_close(scores.score("a"), 0.0);
+
+        let scores = graph
+            .degree_centrality(&["references"], TraversalDirection::Both)
+            .unwrap();
+        assert_close(scores.score("d"), 1.0);
+        assert_close(scores.score("a"), 0.25);
+
+        assert_eq!(
+            graph
+                .degree_centrality(&["extends"], TraversalDirection::Outgoing)
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::EdgeTypeDoesNotExist)
+        );
+    }
+
+    #[test]
+    fn betweenness_centrality_of_intermediate_vertex() {
+        let graph = specification_graph();
+        let scores = graph.betweenness_centrality(&["references"]).unwrap();
+        // d is on the shortest paths from a, b and c to e
+        assert_close(scores.score("d"), 3.0);
+        assert_close(scores.score("a"), 0.0);
+        assert_close(scores.score("e"), 0.0);
+    }
+
+    #[test]
+    fn write_scores_as_vertex_values() {
+        let mut graph = specification_graph();
+        graph
+            .update_vertex_property("d", String::from("status"), String::from("approved").into())
+            .unwrap();
+        let vertex_values = graph
+            .betweenness_centrality(&["references"])
+            .unwrap()
+            .to_vertex_indices_with_score_values();
+        for (vertex_index, vertex_value) in vertex_values {
+            graph
+                .update_vertex_value_by_index(vertex_index, vertex_value)
+                .unwrap();
+        }
+        assert_eq!(
+            graph.vertex_value(&String::from("d")).unwrap(),
+            &3.0f64.into()
+        );
+        assert_eq!(
+            graph.vertex_property(&String::from("d"), "status").unwrap(),
+            Some(&String::from("approved").into())
+        );
+    }
+}
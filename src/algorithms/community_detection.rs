@@ -0,0 +1,224 @@
+use hashbrown::{HashMap, HashSet};
+
+use crate::algorithms::connected_components::{ComponentId, Components};
+use crate::error::GraphComputingError;
+use crate::graph::edge::{DirectedEdgeDefinedByKeys, EdgeTypeRef};
+use crate::graph::graph::{ElementIndex, Graph};
+use crate::graph::vertex::{Vertex, VertexKey};
+use crate::operations::add_edge::AddEdge;
+use crate::operations::add_edge_type::AddEdgeType;
+use crate::operations::add_vertex::AddVertex;
+use crate::operations::transaction::Transaction;
+
+/// A partition of the vertices into communities, with the same interface as connected components.
+pub type Communities<'g> = Components<'g>;
+
+pub trait CommunityDetection {
+    /// Groups vertices that are densely connected by edges of edge_types, regardless of their direction.
+    /// Starting with a community per vertex, each vertex in turn joins the community that is most frequent among its neighbours,
+    /// until no vertex changes community, or after maximum_number_of_iterations.
+    /// Ties are broken in favour of the current community, and otherwise of the community of the vertex with the highest index,
+    /// such that the result is deterministic.
+    fn label_propagation(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+        maximum_number_of_iterations: usize,
+    ) -> Result<Communities<'_>, GraphComputingError>;
+
+    /// Adds a vertex for each community, with key community_key_prefix followed by the community id and the community id as value,
+    /// and an edge of member_of_edge_type from each vertex in community_ids to its community.
+    /// Adds member_of_edge_type if it does not exist yet. Either all or none of the communities are added.
+    /// Returns the keys of the community vertices, ordered by community id.
+    fn add_communities(
+        &mut self,
+        community_ids: &[(VertexKey, ComponentId)],
+        community_key_prefix: &str,
+        member_of_edge_type: &EdgeTypeRef,
+    ) -> Result<Vec<VertexKey>, GraphComputingError>;
+}
+
+impl CommunityDetection for Graph {
+    fn label_propagation(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+        maximum_number_of_iterations: usize,
+    ) -> Result<Communities<'_>, GraphComputingError> {
+        let mut neighbours: HashMap<ElementIndex, HashSet<ElementIndex>> = HashMap::new();
+        for (vertex_index, successors) in self.successors_over(edge_types)? {
+            for successor_vertex_index in successors {
+                if successor_vertex_index != vertex_index {
+                    neighbours
+                        .entry(vertex_index)
+                        .or_default()
+                        .insert(successor_vertex_index);
+                    neighbours
+                        .entry(successor_vertex_index)
+                        .or_default()
+                        .insert(vertex_index);
+                }
+            }
+        }
+
+        let vertex_indices = self.valid_vertex_indices()?;
+        let mut labels: HashMap<ElementIndex, ElementIndex> = vertex_indices
+            .iter()
+            .map(|&vertex_index| (vertex_index, vertex_index))
+            .collect();
+        for _ in 0..maximum_number_of_iterations {
+            let mut is_changed = false;
+            for vertex_index in vertex_indices.iter() {
+                let mut label_frequencies: HashMap<ElementIndex, usize> = HashMap::new();
+                for neighbour_vertex_index in neighbours.get(vertex_index).into_iter().flatten() {
+                    *label_frequencies
+                        .entry(labels[neighbour_vertex_index])
+                        .or_default() += 1;
+                }
+                let highest_frequency = match label_frequencies.values().max() {
+                    None => continue,
+                    Some(&frequency) => frequency,
+                };
+
+                let current_label = labels[vertex_index];
+                if label_frequencies.get(&current_label) == Some(&highest_frequency) {
+                    continue;
+                }
+                let new_label = label_frequencies
+                    .into_iter()
+                    .filter(|(_, frequency)| *frequency == highest_frequency)
+                    .map(|(label, _)| label)
+                    .max()
+                    .unwrap();
+                labels.insert(*vertex_index, new_label);
+                is_changed = true;
+            }
+            if !is_changed {
+                break;
+            }
+        }
+        Ok(Components::from_labels(self, &labels))
+    }
+
+    fn add_communities(
+        &mut self,
+        community_ids: &[(VertexKey, ComponentId)],
+        community_key_prefix: &str,
+        member_of_edge_type: &EdgeTypeRef,
+    ) -> Result<Vec<VertexKey>, GraphComputingError> {
+        let mut distinct_community_ids: Vec<ComponentId> = community_ids
+            .iter()
+            .map(|(_, community_id)| *community_id)
+            .collect::<HashSet<ComponentId>>()
+            .into_iter()
+            .collect();
+        distinct_community_ids.sort_unstable();
+
+        self.transaction(|graph| {
+            graph.add_new_edge_type_or_return_index(member_of_edge_type.to_owned())?;
+            let mut community_vertex_keys = Vec::with_capacity(distinct_community_ids.len());
+            for community_id in distinct_community_ids {
+                let community_vertex_key = format!("{}{}", community_key_prefix, community_id);
+                graph.add_new_vertex(Vertex::new(
+                    community_vertex_key.clone(),
+                    (community_id as u64).into(),
+                ))?;
+                community_vertex_keys.push(community_vertex_key);
+            }
+            for (vertex_key, community_id) in community_ids.iter() {
+                graph.add_edge_using_keys(DirectedEdgeDefinedByKeys::new(
+                    vertex_key.to_owned(),
+                    member_of_edge_type.to_owned(),
+                    format!("{}{}", community_key_prefix, community_id),
+                ))?;
+            }
+            Ok(community_vertex_keys)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::{GraphComputingErrorType, UserErrorType};
+    use crate::graph::graph::GraphTrait;
+    use crate::operations::read_edge::ReadEdge;
+    use crate::tests::{edge, graph_with_vertices};
+
+    // Two groups of four requirements that all relate to each other, connected by d -> e, and the isolated i
+    fn modular_requirements() -> Graph {
+        let mut graph = graph_with_vertices(&["a", "b", "c", "d", "e", "f", "g", "h", "i"]);
+        graph
+            .add_edge_and_edge_type_using_keys(edge("d", "relates_to", "e"))
+            .unwrap();
+        for group in [["a", "b", "c", "d"], ["e", "f", "g", "h"]] {
+            for (position, from_vertex) in group.iter().enumerate() {
+                for to_vertex in group[position + 1..].iter() {
+                    graph
+                        .add_edge_using_keys(edge(from_vertex, "relates_to", to_vertex))
+                        .unwrap();
+                }
+            }
+        }
+        graph
+    }
+
+    #[test]
+    fn detect_communities() {
+        let graph = modular_requirements();
+        let communities = graph.label_propagation(&["relates_to"], 10).unwrap();
+
+        assert_eq!(communities.number_of_components(), 3);
+        assert_eq!(
+            communities.component_ids().unwrap(),
+            vec![
+                ("a", 0),
+                ("b", 0),
+                ("c", 0),
+                ("d", 0),
+                ("e", 1),
+                ("f", 1),
+                ("g", 1),
+                ("h", 1),
+                ("i", 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn store_communities_with_member_of_edges() {
+        let mut graph = modular_requirements();
+        let community_ids: Vec<(VertexKey, ComponentId)> = graph
+            .label_propagation(&["relates_to"], 10)
+            .unwrap()
+            .component_ids()
+            .unwrap()
+            .into_iter()
+            .map(|(vertex_key, community_id)| (vertex_key.to_owned(), community_id))
+            .collect();
+
+        let community_vertex_keys = graph
+            .add_communities(&community_ids, "module_", "member_of")
+            .unwrap();
+        assert_eq!(
+            community_vertex_keys,
+            vec!["module_0", "module_1", "module_2"]
+        );
+        assert_eq!(graph.number_of_vertices().unwrap(), 12);
+        assert!(graph
+            .is_key_defined_edge_in_graph(&edge("f", "member_of", "module_1"))
+            .unwrap());
+        assert!(!graph
+            .is_key_defined_edge_in_graph(&edge("f", "member_of", "module_0"))
+            .unwrap());
+
+        // The community vertices already exist, nothing is added
+        assert_eq!(
+            graph
+                .add_communities(&community_ids, "module_", "member_of")
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::VertexAlreadyExists)
+        );
+        assert_eq!(graph.number_of_vertices().unwrap(), 12);
+    }
+}
@@ -0,0 +1,327 @@
+use hashbrown::{HashMap, HashSet};
+
+use graphblas_sparse_linear_algebra::value_types::sparse_vector::{
+    SetVectorElement, SparseVector, VectorElement,
+};
+
+use crate::algorithms::breadth_first_search::TraversalDirection;
+use crate::error::{GraphComputingError, UserError, UserErrorType};
+use crate::graph::edge::EdgeTypeRef;
+use crate::graph::graph::{ElementIndex, Graph, GraphTrait};
+use crate::graph::vertex::{VertexIndex, VertexKeyAndIndexConversion, VertexKeyRef};
+use crate::operations::selection::vertex_selection::VertexSelection;
+
+pub type ComponentId = usize;
+
+pub trait ConnectedComponents {
+    /// Partitions the vertices into groups that are connected by edges of edge_types, regardless of their direction.
+    fn weakly_connected_components(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<Components<'_>, GraphComputingError>;
+
+    /// Partitions the vertices into groups in which every vertex reaches every other vertex by edges of edge_types.
+    /// A vertex that is not on a cycle forms a component by itself.
+    fn strongly_connected_components(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<Components<'_>, GraphComputingError>;
+}
+
+impl ConnectedComponents for Graph {
+    fn weakly_connected_components(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<Components<'_>, GraphComputingError> {
+        let traversed_edges = self.traversed_edges(edge_types, TraversalDirection::Both)?;
+        self.components(|pivot_vertex_index, assigned_vertices| {
+            Ok(self
+                .breadth_first_search_over(
+                    vec![pivot_vertex_index],
+                    &traversed_edges,
+                    Some(assigned_vertices),
+                )?
+                .reached_vertex_indices())
+        })
+    }
+
+    // Forward-backward algorithm: the strongly connected component of a pivot consists of
+    // the vertices that are both reached from, and reach, the pivot.
+    fn strongly_connected_components(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<Components<'_>, GraphComputingError> {
+        let forward_edges = self.traversed_edges(edge_types, TraversalDirection::Outgoing)?;
+        let backward_edges = self.traversed_edges(edge_types, TraversalDirection::Incoming)?;
+        self.components(|pivot_vertex_index, assigned_vertices| {
+            let reached_vertex_indices: HashSet<ElementIndex> = self
+                .breadth_first_search_over(
+                    vec![pivot_vertex_index],
+                    &forward_edges,
+                    Some(assigned_vertices),
+                )?
+                .reached_vertex_indices()
+                .into_iter()
+                .collect();
+            Ok(self
+                .breadth_first_search_over(
+                    vec![pivot_vertex_index],
+                    &backward_edges,
+                    Some(assigned_vertices),
+                )?
+                .reached_vertex_indices()
+                .into_iter()
+                .filter(|vertex_index| reached_vertex_indices.contains(vertex_index))
+                .collect())
+        })
+    }
+}
+
+impl Graph {
+    // Repeatedly takes the vertex with the lowest index that is not in a component yet as pivot,
+    // and assigns the vertices in the component of the pivot to a new component.
+    // Vertices in earlier components are excluded from the search for the next component.
+    // A path between two vertices of the same component never leaves the component,
+    // so excluding complete components does not affect the components found later.
+    fn components(
+        &self,
+        component_of_pivot: impl Fn(
+            ElementIndex,
+            &SparseVector<bool>,
+        ) -> Result<Vec<ElementIndex>, GraphComputingError>,
+    ) -> Result<Components<'_>, GraphComputingError> {
+        let mut component_ids = HashMap::new();
+        let mut assigned_vertices =
+            SparseVector::<bool>::new(self.graphblas_context_ref(), &self.vertex_capacity()?)?;
+        let mut number_of_components = 0;
+        for pivot_vertex_index in self.valid_vertex_indices()? {
+            if component_ids.contains_key(&pivot_vertex_index) {
+                continue;
+            }
+            for vertex_index in component_of_pivot(pivot_vertex_index, &assigned_vertices)? {
+                component_ids.insert(vertex_index, number_of_components);
+                assigned_vertices.set_element(VectorElement::from_pair(vertex_index, true))?;
+            }
+            number_of_components += 1;
+        }
+        Ok(Components {
+            graph: self,
+            component_ids,
+            number_of_components,
+        })
+    }
+}
+
+/// Component ids are consecutive from 0, in the order of the first vertex in each component.
+#[derive(Clone, Debug)]
+pub struct Components<'g> {
+    graph: &'g Graph,
+    component_ids: HashMap<ElementIndex, ComponentId>,
+    number_of_components: usize,
+}
+
+impl<'g> Components<'g> {
+    /// Vertices with the same label are in the same component. Labels are renumbered to consecutive component ids.
+    pub(crate) fn from_labels(
+        graph: &'g Graph,
+        labels: &HashMap<ElementIndex, ElementIndex>,
+    ) -> Self {
+        let mut vertex_indices: Vec<ElementIndex> = labels.keys().copied().collect();
+        vertex_indices.sort_unstable();
+
+        let mut component_ids_by_label = HashMap::new();
+        let mut component_ids = HashMap::with_capacity(vertex_indices.len());
+        for vertex_index in vertex_indices {
+            let number_of_components = component_ids_by_label.len();
+            let component_id = *component_ids_by_label
+                .entry(labels[&vertex_index])
+                .or_insert(number_of_components);
+            component_ids.insert(vertex_index, component_id);
+        }
+        Components {
+            graph,
+            component_ids,
+            number_of_components: component_ids_by_label.len(),
+        }
+    }
+
+    pub fn number_of_components(&self) -> usize {
+        self.number_of_components
+    }
+
+    /// None if there is no vertex with vertex_key.
+    pub fn component_id(&self, vertex_key: &VertexKeyRef) -> Option<ComponentId> {
+        let vertex_index = self
+            .graph
+            .vertex_key_to_vertex_index_map_ref()
+            .get(vertex_key)?;
+        self.component_ids.get(vertex_index.index_ref()).copied()
+    }
+
+    /// The component id of every vertex, ordered by component id.
+    pub fn component_ids(
+        &self,
+    ) -> Result<Vec<(&'g VertexKeyRef, ComponentId)>, GraphComputingError> {
+        let mut component_ids = Vec::with_capacity(self.component_ids.len());
+        for (&vertex_index, &component_id) in self.component_ids.iter() {
+            component_ids.push((
+                self.graph
+                    .vertex_index_to_vertex_key_ref(VertexIndex::new(vertex_index))?,
+                component_id,
+            ));
+        }
+        component_ids.sort_by(
+            |(vertex_key_a, component_id_a), (vertex_key_b, component_id_b)| {
+                component_id_a
+                    .cmp(component_id_b)
+                    .then(vertex_key_a.cmp(vertex_key_b))
+            },
+        );
+        Ok(component_ids)
+    }
+
+    pub(crate) fn component_id_by_index(&self, vertex_index: ElementIndex) -> Option<ComponentId> {
+        self.component_ids.get(&vertex_index).copied()
+    }
+
+    /// The vertex indices in the component, in ascending order.
+    pub(crate) fn vertex_indices_of_component(
+        &self,
+        component_id: ComponentId,
+    ) -> Vec<ElementIndex> {
+        let mut vertex_indices: Vec<ElementIndex> = self
+            .component_ids
+            .iter()
+            .filter(|(_, &vertex_component_id)| vertex_component_id == component_id)
+            .map(|(&vertex_index, _)| vertex_index)
+            .collect();
+        vertex_indices.sort_unstable();
+        vertex_indices
+    }
+
+    pub fn component(
+        &self,
+        component_id: ComponentId,
+    ) -> Result<VertexSelection<'g>, GraphComputingError> {
+        if component_id >= self.number_of_components {
+            return Err(UserError::new(
+                UserErrorType::IndexOutOfBounds,
+                format!(
+                    "Component id {} is out of bounds, there are {} components",
+                    component_id, self.number_of_components
+                ),
+                None,
+            )
+            .into());
+        }
+        let mut vertex_mask = SparseVector::<bool>::new(
+            self.graph.graphblas_context_ref(),
+            &self.graph.vertex_capacity()?,
+        )?;
+        for (&vertex_index, &vertex_component_id) in self.component_ids.iter() {
+            if vertex_component_id == component_id {
+                vertex_mask.set_element(VectorElement::from_pair(vertex_index, true))?;
+            }
+        }
+        VertexSelection::new(self.graph, vertex_mask)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::GraphComputingErrorType;
+    use crate::operations::add_edge::AddEdge;
+    use crate::operations::delete_vertex::DeleteVertex;
+    use crate::tests::{edge, graph_with_vertices};
+
+    // a -> b -> c -> a, c -> d, e -> f, g (isolated), h <- f via "other"
+    fn graph_with_clusters() -> Graph {
+        let mut graph = graph_with_vertices(&["a", "b", "c", "d", "e", "f", "g", "h"]);
+        graph
+            .add_edge_and_edge_type_using_keys(edge("a", "depends_on", "b"))
+            .unwrap();
+        for (from_vertex, to_vertex) in [("b", "c"), ("c", "a"), ("c", "d"), ("e", "f")] {
+            graph
+                .add_edge_using_keys(edge(from_vertex, "depends_on", to_vertex))
+                .unwrap();
+        }
+        graph
+            .add_edge_and_edge_type_using_keys(edge("f", "other", "h"))
+            .unwrap();
+        graph
+    }
+
+    fn component_keys(components: &Components, component_id: ComponentId) -> Vec<String> {
+        let mut vertex_keys: Vec<String> = components
+            .component(component_id)
+            .unwrap()
+            .vertex_keys_ref()
+            .unwrap()
+            .into_iter()
+            .map(String::from)
+            .collect();
+        vertex_keys.sort();
+        vertex_keys
+    }
+
+    #[test]
+    fn weakly_connected_components() {
+        let graph = graph_with_clusters();
+        let components = graph.weakly_connected_components(&["depends_on"]).unwrap();
+
+        assert_eq!(components.number_of_components(), 4);
+        assert_eq!(
+            components.component_ids().unwrap(),
+            vec![
+                ("a", 0),
+                ("b", 0),
+                ("c", 0),
+                ("d", 0),
+                ("e", 1),
+                ("f", 1),
+                ("g", 2),
+                ("h", 3)
+            ]
+        );
+        assert_eq!(component_keys(&components, 1), vec!["e", "f"]);
+
+        let components = graph
+            .weakly_connected_components(&["depends_on", "other"])
+            .unwrap();
+        assert_eq!(components.number_of_components(), 3);
+        assert_eq!(components.component_id("h"), Some(1));
+    }
+
+    #[test]
+    fn strongly_connected_components() {
+        let graph = graph_with_clusters();
+        let components = graph
+            .strongly_connected_components(&["depends_on"])
+            .unwrap();
+
+        assert_eq!(components.number_of_components(), 6);
+        assert_eq!(component_keys(&components, 0), vec!["a", "b", "c"]);
+        assert_eq!(components.component_id("d"), Some(1));
+        assert_ne!(components.component_id("e"), components.component_id("f"));
+        assert_eq!(components.component_id("z"), None);
+    }
+
+    #[test]
+    fn components_skip_deleted_vertices() {
+        let mut graph = graph_with_clusters();
+        graph
+            .delete_vertex_and_connected_edges_by_key(String::from("c"))
+            .unwrap();
+        let components = graph.weakly_connected_components(&["depends_on"]).unwrap();
+
+        assert_eq!(components.number_of_components(), 5);
+        assert_eq!(component_keys(&components, 0), vec!["a", "b"]);
+        assert_eq!(components.component_id("c"), None);
+        assert_eq!(
+            components.component(5).unwrap_err().error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::IndexOutOfBounds)
+        );
+    }
+}
@@ -0,0 +1,309 @@
+use hashbrown::HashMap;
+
+use crate::algorithms::breadth_first_search::TraversalDirection;
+use crate::algorithms::connected_components::{Components, ConnectedComponents};
+use crate::error::GraphComputingError;
+use crate::graph::edge::adjacency_matrix::AdjacencyMatrix;
+use crate::graph::edge::EdgeTypeRef;
+use crate::graph::graph::{ElementIndex, Graph};
+use crate::graph::vertex::{VertexIndex, VertexKey, VertexKeyAndIndexConversion};
+
+/// A cycle is reported as the keys of the vertices on it, in the direction of its edges.
+/// The first vertex is not repeated at the end, a self-loop is a cycle with a single vertex.
+pub type Cycle = Vec<VertexKey>;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CycleSearch {
+    /// Returns a single cycle, if there is one
+    FirstCycle,
+    /// Returns every elementary cycle (visiting each vertex at most once) with at most max_length edges.
+    /// The number of elementary cycles can grow exponentially with max_length.
+    ElementaryCycles { max_length: usize },
+}
+
+pub trait FindCycles {
+    fn has_cycle(&self, edge_type: &EdgeTypeRef) -> Result<bool, GraphComputingError>;
+
+    /// An empty result means that the edges of edge_type do not form a cycle (within the bound of cycle_search).
+    fn find_cycles(
+        &self,
+        edge_type: &EdgeTypeRef,
+        cycle_search: CycleSearch,
+    ) -> Result<Vec<Cycle>, GraphComputingError>;
+}
+
+impl FindCycles for Graph {
+    fn has_cycle(&self, edge_type: &EdgeTypeRef) -> Result<bool, GraphComputingError> {
+        Ok(!self
+            .find_cycles(edge_type, CycleSearch::FirstCycle)?
+            .is_empty())
+    }
+
+    // Every cycle lies within a strongly connected component
+    fn find_cycles(
+        &self,
+        edge_type: &EdgeTypeRef,
+        cycle_search: CycleSearch,
+    ) -> Result<Vec<Cycle>, GraphComputingError> {
+        let cycles = match cycle_search {
+            CycleSearch::FirstCycle => self.first_cycle(&[edge_type])?.into_iter().collect(),
+            CycleSearch::ElementaryCycles { max_length } => elementary_cycles(
+                self.get_edge_adjacency_matrix_ref(edge_type)?,
+                &self.strongly_connected_components(&[edge_type])?,
+                max_length,
+            )?,
+        };
+
+        let mut cycles_defined_by_keys = Vec::with_capacity(cycles.len());
+        for cycle in cycles {
+            cycles_defined_by_keys.push(self.cycle_to_vertex_keys(cycle)?);
+        }
+        Ok(cycles_defined_by_keys)
+    }
+}
+
+impl Graph {
+    // A self-loop, or else a shortest cycle through the first vertex of the first component with more than one vertex.
+    // The cycle may combine edges of different types.
+    pub(crate) fn first_cycle(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<Option<Vec<ElementIndex>>, GraphComputingError> {
+        let mut adjacency_matrices: Vec<&AdjacencyMatrix> = Vec::with_capacity(edge_types.len());
+        for edge_type in edge_types {
+            adjacency_matrices.push(self.get_edge_adjacency_matrix_ref(edge_type)?);
+        }
+        for adjacency_matrix in adjacency_matrices.iter() {
+            if let Some(self_loop) = adjacency_matrix
+                .get_edge_coordinates()?
+                .into_iter()
+                .find(|coordinate| coordinate.row_index() == coordinate.column_index())
+            {
+                return Ok(Some(vec![self_loop.row_index()]));
+            }
+        }
+
+        let components = self.strongly_connected_components(edge_types)?;
+        for component_id in 0..components.number_of_components() {
+            let component_vertex_indices = components.vertex_indices_of_component(component_id);
+            if component_vertex_indices.len() < 2 {
+                continue;
+            }
+
+            let pivot_vertex_index = component_vertex_indices[0];
+            let breadth_first_search = self.breadth_first_search_over(
+                vec![pivot_vertex_index],
+                &self.traversed_edges(edge_types, TraversalDirection::Outgoing)?,
+                None,
+            )?;
+            // The cycle closes with an edge from the predecessor of the pivot that is closest to the pivot
+            let mut closing_vertex_indices = Vec::new();
+            for adjacency_matrix in adjacency_matrices.iter() {
+                for coordinate in adjacency_matrix.get_edge_coordinates_between(
+                    &component_vertex_indices,
+                    &[pivot_vertex_index],
+                )? {
+                    closing_vertex_indices.push(coordinate.row_index());
+                }
+            }
+            let closing_vertex_index = closing_vertex_indices
+                .into_iter()
+                .min_by_key(|&vertex_index| breadth_first_search.level_by_index(vertex_index))
+                .unwrap();
+
+            let mut cycle = vec![closing_vertex_index];
+            while let Some((parent_vertex_index, _)) =
+                breadth_first_search.parent_by_index(*cycle.last().unwrap())
+            {
+                cycle.push(parent_vertex_index);
+            }
+            cycle.reverse();
+            return Ok(Some(cycle));
+        }
+        Ok(None)
+    }
+
+    pub(crate) fn cycle_to_vertex_keys(
+        &self,
+        cycle: Vec<ElementIndex>,
+    ) -> Result<Cycle, GraphComputingError> {
+        let mut vertex_keys = Vec::with_capacity(cycle.len());
+        for vertex_index in cycle {
+            vertex_keys.push(
+                self.vertex_index_to_vertex_key_ref(VertexIndex::new(vertex_index))?
+                    .to_owned(),
+            );
+        }
+        Ok(vertex_keys)
+    }
+}
+
+// Depth-first search for the cycles that start at, and contain no vertex with a lower index than, each vertex.
+// Every elementary cycle is therefore found exactly once, from its vertex with the lowest index.
+// Only edges within a strongly connected component can be on a cycle.
+fn elementary_cycles(
+    adjacency_matrix: &AdjacencyMatrix,
+    components: &Components,
+    max_length: usize,
+) -> Result<Vec<Vec<ElementIndex>>, GraphComputingError> {
+    let mut successors: HashMap<ElementIndex, Vec<ElementIndex>> = HashMap::new();
+    for coordinate in adjacency_matrix.get_edge_coordinates()? {
+        let (from_vertex_index, to_vertex_index) =
+            (coordinate.row_index(), coordinate.column_index());
+        if components.component_id_by_index(from_vertex_index)
+            == components.component_id_by_index(to_vertex_index)
+        {
+            successors
+                .entry(from_vertex_index)
+                .or_default()
+                .push(to_vertex_index);
+        }
+    }
+    let mut start_vertex_indices: Vec<ElementIndex> = successors.keys().copied().collect();
+    start_vertex_indices.sort_unstable();
+
+    let mut cycles = Vec::new();
+    for start_vertex_index in start_vertex_indices {
+        let mut path = vec![start_vertex_index];
+        extend_path_to_cycles(&successors, &mut path, max_length, &mut cycles);
+    }
+    Ok(cycles)
+}
+
+fn extend_path_to_cycles(
+    successors: &HashMap<ElementIndex, Vec<ElementIndex>>,
+    path: &mut Vec<ElementIndex>,
+    max_length: usize,
+    cycles: &mut Vec<Vec<ElementIndex>>,
+) {
+    let start_vertex_index = path[0];
+    let last_vertex_index = *path.last().unwrap();
+    for &successor_vertex_index in successors.get(&last_vertex_index).into_iter().flatten() {
+        if successor_vertex_index == start_vertex_index {
+            if path.len() <= max_length {
+                cycles.push(path.clone());
+            }
+        } else if successor_vertex_index > start_vertex_index
+            && path.len() < max_length
+            && !path.contains(&successor_vertex_index)
+        {
+            path.push(successor_vertex_index);
+            extend_path_to_cycles(successors, path, max_length, cycles);
+            path.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::{GraphComputingErrorType, UserErrorType};
+    use crate::graph::edge::DirectedEdgeDefinedByKeys;
+    use crate::operations::add_edge::AddEdge;
+    use crate::operations::add_edge_type::AddEdgeType;
+    use crate::tests::graph_with_vertices;
+
+    fn graph_with_edges(edges: &[(&str, &str)]) -> Graph {
+        let mut graph = graph_with_vertices(&["a", "b", "c", "d", "e"]);
+        graph.add_new_edge_type(String::from("refines")).unwrap();
+        for (from_vertex, to_vertex) in edges.iter() {
+            graph
+                .add_edge_using_keys(DirectedEdgeDefinedByKeys::new(
+                    String::from(*from_vertex),
+                    String::from("refines"),
+                    String::from(*to_vertex),
+                ))
+                .unwrap();
+        }
+        graph
+    }
+
+    fn keys(vertex_keys: &[&str]) -> Cycle {
+        vertex_keys
+            .iter()
+            .map(|vertex_key| String::from(*vertex_key))
+            .collect()
+    }
+
+    #[test]
+    fn acyclic_edge_type() {
+        let graph = graph_with_edges(&[("a", "b"), ("a", "c"), ("b", "d"), ("c", "d")]);
+        assert!(!graph.has_cycle("refines").unwrap());
+        assert!(graph
+            .find_cycles("refines", CycleSearch::ElementaryCycles { max_length: 5 })
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn find_first_cycle() {
+        // The shortest cycle through a is a -> b -> e -> a
+        let graph = graph_with_edges(&[
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "d"),
+            ("d", "a"),
+            ("b", "e"),
+            ("e", "a"),
+        ]);
+        assert!(graph.has_cycle("refines").unwrap());
+        assert_eq!(
+            graph
+                .find_cycles("refines", CycleSearch::FirstCycle)
+                .unwrap(),
+            vec![keys(&["a", "b", "e"])]
+        );
+
+        let graph = graph_with_edges(&[("a", "b"), ("c", "c")]);
+        assert_eq!(
+            graph
+                .find_cycles("refines", CycleSearch::FirstCycle)
+                .unwrap(),
+            vec![keys(&["c"])]
+        );
+    }
+
+    #[test]
+    fn enumerate_elementary_cycles_up_to_length() {
+        let graph = graph_with_edges(&[
+            ("a", "b"),
+            ("b", "a"),
+            ("b", "c"),
+            ("c", "a"),
+            ("c", "d"),
+            ("d", "b"),
+            ("e", "e"),
+        ]);
+
+        let mut cycles = graph
+            .find_cycles("refines", CycleSearch::ElementaryCycles { max_length: 3 })
+            .unwrap();
+        cycles.sort();
+        assert_eq!(
+            cycles,
+            vec![
+                keys(&["a", "b"]),
+                keys(&["a", "b", "c"]),
+                keys(&["b", "c", "d"]),
+                keys(&["e"])
+            ]
+        );
+
+        assert_eq!(
+            graph
+                .find_cycles("refines", CycleSearch::ElementaryCycles { max_length: 1 })
+                .unwrap(),
+            vec![keys(&["e"])]
+        );
+    }
+
+    #[test]
+    fn unknown_edge_type() {
+        let graph = graph_with_edges(&[]);
+        assert_eq!(
+            graph.has_cycle("depends_on").unwrap_err().error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::EdgeTypeDoesNotExist)
+        );
+    }
+}
@@ -1,3 +1,11 @@
-pub mod connected_vertices;
+pub mod breadth_first_search;
+pub mod centrality;
+pub mod community_detection;
+pub mod connected_components;
+pub mod find_cycles;
+pub mod shortest_path;
+pub mod single_source_shortest_path;
+pub mod topological_sort;
+pub mod triangles;
 
-// https://tinkerpop.apache.org/docs/3.5.1/reference/
\ No newline at end of file
+// https://tinkerpop.apache.org/docs/3.5.1/reference/
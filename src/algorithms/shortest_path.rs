@@ -0,0 +1,305 @@
+use hashbrown::HashMap;
+
+use crate::algorithms::breadth_first_search::{BreadthFirstSearch, TraversalDirection};
+use crate::error::GraphComputingError;
+use crate::graph::edge::adjacency_matrix::AdjacencyMatrix;
+use crate::graph::edge::{EdgeType, EdgeTypeIndex, EdgeTypeKeyAndIndexConversion, EdgeTypeRef};
+use crate::graph::graph::{ElementIndex, Graph};
+use crate::graph::vertex::{Vertex, VertexIndex, VertexKeyRef};
+
+/// A path along outgoing edges. The edge type at position i connects the vertices at positions i and i + 1.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VertexPath {
+    vertices: Vec<Vertex>,
+    edge_types: Vec<EdgeType>,
+}
+
+impl VertexPath {
+    pub fn vertices_ref(&self) -> &[Vertex] {
+        &self.vertices
+    }
+
+    pub fn edge_types_ref(&self) -> &[EdgeType] {
+        &self.edge_types
+    }
+
+    /// The number of edges on the path
+    pub fn length(&self) -> usize {
+        self.edge_types.len()
+    }
+}
+
+pub trait ShortestPath {
+    /// A path with the fewest edges from from_vertex_key to to_vertex_key, following outgoing edges of edge_types.
+    /// None if to_vertex_key is not reachable. If several paths are equally short, one of them is returned.
+    fn shortest_path(
+        &self,
+        from_vertex_key: &VertexKeyRef,
+        to_vertex_key: &VertexKeyRef,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<Option<VertexPath>, GraphComputingError>;
+
+    /// All paths with the fewest edges from from_vertex_key to to_vertex_key, following outgoing edges of edge_types.
+    /// Paths that visit the same vertices by edges of different types are different paths.
+    fn all_shortest_paths(
+        &self,
+        from_vertex_key: &VertexKeyRef,
+        to_vertex_key: &VertexKeyRef,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<Vec<VertexPath>, GraphComputingError>;
+}
+
+impl ShortestPath for Graph {
+    fn shortest_path(
+        &self,
+        from_vertex_key: &VertexKeyRef,
+        to_vertex_key: &VertexKeyRef,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<Option<VertexPath>, GraphComputingError> {
+        let breadth_first_search = self.breadth_first_search_from_vertex_key(
+            from_vertex_key,
+            edge_types,
+            TraversalDirection::Outgoing,
+        )?;
+        let to_vertex_index = self.existing_vertex_index(to_vertex_key)?;
+        if breadth_first_search
+            .level_by_index(to_vertex_index)
+            .is_none()
+        {
+            return Ok(None);
+        }
+
+        let mut vertex_indices = vec![to_vertex_index];
+        let mut edge_type_indices = Vec::new();
+        while let Some((parent_vertex_index, edge_type_index)) =
+            breadth_first_search.parent_by_index(*vertex_indices.last().unwrap())
+        {
+            vertex_indices.push(parent_vertex_index);
+            edge_type_indices.push(edge_type_index);
+        }
+        vertex_indices.reverse();
+        edge_type_indices.reverse();
+        Ok(Some(self.vertex_path_from_indices(
+            vertex_indices,
+            edge_type_indices,
+        )?))
+    }
+
+    fn all_shortest_paths(
+        &self,
+        from_vertex_key: &VertexKeyRef,
+        to_vertex_key: &VertexKeyRef,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<Vec<VertexPath>, GraphComputingError> {
+        let breadth_first_search = self.breadth_first_search_from_vertex_key(
+            from_vertex_key,
+            edge_types,
+            TraversalDirection::Outgoing,
+        )?;
+        let to_vertex_index = self.existing_vertex_index(to_vertex_key)?;
+        let to_vertex_level = match breadth_first_search.level_by_index(to_vertex_index) {
+            None => return Ok(Vec::new()),
+            Some(level) => level,
+        };
+
+        let mut traversed_adjacency_matrices: Vec<(EdgeTypeIndex, &AdjacencyMatrix)> =
+            Vec::with_capacity(edge_types.len());
+        for edge_type in edge_types {
+            traversed_adjacency_matrices.push((
+                *self.edge_type_ref_to_edge_type_index_ref(edge_type)?,
+                self.get_edge_adjacency_matrix_ref(edge_type)?,
+            ));
+        }
+
+        // Partial paths grow backwards from to_vertex, one level at a time.
+        // Every vertex on a shortest path at level l is reached from a vertex at level l - 1.
+        let mut partial_paths: Vec<(Vec<ElementIndex>, Vec<EdgeTypeIndex>)> =
+            vec![(vec![to_vertex_index], Vec::new())];
+        for level in (1..=to_vertex_level).rev() {
+            let vertex_indices_at_previous_level =
+                breadth_first_search.vertex_indices_at_level(level - 1);
+            let mut predecessors: HashMap<ElementIndex, Vec<(ElementIndex, EdgeTypeIndex)>> =
+                HashMap::new();
+            let mut extended_partial_paths = Vec::new();
+            for (vertex_indices, edge_type_indices) in partial_paths {
+                let vertex_index = *vertex_indices.last().unwrap();
+                if !predecessors.contains_key(&vertex_index) {
+                    let mut vertex_predecessors = Vec::new();
+                    for (edge_type_index, adjacency_matrix) in traversed_adjacency_matrices.iter() {
+                        for edge_coordinate in adjacency_matrix.get_edge_coordinates_between(
+                            &vertex_indices_at_previous_level,
+                            &[vertex_index],
+                        )? {
+                            vertex_predecessors
+                                .push((edge_coordinate.row_index(), *edge_type_index));
+                        }
+                    }
+                    predecessors.insert(vertex_index, vertex_predecessors);
+                }
+
+                for &(predecessor_vertex_index, edge_type_index) in
+                    predecessors[&vertex_index].iter()
+                {
+                    let mut extended_vertex_indices = vertex_indices.clone();
+                    extended_vertex_indices.push(predecessor_vertex_index);
+                    let mut extended_edge_type_indices = edge_type_indices.clone();
+                    extended_edge_type_indices.push(edge_type_index);
+                    extended_partial_paths
+                        .push((extended_vertex_indices, extended_edge_type_indices));
+                }
+            }
+            partial_paths = extended_partial_paths;
+        }
+
+        let mut shortest_paths = Vec::with_capacity(partial_paths.len());
+        for (mut vertex_indices, mut edge_type_indices) in partial_paths {
+            vertex_indices.reverse();
+            edge_type_indices.reverse();
+            shortest_paths.push(self.vertex_path_from_indices(vertex_indices, edge_type_indices)?);
+        }
+        Ok(shortest_paths)
+    }
+}
+
+impl Graph {
+    fn vertex_path_from_indices(
+        &self,
+        vertex_indices: Vec<ElementIndex>,
+        edge_type_indices: Vec<EdgeTypeIndex>,
+    ) -> Result<VertexPath, GraphComputingError> {
+        let mut vertices = Vec::with_capacity(vertex_indices.len());
+        for vertex_index in vertex_indices {
+            vertices.push(
+                self.vertex_store_ref()
+                    .get_ref(VertexIndex::new(vertex_index))?
+                    .clone(),
+            );
+        }
+        let mut edge_types = Vec::with_capacity(edge_type_indices.len());
+        for edge_type_index in edge_type_indices {
+            edge_types.push(
+                self.edge_type_index_to_edge_type_ref(edge_type_index)?
+                    .to_owned(),
+            );
+        }
+        Ok(VertexPath {
+            vertices,
+            edge_types,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::{GraphComputingErrorType, UserErrorType};
+    use crate::operations::add_edge::AddEdge;
+    use crate::tests::{edge, graph_with_vertices};
+
+    fn vertex_keys(path: &VertexPath) -> Vec<&str> {
+        path.vertices_ref()
+            .iter()
+            .map(|vertex| vertex.key_ref())
+            .collect()
+    }
+
+    // a -requires-> b -requires-> d -requires-> e, a -requires-> c -requires-> d,
+    // a -refines-> c, e -refines-> f, g
+    fn requirements_graph() -> Graph {
+        let mut graph = graph_with_vertices(&["a", "b", "c", "d", "e", "f", "g"]);
+        graph
+            .add_edge_and_edge_type_using_keys(edge("a", "requires", "b"))
+            .unwrap();
+        for (from_vertex, to_vertex) in [("a", "c"), ("b", "d"), ("c", "d"), ("d", "e")] {
+            graph
+                .add_edge_using_keys(edge(from_vertex, "requires", to_vertex))
+                .unwrap();
+        }
+        graph
+            .add_edge_and_edge_type_using_keys(edge("a", "refines", "c"))
+            .unwrap();
+        graph
+            .add_edge_using_keys(edge("e", "refines", "f"))
+            .unwrap();
+        graph
+    }
+
+    #[test]
+    fn shortest_path_with_edge_types() {
+        let graph = requirements_graph();
+        let path = graph
+            .shortest_path("a", "f", &["requires", "refines"])
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(path.length(), 4);
+        assert_eq!(vertex_keys(&path), vec!["a", "b", "d", "e", "f"]);
+        assert_eq!(
+            path.edge_types_ref(),
+            &["requires", "requires", "requires", "refines"]
+        );
+
+        let path = graph
+            .shortest_path("c", "c", &["requires"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(vertex_keys(&path), vec!["c"]);
+        assert_eq!(path.length(), 0);
+    }
+
+    #[test]
+    fn unreachable_vertex_has_no_shortest_path() {
+        let graph = requirements_graph();
+        assert_eq!(graph.shortest_path("a", "f", &["requires"]).unwrap(), None);
+        assert_eq!(graph.shortest_path("e", "a", &["requires"]).unwrap(), None);
+        assert!(graph
+            .all_shortest_paths("a", "g", &["requires", "refines"])
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            graph
+                .shortest_path("a", "z", &["requires"])
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::VertexKeyNotFound)
+        );
+    }
+
+    #[test]
+    fn all_shortest_paths_with_ties() {
+        let graph = requirements_graph();
+        let mut paths: Vec<(Vec<&str>, Vec<EdgeType>)> = Vec::new();
+        let shortest_paths = graph
+            .all_shortest_paths("a", "e", &["requires", "refines"])
+            .unwrap();
+        for path in shortest_paths.iter() {
+            paths.push((vertex_keys(path), path.edge_types_ref().to_vec()));
+        }
+        paths.sort();
+
+        let edge_types = |edge_types: [&str; 3]| -> Vec<EdgeType> {
+            edge_types
+                .iter()
+                .map(|edge_type| edge_type.to_string())
+                .collect()
+        };
+        assert_eq!(
+            paths,
+            vec![
+                (
+                    vec!["a", "b", "d", "e"],
+                    edge_types(["requires", "requires", "requires"])
+                ),
+                (
+                    vec!["a", "c", "d", "e"],
+                    edge_types(["refines", "requires", "requires"])
+                ),
+                (
+                    vec!["a", "c", "d", "e"],
+                    edge_types(["requires", "requires", "requires"])
+                ),
+            ]
+        );
+    }
+}
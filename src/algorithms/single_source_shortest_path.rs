@@ -0,0 +1,211 @@
+use hashbrown::HashMap;
+
+use crate::error::{GraphComputingError, UserError, UserErrorType};
+use crate::graph::edge::{EdgeTypeRef, EdgeWeight};
+use crate::graph::graph::{ElementIndex, Graph, GraphTrait};
+use crate::graph::vertex::{VertexIndex, VertexKey, VertexKeyAndIndexConversion, VertexKeyRef};
+
+pub trait SingleSourceShortestPath {
+    /// The total weight of the lightest path from from_vertex_key to each vertex it reaches by edges of edge_type.
+    /// Vertices that are not reached are not in the map. Weights may be negative;
+    /// a negative cycle reachable from from_vertex_key returns a UserError of type NegativeCycle.
+    fn single_source_shortest_path_weights(
+        &self,
+        from_vertex_key: &VertexKeyRef,
+        edge_type: &EdgeTypeRef,
+    ) -> Result<HashMap<VertexKey, EdgeWeight>, GraphComputingError>;
+}
+
+impl SingleSourceShortestPath for Graph {
+    fn single_source_shortest_path_weights(
+        &self,
+        from_vertex_key: &VertexKeyRef,
+        edge_type: &EdgeTypeRef,
+    ) -> Result<HashMap<VertexKey, EdgeWeight>, GraphComputingError> {
+        let from_vertex_index = self.existing_vertex_index(from_vertex_key)?;
+        let mut weighted_edges_by_from_vertex: HashMap<
+            ElementIndex,
+            Vec<(ElementIndex, EdgeWeight)>,
+        > = HashMap::new();
+        for (coordinate, weight) in self
+            .get_edge_adjacency_matrix_ref(edge_type)?
+            .get_weighted_edges()?
+        {
+            weighted_edges_by_from_vertex
+                .entry(coordinate.row_index())
+                .or_default()
+                .push((coordinate.column_index(), weight));
+        }
+
+        let path_weights = bellman_ford(
+            from_vertex_index,
+            &weighted_edges_by_from_vertex,
+            self.number_of_vertices()?,
+        )
+        .map_err(|_| -> GraphComputingError {
+            UserError::new(
+                UserErrorType::NegativeCycle,
+                format!(
+                    "Edges of type {} have a negative weight cycle that is reachable from vertex {}",
+                    edge_type, from_vertex_key
+                ),
+                None,
+            )
+            .into()
+        })?;
+
+        let mut path_weights_by_vertex_key = HashMap::with_capacity(path_weights.len());
+        for (vertex_index, path_weight) in path_weights {
+            path_weights_by_vertex_key.insert(
+                self.vertex_index_to_vertex_key_ref(VertexIndex::new(vertex_index))?
+                    .to_owned(),
+                path_weight,
+            );
+        }
+        Ok(path_weights_by_vertex_key)
+    }
+}
+
+struct NegativeCycle;
+
+// graphblas_sparse_linear_algebra does not provide a min-plus semiring, so this is a CPU fallback:
+// the weighted edges are copied out of the adjacency matrix and relaxed in Rust, outside GraphBLAS.
+// Each iteration relaxes the edges from the vertices whose path weight changed in the previous iteration,
+// and keeps the candidates that improve a path weight.
+// After iteration k, all paths of up to k edges are accounted for. A shortest path visits every vertex at most once,
+// so a path weight that still improves in iteration number_of_vertices can only be due to a negative cycle.
+fn bellman_ford(
+    from_vertex_index: ElementIndex,
+    weighted_edges_by_from_vertex: &HashMap<ElementIndex, Vec<(ElementIndex, EdgeWeight)>>,
+    number_of_vertices: usize,
+) -> Result<HashMap<ElementIndex, EdgeWeight>, NegativeCycle> {
+    let mut path_weights = HashMap::new();
+    path_weights.insert(from_vertex_index, 0.0);
+    let mut changed_vertex_indices = vec![from_vertex_index];
+
+    for _ in 0..number_of_vertices {
+        if changed_vertex_indices.is_empty() {
+            return Ok(path_weights);
+        }
+
+        let mut product: HashMap<ElementIndex, EdgeWeight> = HashMap::new();
+        for vertex_index in changed_vertex_indices.iter() {
+            let path_weight = path_weights[vertex_index];
+            for &(to_vertex_index, edge_weight) in weighted_edges_by_from_vertex
+                .get(vertex_index)
+                .into_iter()
+                .flatten()
+            {
+                let candidate_path_weight = path_weight + edge_weight;
+                product
+                    .entry(to_vertex_index)
+                    .and_modify(|minimum| {
+                        if candidate_path_weight < *minimum {
+                            *minimum = candidate_path_weight
+                        }
+                    })
+                    .or_insert(candidate_path_weight);
+            }
+        }
+
+        changed_vertex_indices.clear();
+        for (vertex_index, candidate_path_weight) in product {
+            let is_improvement = match path_weights.get(&vertex_index) {
+                None => true,
+                Some(&path_weight) => candidate_path_weight < path_weight,
+            };
+            if is_improvement {
+                path_weights.insert(vertex_index, candidate_path_weight);
+                changed_vertex_indices.push(vertex_index);
+            }
+        }
+    }
+
+    if changed_vertex_indices.is_empty() {
+        Ok(path_weights)
+    } else {
+        Err(NegativeCycle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::GraphComputingErrorType;
+    use crate::graph::edge::EdgeWeightType;
+    use crate::operations::add_edge::AddEdge;
+    use crate::operations::add_edge_type::AddEdgeType;
+    use crate::operations::set_edge_weight::SetEdgeWeight;
+    use crate::tests::{edge, graph_with_vertices};
+
+    fn graph_with_weighted_edges(weighted_edges: &[(&str, &str, EdgeWeight)]) -> Graph {
+        let mut graph = graph_with_vertices(&["a", "b", "c", "d", "e"]);
+        graph
+            .add_new_edge_type_with_weight_type(
+                String::from("effort"),
+                EdgeWeightType::FloatingPoint64Bit,
+            )
+            .unwrap();
+        graph.add_edge_using_keys(edge("a", "effort", "b")).unwrap();
+        for &(from_vertex, to_vertex, weight) in weighted_edges.iter() {
+            let weighted_edge = edge(from_vertex, "effort", to_vertex);
+            graph.add_edge_using_keys(weighted_edge.clone()).unwrap();
+            graph
+                .set_edge_weight_using_keys(&weighted_edge, weight)
+                .unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn lightest_paths_from_vertex() {
+        // a -> b has the default weight
+        let graph = graph_with_weighted_edges(&[
+            ("a", "c", 4.0),
+            ("b", "c", 2.0),
+            ("c", "d", 1.5),
+            ("b", "d", 5.0),
+        ]);
+        let path_weights = graph
+            .single_source_shortest_path_weights("a", "effort")
+            .unwrap();
+
+        assert_eq!(path_weights.len(), 4);
+        assert_eq!(path_weights["a"], 0.0);
+        assert_eq!(path_weights["b"], 1.0);
+        assert_eq!(path_weights["c"], 3.0);
+        assert_eq!(path_weights["d"], 4.5);
+        assert!(!path_weights.contains_key("e"));
+    }
+
+    #[test]
+    fn negative_weights_without_cycle() {
+        let graph =
+            graph_with_weighted_edges(&[("a", "c", 4.0), ("c", "b", -3.5), ("b", "d", 1.0)]);
+        let path_weights = graph
+            .single_source_shortest_path_weights("a", "effort")
+            .unwrap();
+
+        assert_eq!(path_weights["b"], 0.5);
+        assert_eq!(path_weights["d"], 1.5);
+    }
+
+    #[test]
+    fn reject_negative_cycle() {
+        let graph = graph_with_weighted_edges(&[("b", "c", 1.0), ("c", "b", -2.0)]);
+        assert_eq!(
+            graph
+                .single_source_shortest_path_weights("a", "effort")
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::NegativeCycle)
+        );
+
+        // The cycle is not reachable from d
+        let path_weights = graph
+            .single_source_shortest_path_weights("d", "effort")
+            .unwrap();
+        assert_eq!(path_weights.len(), 1);
+    }
+}
@@ -0,0 +1,201 @@
+use hashbrown::HashMap;
+
+use crate::error::{GraphComputingError, UserError, UserErrorType};
+use crate::graph::edge::EdgeTypeRef;
+use crate::graph::graph::{ElementIndex, Graph};
+use crate::graph::vertex::{VertexIndex, VertexKey, VertexKeyAndIndexConversion};
+
+pub trait TopologicalSort {
+    /// Every vertex, ordered such that each edge of edge_types points from an earlier to a later vertex.
+    /// Returns a UserError of type NotAcyclic, naming a cycle, if the edges of edge_types form a cycle.
+    fn topological_sort(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<Vec<VertexKey>, GraphComputingError>;
+
+    /// Every vertex, grouped in layers. A vertex is in the layer after the last layer that has an edge to it,
+    /// so the vertices within a layer do not depend on each other. The vertex keys in a layer are sorted.
+    fn topological_layers(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<Vec<Vec<VertexKey>>, GraphComputingError>;
+}
+
+impl TopologicalSort for Graph {
+    fn topological_sort(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<Vec<VertexKey>, GraphComputingError> {
+        Ok(self
+            .topological_layers(edge_types)?
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+
+    // Kahn's algorithm, removing a complete layer of vertices without incoming edges at a time
+    fn topological_layers(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<Vec<Vec<VertexKey>>, GraphComputingError> {
+        let mut number_of_incoming_edges: HashMap<ElementIndex, usize> = self
+            .valid_vertex_indices()?
+            .into_iter()
+            .map(|vertex_index| (vertex_index, 0))
+            .collect();
+        let mut successors: HashMap<ElementIndex, Vec<ElementIndex>> = HashMap::new();
+        for edge_type in edge_types {
+            for coordinate in self
+                .get_edge_adjacency_matrix_ref(edge_type)?
+                .get_edge_coordinates()?
+            {
+                if let Some(number_of_incoming_edges) =
+                    number_of_incoming_edges.get_mut(&coordinate.column_index())
+                {
+                    *number_of_incoming_edges += 1;
+                    successors
+                        .entry(coordinate.row_index())
+                        .or_default()
+                        .push(coordinate.column_index());
+                }
+            }
+        }
+
+        let mut layer: Vec<ElementIndex> = number_of_incoming_edges
+            .iter()
+            .filter(|(_, &number_of_incoming_edges)| number_of_incoming_edges == 0)
+            .map(|(&vertex_index, _)| vertex_index)
+            .collect();
+        let mut number_of_sorted_vertices = 0;
+        let mut layers = Vec::new();
+        while !layer.is_empty() {
+            let mut next_layer = Vec::new();
+            for vertex_index in layer.iter() {
+                for successor_vertex_index in successors.get(vertex_index).into_iter().flatten() {
+                    let number_of_incoming_edges = number_of_incoming_edges
+                        .get_mut(successor_vertex_index)
+                        .unwrap();
+                    *number_of_incoming_edges -= 1;
+                    if *number_of_incoming_edges == 0 {
+                        next_layer.push(*successor_vertex_index);
+                    }
+                }
+            }
+
+            number_of_sorted_vertices += layer.len();
+            let mut vertex_keys = Vec::with_capacity(layer.len());
+            for vertex_index in layer {
+                vertex_keys.push(
+                    self.vertex_index_to_vertex_key_ref(VertexIndex::new(vertex_index))?
+                        .to_owned(),
+                );
+            }
+            vertex_keys.sort();
+            layers.push(vertex_keys);
+            layer = next_layer;
+        }
+
+        // The vertices that remain all have an incoming edge from another remaining vertex
+        if number_of_sorted_vertices < number_of_incoming_edges.len() {
+            let cycle = match self.first_cycle(edge_types)? {
+                Some(cycle) => self.cycle_to_vertex_keys(cycle)?,
+                None => Vec::new(),
+            };
+            return Err(UserError::new(
+                UserErrorType::NotAcyclic,
+                format!(
+                    "Cannot sort the vertices topologically, edges of types {:?} form the cycle {}",
+                    edge_types,
+                    cycle.join(" -> ")
+                ),
+                None,
+            )
+            .into());
+        }
+        Ok(layers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::GraphComputingErrorType;
+    use crate::operations::add_edge::AddEdge;
+    use crate::operations::delete_vertex::DeleteVertex;
+    use crate::tests::{edge, graph_with_vertices};
+
+    // a -> b -> d, a -> c -> d, e, and c -refines-> e
+    fn requirements_graph() -> Graph {
+        let mut graph = graph_with_vertices(&["a", "b", "c", "d", "e"]);
+        graph
+            .add_edge_and_edge_type_using_keys(edge("a", "precedes", "b"))
+            .unwrap();
+        for (from_vertex, to_vertex) in [("a", "c"), ("b", "d"), ("c", "d")] {
+            graph
+                .add_edge_using_keys(edge(from_vertex, "precedes", to_vertex))
+                .unwrap();
+        }
+        graph
+            .add_edge_and_edge_type_using_keys(edge("c", "refines", "e"))
+            .unwrap();
+        graph
+    }
+
+    fn keys(vertex_keys: &[&str]) -> Vec<VertexKey> {
+        vertex_keys
+            .iter()
+            .map(|vertex_key| String::from(*vertex_key))
+            .collect()
+    }
+
+    #[test]
+    fn sort_in_dependency_order() {
+        let graph = requirements_graph();
+        assert_eq!(
+            graph.topological_layers(&["precedes"]).unwrap(),
+            vec![keys(&["a", "e"]), keys(&["b", "c"]), keys(&["d"])]
+        );
+        assert_eq!(
+            graph.topological_layers(&["precedes", "refines"]).unwrap(),
+            vec![keys(&["a"]), keys(&["b", "c"]), keys(&["d", "e"])]
+        );
+        assert_eq!(
+            graph.topological_sort(&["precedes"]).unwrap(),
+            keys(&["a", "e", "b", "c", "d"])
+        );
+    }
+
+    #[test]
+    fn cycle_is_named_in_error() {
+        let mut graph = requirements_graph();
+        graph
+            .add_edge_using_keys(edge("e", "refines", "a"))
+            .unwrap();
+        assert_eq!(
+            graph.topological_sort(&["precedes"]).unwrap(),
+            keys(&["a", "e", "b", "c", "d"])
+        );
+
+        let error = graph
+            .topological_sort(&["precedes", "refines"])
+            .unwrap_err();
+        assert_eq!(
+            error.error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::NotAcyclic)
+        );
+        assert!(error.to_string().contains("a -> c -> e"));
+    }
+
+    #[test]
+    fn sort_skips_deleted_vertices() {
+        let mut graph = requirements_graph();
+        graph
+            .delete_vertex_and_connected_edges_by_key(String::from("a"))
+            .unwrap();
+        assert_eq!(
+            graph.topological_layers(&["precedes"]).unwrap(),
+            vec![keys(&["b", "c", "e"]), keys(&["d"])]
+        );
+    }
+}
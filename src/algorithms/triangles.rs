@@ -0,0 +1,213 @@
+use hashbrown::HashMap;
+
+use graphblas_sparse_linear_algebra::value_types::sparse_matrix::{Size, SparseMatrix};
+use graphblas_sparse_linear_algebra::value_types::sparse_vector::{
+    GetVectorElementList, SparseVector,
+};
+
+use crate::error::GraphComputingError;
+use crate::graph::edge::boolean_matrix_operations::{
+    count_paths_of_length_two_closed_by_an_element, difference, from_coordinates, sum_per_row,
+    to_count_matrix, transpose, union,
+};
+use crate::graph::edge::EdgeTypeRef;
+use crate::graph::graph::{ElementCount, ElementIndex, Graph, GraphTrait};
+use crate::graph::vertex::{VertexIndex, VertexKey, VertexKeyAndIndexConversion};
+
+/// Triangles are formed by the edges of all edge_types together, regardless of their direction.
+/// Several edges between the same two vertices count as one, self-loops are ignored.
+pub trait Triangles {
+    fn number_of_triangles(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<ElementCount, GraphComputingError>;
+
+    /// The number of triangles that each vertex is part of
+    fn triangles_per_vertex(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<HashMap<VertexKey, ElementCount>, GraphComputingError>;
+
+    /// The fraction of the pairs of neighbours of each vertex that are neighbours of each other.
+    /// A vertex with fewer than two neighbours has a coefficient of 0.
+    fn local_clustering_coefficients(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<HashMap<VertexKey, f64>, GraphComputingError>;
+}
+
+impl Triangles for Graph {
+    fn number_of_triangles(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<ElementCount, GraphComputingError> {
+        let (triangles, _) = self.triangles_and_neighbours_per_vertex(edge_types)?;
+        // Each triangle is counted at each of its three vertices
+        Ok(triangles.values().sum::<ElementCount>() / 3)
+    }
+
+    fn triangles_per_vertex(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<HashMap<VertexKey, ElementCount>, GraphComputingError> {
+        let (triangles, _) = self.triangles_and_neighbours_per_vertex(edge_types)?;
+        let mut triangles_by_vertex_key = HashMap::new();
+        for vertex_index in self.valid_vertex_indices()? {
+            triangles_by_vertex_key.insert(
+                self.vertex_index_to_vertex_key_ref(VertexIndex::new(vertex_index))?
+                    .to_owned(),
+                triangles.get(&vertex_index).copied().unwrap_or(0),
+            );
+        }
+        Ok(triangles_by_vertex_key)
+    }
+
+    fn local_clustering_coefficients(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<HashMap<VertexKey, f64>, GraphComputingError> {
+        let (triangles, neighbours) = self.triangles_and_neighbours_per_vertex(edge_types)?;
+        let mut coefficients = HashMap::new();
+        for vertex_index in self.valid_vertex_indices()? {
+            let number_of_neighbours = neighbours.get(&vertex_index).copied().unwrap_or(0);
+            let coefficient = if number_of_neighbours < 2 {
+                0.0
+            } else {
+                let number_of_neighbour_pairs =
+                    number_of_neighbours * (number_of_neighbours - 1) / 2;
+                triangles.get(&vertex_index).copied().unwrap_or(0) as f64
+                    / number_of_neighbour_pairs as f64
+            };
+            coefficients.insert(
+                self.vertex_index_to_vertex_key_ref(VertexIndex::new(vertex_index))?
+                    .to_owned(),
+                coefficient,
+            );
+        }
+        Ok(coefficients)
+    }
+}
+
+type CountPerVertex = HashMap<ElementIndex, ElementCount>;
+
+impl Graph {
+    // With S the symmetric adjacency matrix without diagonal, (S * S)<S> counts for every edge (i, j)
+    // the vertices k that close a triangle i - k - j. Its row sum counts every triangle at i twice, once per edge of i.
+    fn triangles_and_neighbours_per_vertex(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<(CountPerVertex, CountPerVertex), GraphComputingError> {
+        let vertex_capacity = self.vertex_capacity()?;
+        let size = Size::new(vertex_capacity, vertex_capacity);
+        let mut relation = SparseMatrix::<bool>::new(self.graphblas_context_ref(), &size)?;
+        for edge_type in edge_types {
+            relation = union(
+                &relation,
+                self.get_edge_adjacency_matrix_ref(edge_type)?
+                    .as_sparse_matrix(),
+            )?;
+        }
+        let diagonal = from_coordinates(
+            self.graphblas_context_ref(),
+            &size,
+            (0..vertex_capacity).collect(),
+            (0..vertex_capacity).collect(),
+        )?;
+        let symmetric_relation = to_count_matrix(&difference(
+            &union(&relation, &transpose(&relation)?)?,
+            &diagonal,
+        )?)?;
+
+        let closed_paths = count_paths_of_length_two_closed_by_an_element(&symmetric_relation)?;
+        let triangles = to_map(&sum_per_row(&closed_paths, false)?)?
+            .into_iter()
+            .map(|(vertex_index, count)| (vertex_index, count / 2))
+            .collect();
+        let neighbours = to_map(&sum_per_row(&symmetric_relation, false)?)?;
+        Ok((triangles, neighbours))
+    }
+}
+
+fn to_map(vector: &SparseVector<u64>) -> Result<CountPerVertex, GraphComputingError> {
+    let element_list = vector.get_element_list()?;
+    Ok(element_list
+        .indices_ref()
+        .iter()
+        .zip(element_list.values_ref().iter())
+        .map(|(&index, &value)| (index, value as ElementCount))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::{GraphComputingErrorType, UserErrorType};
+    use crate::operations::add_edge::AddEdge;
+    use crate::tests::{edge, graph_with_vertices};
+
+    // The triangles a-b-c and b-c-d, with b -> c in both directions, d -> d, and d -> e by "traces"
+    fn related_specifications() -> Graph {
+        let mut graph = graph_with_vertices(&["a", "b", "c", "d", "e"]);
+        graph
+            .add_edge_and_edge_type_using_keys(edge("a", "relates_to", "b"))
+            .unwrap();
+        for (from_vertex, to_vertex) in [
+            ("b", "c"),
+            ("c", "b"),
+            ("c", "a"),
+            ("b", "d"),
+            ("d", "c"),
+            ("d", "d"),
+        ] {
+            graph
+                .add_edge_using_keys(edge(from_vertex, "relates_to", to_vertex))
+                .unwrap();
+        }
+        graph
+            .add_edge_and_edge_type_using_keys(edge("d", "traces", "e"))
+            .unwrap();
+        graph
+    }
+
+    #[test]
+    fn count_triangles() {
+        let graph = related_specifications();
+        assert_eq!(graph.number_of_triangles(&["relates_to"]).unwrap(), 2);
+        assert_eq!(graph.number_of_triangles(&["traces"]).unwrap(), 0);
+
+        let triangles = graph.triangles_per_vertex(&["relates_to"]).unwrap();
+        assert_eq!(triangles["a"], 1);
+        assert_eq!(triangles["b"], 2);
+        assert_eq!(triangles["c"], 2);
+        assert_eq!(triangles["d"], 1);
+        assert_eq!(triangles["e"], 0);
+    }
+
+    #[test]
+    fn clustering_coefficients_over_union_of_edge_types() {
+        let graph = related_specifications();
+        let coefficients = graph
+            .local_clustering_coefficients(&["relates_to"])
+            .unwrap();
+        assert_eq!(coefficients["a"], 1.0);
+        assert_eq!(coefficients["b"], 2.0 / 3.0);
+        assert_eq!(coefficients["d"], 1.0);
+        assert_eq!(coefficients["e"], 0.0);
+
+        // e adds a neighbour to d that is not related to b or c
+        let coefficients = graph
+            .local_clustering_coefficients(&["relates_to", "traces"])
+            .unwrap();
+        assert_eq!(coefficients["d"], 1.0 / 3.0);
+        assert_eq!(coefficients["e"], 0.0);
+
+        assert_eq!(
+            graph
+                .local_clustering_coefficients(&["extends"])
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::EdgeTypeDoesNotExist)
+        );
+    }
+}
@@ -94,6 +94,12 @@ impl From<GraphComputingError> for std::fmt::Error {
     }
 }
 
+impl From<std::io::Error> for GraphComputingError {
+    fn from(error: std::io::Error) -> Self {
+        GraphComputingError::SystemError(error.into())
+    }
+}
+
 impl From<SparseLinearAlgebraError> for GraphComputingError {
     fn from(error: SparseLinearAlgebraError) -> Self {
         match error.error_type() {
@@ -16,6 +16,7 @@ pub struct SystemError {
 #[derive(Debug)]
 pub enum SystemErrorSource {
     SparseLinearAlgebra(SparseLinearAlgebraError),
+    Io(std::io::Error),
     PoisonedData,
 }
 
@@ -29,6 +30,7 @@ pub enum SystemErrorType {
     ContextAlreadyInitialized,
     PoisonedData,
     IndexOutOfBounds,
+    Io,
     Other,
 }
 
@@ -58,6 +60,7 @@ impl error::Error for SystemError {
         match self.source {
             Some(ref error) => match error {
                 SystemErrorSource::SparseLinearAlgebra(error) => Some(error),
+                SystemErrorSource::Io(error) => Some(error),
                 SystemErrorSource::PoisonedData => None,
             },
             None => None,
@@ -88,3 +91,13 @@ impl From<SparseLinearAlgebraError> for SystemError {
         }
     }
 }
+
+impl From<std::io::Error> for SystemError {
+    fn from(error: std::io::Error) -> Self {
+        Self {
+            error_type: SystemErrorType::Io,
+            explanation: String::new(),
+            source: Some(SystemErrorSource::Io(error)),
+        }
+    }
+}
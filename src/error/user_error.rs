@@ -21,8 +21,20 @@ pub enum UserErrorSource {
 #[derive(Debug, Clone, PartialEq)]
 pub enum UserErrorType {
     SparseLinearAlgebra(SparseLinearAlgebraErrorType),
+    CorruptData,
+    EdgeDoesNotExist,
     EdgeTypeDoesNotExist,
+    EdgeWeightTypeMismatch,
     IndexOutOfBounds,
+    InvalidInferenceRule,
+    NegativeCycle,
+    NotAcyclic,
+    QuerySyntax { line: usize, column: usize },
+    SchemaViolation,
+    TransactionInProgress,
+    UndefinedQueryVariable { line: usize, column: usize },
+    UnsupportedSnapshotVersion,
+    UnsupportedWriteAheadLogVersion,
     VertexAlreadyExists,
     VertexKeyNotFound,
     Other,
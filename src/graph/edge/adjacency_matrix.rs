@@ -1,26 +1,34 @@
 use std::sync::Arc;
 
+use hashbrown::HashMap;
 use once_cell::sync::Lazy;
 
-use crate::error::GraphComputingError;
+use crate::error::{GraphComputingError, UserError, UserErrorType};
 use crate::graph::vertex::VertexIndex;
 
+use super::boolean_matrix_operations;
+use super::edge::{
+    EdgeValue, EdgeWeight, EdgeWeightType, IntegerEdgeWeight, DEFAULT_EDGE_WEIGHT,
+    DEFAULT_INTEGER_EDGE_WEIGHT,
+};
 use super::edge_type::{EdgeType, EdgeTypeRef};
 
 use graphblas_sparse_linear_algebra::{
     context::Context,
     operators::{
         element_wise_addition::ElementWiseVectorAdditionMonoidOperator,
+        extract::SubMatrixExtractor,
         monoid::{Any, LogicalOr},
         options::OperatorOptions,
         reduce::MonoidReducer,
     },
-    util::ElementIndex,
+    util::{ElementIndex, ElementIndexSelector},
     value_types::sparse_matrix::{
         Coordinate, GetMatrixElementList, GetMatrixElementValue, MatrixElement, SetMatrixElement,
         Size, SparseMatrix,
     },
     value_types::sparse_vector::{GetVectorElementList, SparseVector},
+    value_types::value_type::ValueType,
 };
 
 static DEFAULT_GRAPHBLAS_OPERATOR_OPTIONS: Lazy<OperatorOptions> =
@@ -57,28 +65,95 @@ static GRAPHBLAS_VECTOR_OR_OPERATOR: Lazy<ElementWiseVectorAdditionMonoidOperato
         )
     });
 
+static GRAPHBLAS_SUB_MATRIX_EXTRACTOR: Lazy<SubMatrixExtractor<bool, bool>> =
+    Lazy::new(|| SubMatrixExtractor::<bool, bool>::new(&DEFAULT_GRAPHBLAS_OPERATOR_OPTIONS, None));
+
+static GRAPHBLAS_EDGE_WEIGHT_EXTRACTOR: Lazy<SubMatrixExtractor<EdgeWeight, EdgeWeight>> =
+    Lazy::new(|| {
+        SubMatrixExtractor::<EdgeWeight, EdgeWeight>::new(&DEFAULT_GRAPHBLAS_OPERATOR_OPTIONS, None)
+    });
+
+static GRAPHBLAS_INTEGER_EDGE_WEIGHT_EXTRACTOR: Lazy<
+    SubMatrixExtractor<IntegerEdgeWeight, IntegerEdgeWeight>,
+> = Lazy::new(|| {
+    SubMatrixExtractor::<IntegerEdgeWeight, IntegerEdgeWeight>::new(
+        &DEFAULT_GRAPHBLAS_OPERATOR_OPTIONS,
+        None,
+    )
+});
+
 pub type EdgeCoordinate = Coordinate;
 
 #[derive(Clone, Debug)]
 pub(crate) struct AdjacencyMatrix {
     edge_type: EdgeType,
-    sparse_matrix: SparseMatrix<bool>,
+    // Shared between clones until modified
+    sparse_matrix: Arc<SparseMatrix<bool>>,
+    // The subset of the edges that was derived by inference rules, instead of asserted
+    inferred_edges: Arc<SparseMatrix<bool>>,
+    edge_weights: EdgeWeights,
+    // Edge values are not GraphBLAS value types, they are stored by (from vertex index, to vertex index)
+    edge_values: Arc<HashMap<(ElementIndex, ElementIndex), EdgeValue>>,
+}
+
+// The weights are stored in a matrix of the weight type of the edge type
+#[derive(Clone, Debug)]
+enum EdgeWeights {
+    // The weight of an edge of a boolean edge type is its presence in the adjacency matrix
+    Boolean,
+    // Edges without a stored weight weigh DEFAULT_INTEGER_EDGE_WEIGHT
+    Integer64Bit(Arc<SparseMatrix<IntegerEdgeWeight>>),
+    // Edges without a stored weight weigh DEFAULT_EDGE_WEIGHT
+    FloatingPoint64Bit(Arc<SparseMatrix<EdgeWeight>>),
 }
 
 // TODO: consider the use of a GraphBLAS iso-matrix (currently not supported by graphblas_sparse_linear_algebra)
 impl AdjacencyMatrix {
+    // Stops sharing the GraphBLAS matrices with clones, see Graph::copy_graphblas_objects()
+    pub(crate) fn copy_graphblas_objects(&mut self) {
+        self.sparse_matrix = Arc::new(self.sparse_matrix.as_ref().clone());
+        self.inferred_edges = Arc::new(self.inferred_edges.as_ref().clone());
+        self.edge_weights = match &self.edge_weights {
+            EdgeWeights::Boolean => EdgeWeights::Boolean,
+            EdgeWeights::Integer64Bit(edge_weights) => {
+                EdgeWeights::Integer64Bit(Arc::new(edge_weights.as_ref().clone()))
+            }
+            EdgeWeights::FloatingPoint64Bit(edge_weights) => {
+                EdgeWeights::FloatingPoint64Bit(Arc::new(edge_weights.as_ref().clone()))
+            }
+        };
+    }
+
     pub(crate) fn new(
         graphblas_context: &Arc<Context>,
         edge_type: EdgeType,
         vertex_capacity: ElementIndex,
+        edge_weight_type: EdgeWeightType,
     ) -> Result<Self, GraphComputingError> {
         let sparse_matrix = SparseMatrix::new(
             &graphblas_context,
             &Size::new(vertex_capacity, vertex_capacity),
         )?;
+        let inferred_edges = SparseMatrix::new(
+            graphblas_context,
+            &Size::new(vertex_capacity, vertex_capacity),
+        )?;
+        let size = Size::new(vertex_capacity, vertex_capacity);
+        let edge_weights = match edge_weight_type {
+            EdgeWeightType::Boolean => EdgeWeights::Boolean,
+            EdgeWeightType::Integer64Bit => {
+                EdgeWeights::Integer64Bit(Arc::new(SparseMatrix::new(graphblas_context, &size)?))
+            }
+            EdgeWeightType::FloatingPoint64Bit => EdgeWeights::FloatingPoint64Bit(Arc::new(
+                SparseMatrix::new(graphblas_context, &size)?,
+            )),
+        };
         Ok(Self {
             edge_type,
-            sparse_matrix,
+            sparse_matrix: Arc::new(sparse_matrix),
+            inferred_edges: Arc::new(inferred_edges),
+            edge_weights,
+            edge_values: Arc::new(HashMap::new()),
         })
     }
 
@@ -86,12 +161,31 @@ impl AdjacencyMatrix {
         &self.edge_type.as_str()
     }
 
+    /// Asserting an edge that was inferred turns it into an asserted edge.
     pub(crate) fn add_edge(
         &mut self,
         coordinate: &EdgeCoordinate,
     ) -> Result<(), GraphComputingError> {
-        self.sparse_matrix
+        self.as_mut_sparse_matrix()
             .set_element(MatrixElement::new(*coordinate, true))?;
+        if self.is_inferred_edge(coordinate)? {
+            self.as_mut_inferred_edges_sparse_matrix()
+                .drop_element(*coordinate)?;
+        }
+        Ok(())
+    }
+
+    /// Adds all edges stored in edges. Inferred edges among them become asserted edges.
+    pub(crate) fn add_edges(
+        &mut self,
+        edges: &SparseMatrix<bool>,
+    ) -> Result<(), GraphComputingError> {
+        *self.as_mut_sparse_matrix() =
+            boolean_matrix_operations::union(&self.sparse_matrix, edges)?;
+        if self.number_of_inferred_edges()? > 0 {
+            *self.as_mut_inferred_edges_sparse_matrix() =
+                boolean_matrix_operations::difference(&self.inferred_edges, edges)?;
+        }
         Ok(())
     }
 
@@ -103,7 +197,13 @@ impl AdjacencyMatrix {
         &mut self,
         coordinate: &EdgeCoordinate,
     ) -> Result<(), GraphComputingError> {
-        self.sparse_matrix.drop_element(*coordinate)?;
+        self.as_mut_sparse_matrix().drop_element(*coordinate)?;
+        if self.is_inferred_edge(coordinate)? {
+            self.as_mut_inferred_edges_sparse_matrix()
+                .drop_element(*coordinate)?;
+        }
+        self.delete_edge_weight(coordinate)?;
+        self.delete_edge_value(coordinate);
         Ok(())
     }
 
@@ -117,7 +217,291 @@ impl AdjacencyMatrix {
     }
 
     pub(crate) fn as_mut_sparse_matrix(&mut self) -> &mut SparseMatrix<bool> {
-        &mut self.sparse_matrix
+        Arc::make_mut(&mut self.sparse_matrix)
+    }
+
+    pub(crate) fn is_inferred_edge(
+        &self,
+        coordinate: &EdgeCoordinate,
+    ) -> Result<bool, GraphComputingError> {
+        Ok(self.inferred_edges.get_element_value(coordinate)?)
+    }
+
+    pub(crate) fn as_mut_inferred_edges_sparse_matrix(&mut self) -> &mut SparseMatrix<bool> {
+        Arc::make_mut(&mut self.inferred_edges)
+    }
+
+    /// Adds the edges in inferred_edges, and marks them as inferred. Edges that are already in the matrix are not marked.
+    pub(crate) fn add_inferred_edges(
+        &mut self,
+        inferred_edges: &SparseMatrix<bool>,
+    ) -> Result<(), GraphComputingError> {
+        let new_edges = boolean_matrix_operations::difference(inferred_edges, &self.sparse_matrix)?;
+        *self.as_mut_inferred_edges_sparse_matrix() =
+            boolean_matrix_operations::union(&self.inferred_edges, &new_edges)?;
+        *self.as_mut_sparse_matrix() =
+            boolean_matrix_operations::union(&self.sparse_matrix, &new_edges)?;
+        Ok(())
+    }
+
+    pub(crate) fn delete_inferred_edges(&mut self) -> Result<(), GraphComputingError> {
+        if self.number_of_stored_edge_weights()? > 0 || !self.edge_values.is_empty() {
+            for coordinate in self.get_inferred_edge_coordinates()? {
+                self.delete_edge_weight(&coordinate)?;
+                self.delete_edge_value(&coordinate);
+            }
+        }
+        *self.as_mut_sparse_matrix() =
+            boolean_matrix_operations::difference(&self.sparse_matrix, &self.inferred_edges)?;
+        self.as_mut_inferred_edges_sparse_matrix().clear()?;
+        Ok(())
+    }
+
+    pub(crate) fn edge_weight_type(&self) -> EdgeWeightType {
+        match self.edge_weights {
+            EdgeWeights::Boolean => EdgeWeightType::Boolean,
+            EdgeWeights::Integer64Bit(_) => EdgeWeightType::Integer64Bit,
+            EdgeWeights::FloatingPoint64Bit(_) => EdgeWeightType::FloatingPoint64Bit,
+        }
+    }
+
+    fn number_of_stored_edge_weights(&self) -> Result<ElementIndex, GraphComputingError> {
+        Ok(match &self.edge_weights {
+            EdgeWeights::Boolean => 0,
+            EdgeWeights::Integer64Bit(edge_weights) => edge_weights.number_of_stored_elements()?,
+            EdgeWeights::FloatingPoint64Bit(edge_weights) => {
+                edge_weights.number_of_stored_elements()?
+            }
+        })
+    }
+
+    fn delete_edge_weight(
+        &mut self,
+        coordinate: &EdgeCoordinate,
+    ) -> Result<(), GraphComputingError> {
+        if self.number_of_stored_edge_weights()? == 0 {
+            return Ok(());
+        }
+        match &mut self.edge_weights {
+            EdgeWeights::Boolean => {}
+            EdgeWeights::Integer64Bit(edge_weights) => {
+                Arc::make_mut(edge_weights).drop_element(*coordinate)?
+            }
+            EdgeWeights::FloatingPoint64Bit(edge_weights) => {
+                Arc::make_mut(edge_weights).drop_element(*coordinate)?
+            }
+        }
+        Ok(())
+    }
+
+    fn edge_weight_type_mismatch_error(
+        &self,
+        required_edge_weight_type: EdgeWeightType,
+    ) -> GraphComputingError {
+        UserError::new(
+            UserErrorType::EdgeWeightTypeMismatch,
+            format!(
+                "Edge type {} has weights of type {:?}, not {:?}",
+                self.edge_type,
+                self.edge_weight_type(),
+                required_edge_weight_type
+            ),
+            None,
+        )
+        .into()
+    }
+
+    /// The caller must make sure the edge exists. The edge type must have weights of type FloatingPoint64Bit.
+    pub(crate) fn set_edge_weight(
+        &mut self,
+        coordinate: &EdgeCoordinate,
+        weight: EdgeWeight,
+    ) -> Result<(), GraphComputingError> {
+        match &mut self.edge_weights {
+            EdgeWeights::FloatingPoint64Bit(edge_weights) => {
+                Arc::make_mut(edge_weights).set_element(MatrixElement::new(*coordinate, weight))?;
+                Ok(())
+            }
+            _ => Err(self.edge_weight_type_mismatch_error(EdgeWeightType::FloatingPoint64Bit)),
+        }
+    }
+
+    /// The caller must make sure the edge exists. The edge type must have weights of type Integer64Bit.
+    pub(crate) fn set_integer_edge_weight(
+        &mut self,
+        coordinate: &EdgeCoordinate,
+        weight: IntegerEdgeWeight,
+    ) -> Result<(), GraphComputingError> {
+        match &mut self.edge_weights {
+            EdgeWeights::Integer64Bit(edge_weights) => {
+                Arc::make_mut(edge_weights).set_element(MatrixElement::new(*coordinate, weight))?;
+                Ok(())
+            }
+            _ => Err(self.edge_weight_type_mismatch_error(EdgeWeightType::Integer64Bit)),
+        }
+    }
+
+    /// The weight of an existing edge, of any weight type.
+    pub(crate) fn edge_weight(
+        &self,
+        coordinate: &EdgeCoordinate,
+    ) -> Result<EdgeWeight, GraphComputingError> {
+        match &self.edge_weights {
+            EdgeWeights::Boolean => Ok(DEFAULT_EDGE_WEIGHT),
+            EdgeWeights::Integer64Bit(_) => Ok(self.integer_edge_weight(coordinate)? as EdgeWeight),
+            EdgeWeights::FloatingPoint64Bit(edge_weights) => {
+                // get_element_value() cannot distinguish a missing weight from a stored default value
+                let mut stored_weight =
+                    SparseMatrix::new(edge_weights.context_ref(), &Size::new(1, 1))?;
+                GRAPHBLAS_EDGE_WEIGHT_EXTRACTOR.apply(
+                    edge_weights,
+                    &ElementIndexSelector::Index(&vec![coordinate.row_index()]),
+                    &ElementIndexSelector::Index(&vec![coordinate.column_index()]),
+                    &mut stored_weight,
+                )?;
+                if stored_weight.number_of_stored_elements()? == 0 {
+                    Ok(DEFAULT_EDGE_WEIGHT)
+                } else {
+                    Ok(stored_weight.get_element_value(&Coordinate::new(0, 0))?)
+                }
+            }
+        }
+    }
+
+    /// The weight of an existing edge. The edge type must have weights of type Integer64Bit.
+    pub(crate) fn integer_edge_weight(
+        &self,
+        coordinate: &EdgeCoordinate,
+    ) -> Result<IntegerEdgeWeight, GraphComputingError> {
+        match &self.edge_weights {
+            EdgeWeights::Integer64Bit(edge_weights) => {
+                let mut stored_weight =
+                    SparseMatrix::new(edge_weights.context_ref(), &Size::new(1, 1))?;
+                GRAPHBLAS_INTEGER_EDGE_WEIGHT_EXTRACTOR.apply(
+                    edge_weights,
+                    &ElementIndexSelector::Index(&vec![coordinate.row_index()]),
+                    &ElementIndexSelector::Index(&vec![coordinate.column_index()]),
+                    &mut stored_weight,
+                )?;
+                if stored_weight.number_of_stored_elements()? == 0 {
+                    Ok(DEFAULT_INTEGER_EDGE_WEIGHT)
+                } else {
+                    Ok(stored_weight.get_element_value(&Coordinate::new(0, 0))?)
+                }
+            }
+            _ => Err(self.edge_weight_type_mismatch_error(EdgeWeightType::Integer64Bit)),
+        }
+    }
+
+    /// The edges with a weight that is stored explicitly, integer weights are converted.
+    pub(crate) fn get_stored_edge_weights(
+        &self,
+    ) -> Result<Vec<(EdgeCoordinate, EdgeWeight)>, GraphComputingError> {
+        match &self.edge_weights {
+            EdgeWeights::Boolean => Ok(Vec::new()),
+            EdgeWeights::Integer64Bit(edge_weights) => Ok(matrix_elements(edge_weights)?
+                .into_iter()
+                .map(|(coordinate, weight)| (coordinate, weight as EdgeWeight))
+                .collect()),
+            EdgeWeights::FloatingPoint64Bit(edge_weights) => matrix_elements(edge_weights),
+        }
+    }
+
+    /// The edges with a weight that is stored explicitly. The edge type must have weights of type Integer64Bit.
+    pub(crate) fn get_stored_integer_edge_weights(
+        &self,
+    ) -> Result<Vec<(EdgeCoordinate, IntegerEdgeWeight)>, GraphComputingError> {
+        match &self.edge_weights {
+            EdgeWeights::Integer64Bit(edge_weights) => matrix_elements(edge_weights),
+            _ => Err(self.edge_weight_type_mismatch_error(EdgeWeightType::Integer64Bit)),
+        }
+    }
+
+    /// All edges, with their stored or default weight.
+    pub(crate) fn get_weighted_edges(
+        &self,
+    ) -> Result<Vec<(EdgeCoordinate, EdgeWeight)>, GraphComputingError> {
+        let default_edge_weight = match self.edge_weights {
+            EdgeWeights::Integer64Bit(_) => DEFAULT_INTEGER_EDGE_WEIGHT as EdgeWeight,
+            _ => DEFAULT_EDGE_WEIGHT,
+        };
+        let stored_edge_weights: HashMap<(ElementIndex, ElementIndex), EdgeWeight> = self
+            .get_stored_edge_weights()?
+            .into_iter()
+            .map(|(coordinate, weight)| {
+                ((coordinate.row_index(), coordinate.column_index()), weight)
+            })
+            .collect();
+        Ok(self
+            .get_edge_coordinates()?
+            .into_iter()
+            .map(|coordinate| {
+                let weight = stored_edge_weights
+                    .get(&(coordinate.row_index(), coordinate.column_index()))
+                    .copied()
+                    .unwrap_or(default_edge_weight);
+                (coordinate, weight)
+            })
+            .collect())
+    }
+
+    pub(crate) fn delete_edge_weights_of_vertex(
+        &mut self,
+        vertex_index: ElementIndex,
+    ) -> Result<(), GraphComputingError> {
+        for (coordinate, _) in self.get_stored_edge_weights()? {
+            if coordinate.row_index() == vertex_index || coordinate.column_index() == vertex_index {
+                self.delete_edge_weight(&coordinate)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The caller must make sure the edge exists.
+    pub(crate) fn set_edge_value(&mut self, coordinate: &EdgeCoordinate, value: EdgeValue) {
+        Arc::make_mut(&mut self.edge_values)
+            .insert((coordinate.row_index(), coordinate.column_index()), value);
+    }
+
+    /// Returns the deleted value, if there was one.
+    pub(crate) fn delete_edge_value(&mut self, coordinate: &EdgeCoordinate) -> Option<EdgeValue> {
+        if self.edge_values.is_empty() {
+            return None;
+        }
+        Arc::make_mut(&mut self.edge_values)
+            .remove(&(coordinate.row_index(), coordinate.column_index()))
+    }
+
+    pub(crate) fn edge_value_ref(&self, coordinate: &EdgeCoordinate) -> Option<&EdgeValue> {
+        self.edge_values
+            .get(&(coordinate.row_index(), coordinate.column_index()))
+    }
+
+    /// The edges with a value, ordered by coordinate.
+    pub(crate) fn get_edge_values(&self) -> Vec<(EdgeCoordinate, EdgeValue)> {
+        let mut edge_values: Vec<(EdgeCoordinate, EdgeValue)> = self
+            .edge_values
+            .iter()
+            .map(|(&(row_index, column_index), value)| {
+                (EdgeCoordinate::new(row_index, column_index), value.clone())
+            })
+            .collect();
+        edge_values
+            .sort_by_key(|(coordinate, _)| (coordinate.row_index(), coordinate.column_index()));
+        edge_values
+    }
+
+    pub(crate) fn delete_edge_values_of_vertex(&mut self, vertex_index: ElementIndex) {
+        if self.edge_values.is_empty() {
+            return;
+        }
+        Arc::make_mut(&mut self.edge_values).retain(|&(from_vertex_index, to_vertex_index), _| {
+            from_vertex_index != vertex_index && to_vertex_index != vertex_index
+        });
+    }
+
+    pub(crate) fn number_of_inferred_edges(&self) -> Result<ElementIndex, GraphComputingError> {
+        Ok(self.inferred_edges.number_of_stored_elements()?)
     }
 
     // The API suggests a design problem. Returning a ref would be safer, but technically not possible.
@@ -134,9 +518,18 @@ impl AdjacencyMatrix {
         &mut self,
         target_vertex_capacity: ElementIndex,
     ) -> Result<(), GraphComputingError> {
-        Ok(self
-            .sparse_matrix
-            .resize(&Size::new(target_vertex_capacity, target_vertex_capacity))?)
+        self.as_mut_sparse_matrix()
+            .resize(&Size::new(target_vertex_capacity, target_vertex_capacity))?;
+        self.as_mut_inferred_edges_sparse_matrix()
+            .resize(&Size::new(target_vertex_capacity, target_vertex_capacity))?;
+        match &mut self.edge_weights {
+            EdgeWeights::Boolean => {}
+            EdgeWeights::Integer64Bit(edge_weights) => Arc::make_mut(edge_weights)
+                .resize(&Size::new(target_vertex_capacity, target_vertex_capacity))?,
+            EdgeWeights::FloatingPoint64Bit(edge_weights) => Arc::make_mut(edge_weights)
+                .resize(&Size::new(target_vertex_capacity, target_vertex_capacity))?,
+        }
+        Ok(())
     }
 
     pub(crate) fn size(&self) -> Result<Size, GraphComputingError> {
@@ -148,20 +541,44 @@ impl AdjacencyMatrix {
     }
 
     pub(crate) fn get_edge_coordinates(&self) -> Result<Vec<EdgeCoordinate>, GraphComputingError> {
-        let matrix_element_list = self.sparse_matrix.get_element_list()?;
-        let element_indices_from_vertices = matrix_element_list.row_indices_ref();
-        let element_indices_to_vertices = matrix_element_list.column_indices_ref();
-
-        let mut edge_coordinates: Vec<EdgeCoordinate> =
-            Vec::with_capacity(matrix_element_list.length());
-        for element_index in 0..matrix_element_list.length() {
-            let element_coordinate = EdgeCoordinate::new(
-                element_indices_from_vertices[element_index],
-                element_indices_to_vertices[element_index],
-            );
-            edge_coordinates.push(element_coordinate);
+        edge_coordinates(&self.sparse_matrix)
+    }
+
+    pub(crate) fn get_inferred_edge_coordinates(
+        &self,
+    ) -> Result<Vec<EdgeCoordinate>, GraphComputingError> {
+        edge_coordinates(&self.inferred_edges)
+    }
+
+    /// The coordinates of the edges from any of from_vertex_indices to any of to_vertex_indices.
+    pub(crate) fn get_edge_coordinates_between(
+        &self,
+        from_vertex_indices: &[ElementIndex],
+        to_vertex_indices: &[ElementIndex],
+    ) -> Result<Vec<EdgeCoordinate>, GraphComputingError> {
+        if from_vertex_indices.is_empty() || to_vertex_indices.is_empty() {
+            return Ok(Vec::new());
         }
-        Ok(edge_coordinates)
+        let mut edges_between_vertices = SparseMatrix::new(
+            self.sparse_matrix.context_ref(),
+            &Size::new(from_vertex_indices.len(), to_vertex_indices.len()),
+        )?;
+        GRAPHBLAS_SUB_MATRIX_EXTRACTOR.apply(
+            &self.sparse_matrix,
+            &ElementIndexSelector::Index(&from_vertex_indices.to_vec()),
+            &ElementIndexSelector::Index(&to_vertex_indices.to_vec()),
+            &mut edges_between_vertices,
+        )?;
+        // The sub-matrix is indexed by the position in the index lists
+        Ok(edge_coordinates(&edges_between_vertices)?
+            .into_iter()
+            .map(|coordinate| {
+                EdgeCoordinate::new(
+                    from_vertex_indices[coordinate.row_index()],
+                    to_vertex_indices[coordinate.column_index()],
+                )
+            })
+            .collect())
     }
 
     pub(crate) fn get_from_vertex_indices(&self) -> Result<Vec<VertexIndex>, GraphComputingError> {
@@ -222,6 +639,183 @@ impl AdjacencyMatrix {
         )?;
         Ok(vertex_vector_mask)
     }
+
+    /// Selects the vertices with an edge from any of the vertices in from_vertex_mask.
+    pub(crate) fn get_to_vertex_index_mask_for_from_vertices(
+        &self,
+        from_vertex_mask: &SparseVector<bool>,
+    ) -> Result<SparseVector<bool>, GraphComputingError> {
+        multiply_vertex_mask(
+            from_vertex_mask,
+            &boolean_matrix_operations::to_structure_matrix(&self.sparse_matrix)?,
+            false,
+        )
+    }
+
+    /// Selects the vertices with an edge to any of the vertices in to_vertex_mask.
+    pub(crate) fn get_from_vertex_index_mask_for_to_vertices(
+        &self,
+        to_vertex_mask: &SparseVector<bool>,
+    ) -> Result<SparseVector<bool>, GraphComputingError> {
+        multiply_vertex_mask(
+            to_vertex_mask,
+            &boolean_matrix_operations::to_structure_matrix(&self.sparse_matrix)?,
+            true,
+        )
+    }
+
+    /// The number of outgoing edges of each vertex. Vertices without outgoing edges are not stored.
+    pub(crate) fn out_degrees(&self) -> Result<SparseVector<u64>, GraphComputingError> {
+        boolean_matrix_operations::sum_per_row(
+            &boolean_matrix_operations::to_count_matrix(&self.sparse_matrix)?,
+            false,
+        )
+    }
+
+    /// The number of incoming edges of each vertex. Vertices without incoming edges are not stored.
+    pub(crate) fn in_degrees(&self) -> Result<SparseVector<u64>, GraphComputingError> {
+        boolean_matrix_operations::sum_per_row(
+            &boolean_matrix_operations::to_count_matrix(&self.sparse_matrix)?,
+            true,
+        )
+    }
+
+    /// The number of outgoing edges of a single vertex, only its row is read.
+    pub(crate) fn out_degree(
+        &self,
+        vertex_index: ElementIndex,
+    ) -> Result<ElementIndex, GraphComputingError> {
+        let vertex_capacity = self.sparse_matrix.row_height()?;
+        self.number_of_edges_in_sub_matrix(
+            &ElementIndexSelector::Index(&vec![vertex_index]),
+            &ElementIndexSelector::All,
+            &Size::new(1, vertex_capacity),
+        )
+    }
+
+    /// The number of incoming edges of a single vertex, only its column is read.
+    pub(crate) fn in_degree(
+        &self,
+        vertex_index: ElementIndex,
+    ) -> Result<ElementIndex, GraphComputingError> {
+        let vertex_capacity = self.sparse_matrix.row_height()?;
+        self.number_of_edges_in_sub_matrix(
+            &ElementIndexSelector::All,
+            &ElementIndexSelector::Index(&vec![vertex_index]),
+            &Size::new(vertex_capacity, 1),
+        )
+    }
+
+    fn number_of_edges_in_sub_matrix(
+        &self,
+        row_selector: &ElementIndexSelector,
+        column_selector: &ElementIndexSelector,
+        size: &Size,
+    ) -> Result<ElementIndex, GraphComputingError> {
+        let mut sub_matrix = SparseMatrix::new(self.sparse_matrix.context_ref(), size)?;
+        GRAPHBLAS_SUB_MATRIX_EXTRACTOR.apply(
+            &self.sparse_matrix,
+            row_selector,
+            column_selector,
+            &mut sub_matrix,
+        )?;
+        Ok(sub_matrix
+            .get_element_list()?
+            .values_ref()
+            .iter()
+            .filter(|is_edge| **is_edge)
+            .count())
+    }
+
+    /// Selects the vertices with an edge to or from any of the vertices in vertex_mask.
+    pub(crate) fn get_vertex_index_mask_for_connected_vertices(
+        &self,
+        vertex_mask: &SparseVector<bool>,
+    ) -> Result<SparseVector<bool>, GraphComputingError> {
+        let structure_matrix = boolean_matrix_operations::to_structure_matrix(&self.sparse_matrix)?;
+        let mut connected_vertex_mask = SparseVector::new(
+            self.sparse_matrix.context_ref(),
+            &self.sparse_matrix.row_height()?,
+        )?;
+        GRAPHBLAS_VECTOR_OR_OPERATOR.apply(
+            &multiply_vertex_mask(vertex_mask, &structure_matrix, false)?,
+            &multiply_vertex_mask(vertex_mask, &structure_matrix, true)?,
+            &mut connected_vertex_mask,
+        )?;
+        Ok(connected_vertex_mask)
+    }
+}
+
+// A single step of a traversal: the boolean vector-matrix product of the selected vertices and the adjacency matrix,
+// computed as a structural product, see boolean_matrix_operations.
+fn multiply_vertex_mask(
+    vertex_mask: &SparseVector<bool>,
+    structure_matrix: &SparseMatrix<u8>,
+    is_transposed: bool,
+) -> Result<SparseVector<bool>, GraphComputingError> {
+    let structure_vector = boolean_matrix_operations::structure_vector_from_indices(
+        vertex_mask.context_ref(),
+        vertex_mask.length()?,
+        selected_element_indices(vertex_mask)?,
+    )?;
+    boolean_matrix_operations::from_structure_vector(
+        &boolean_matrix_operations::multiply_structure_vector(
+            &structure_vector,
+            structure_matrix,
+            is_transposed,
+        )?,
+    )
+}
+
+pub(crate) fn edge_coordinates(
+    sparse_matrix: &SparseMatrix<bool>,
+) -> Result<Vec<EdgeCoordinate>, GraphComputingError> {
+    let matrix_element_list = sparse_matrix.get_element_list()?;
+    let element_indices_from_vertices = matrix_element_list.row_indices_ref();
+    let element_indices_to_vertices = matrix_element_list.column_indices_ref();
+
+    let mut edge_coordinates: Vec<EdgeCoordinate> =
+        Vec::with_capacity(matrix_element_list.length());
+    for element_index in 0..matrix_element_list.length() {
+        let element_coordinate = EdgeCoordinate::new(
+            element_indices_from_vertices[element_index],
+            element_indices_to_vertices[element_index],
+        );
+        edge_coordinates.push(element_coordinate);
+    }
+    Ok(edge_coordinates)
+}
+
+fn matrix_elements<T: ValueType + Copy>(
+    sparse_matrix: &SparseMatrix<T>,
+) -> Result<Vec<(EdgeCoordinate, T)>, GraphComputingError>
+where
+    SparseMatrix<T>: GetMatrixElementList<T>,
+{
+    let matrix_element_list = sparse_matrix.get_element_list()?;
+    Ok(matrix_element_list
+        .row_indices_ref()
+        .iter()
+        .zip(matrix_element_list.column_indices_ref().iter())
+        .zip(matrix_element_list.values_ref().iter())
+        .map(|((&row_index, &column_index), &value)| {
+            (EdgeCoordinate::new(row_index, column_index), value)
+        })
+        .collect())
+}
+
+// A mask may store false values, these do not select an index.
+pub(crate) fn selected_element_indices(
+    mask: &SparseVector<bool>,
+) -> Result<Vec<ElementIndex>, GraphComputingError> {
+    let mask_elements = mask.get_element_list()?;
+    Ok(mask_elements
+        .indices_ref()
+        .iter()
+        .zip(mask_elements.values_ref().iter())
+        .filter(|(_, &is_selected)| is_selected)
+        .map(|(&index, _)| index)
+        .collect())
 }
 
 fn index_elements_to_vertex_indices(index_elements: Vec<ElementIndex>) -> Vec<VertexIndex> {
@@ -244,7 +838,9 @@ mod tests {
     use super::*;
 
     use graphblas_sparse_linear_algebra::context::Mode;
-    use graphblas_sparse_linear_algebra::value_types::sparse_vector::GetVectorElementValue;
+    use graphblas_sparse_linear_algebra::value_types::sparse_vector::{
+        GetVectorElementValue, SetVectorElement, VectorElement,
+    };
 
     #[test]
     fn test_adjacency_matrix_construction() {
@@ -253,7 +849,13 @@ mod tests {
 
         let edge_type: EdgeType = String::from("Test edge type");
 
-        let adjacency_matrix = AdjacencyMatrix::new(&context, edge_type, vertex_capacity).unwrap();
+        let adjacency_matrix = AdjacencyMatrix::new(
+            &context,
+            edge_type,
+            vertex_capacity,
+            EdgeWeightType::FloatingPoint64Bit,
+        )
+        .unwrap();
     }
 
     #[test]
@@ -263,8 +865,13 @@ mod tests {
 
         let edge_type: EdgeType = String::from("Test edge type");
 
-        let mut adjacency_matrix =
-            AdjacencyMatrix::new(&context, edge_type, vertex_capacity).unwrap();
+        let mut adjacency_matrix = AdjacencyMatrix::new(
+            &context,
+            edge_type,
+            vertex_capacity,
+            EdgeWeightType::FloatingPoint64Bit,
+        )
+        .unwrap();
 
         let edge_to_add = EdgeCoordinate::new(2, 1);
         assert!(!adjacency_matrix.is_edge(&edge_to_add).unwrap());
@@ -283,8 +890,13 @@ mod tests {
 
         let edge_type: EdgeType = String::from("Test edge type");
 
-        let mut adjacency_matrix =
-            AdjacencyMatrix::new(&context, edge_type, vertex_capacity).unwrap();
+        let mut adjacency_matrix = AdjacencyMatrix::new(
+            &context,
+            edge_type,
+            vertex_capacity,
+            EdgeWeightType::FloatingPoint64Bit,
+        )
+        .unwrap();
 
         let edge_to_add_1 = EdgeCoordinate::new(2, 1);
         adjacency_matrix.add_edge(&edge_to_add_1).unwrap();
@@ -305,7 +917,13 @@ mod tests {
 
         let edge_type: EdgeType = String::from("Test edge type");
 
-        let adjacency_matrix = AdjacencyMatrix::new(&context, edge_type, vertex_capacity).unwrap();
+        let adjacency_matrix = AdjacencyMatrix::new(
+            &context,
+            edge_type,
+            vertex_capacity,
+            EdgeWeightType::FloatingPoint64Bit,
+        )
+        .unwrap();
 
         // let coordinate = Coordinate::new(0, 1);
         // let vector_index = adjacency_matrix.convert_coordinate_to_vector_index(coordinate);
@@ -342,8 +960,13 @@ mod tests {
         let vertex_capacity = 10;
         let edge_type: EdgeType = String::from("Test edge type");
 
-        let mut adjacency_matrix =
-            AdjacencyMatrix::new(&context, edge_type, vertex_capacity).unwrap();
+        let mut adjacency_matrix = AdjacencyMatrix::new(
+            &context,
+            edge_type,
+            vertex_capacity,
+            EdgeWeightType::FloatingPoint64Bit,
+        )
+        .unwrap();
 
         let edge_to_add_1 = EdgeCoordinate::new(2, 1);
         adjacency_matrix.add_edge(&edge_to_add_1).unwrap();
@@ -364,8 +987,13 @@ mod tests {
         let vertex_capacity = 10;
         let edge_type: EdgeType = String::from("Test edge type");
 
-        let mut adjacency_matrix =
-            AdjacencyMatrix::new(&context, edge_type, vertex_capacity).unwrap();
+        let mut adjacency_matrix = AdjacencyMatrix::new(
+            &context,
+            edge_type,
+            vertex_capacity,
+            EdgeWeightType::FloatingPoint64Bit,
+        )
+        .unwrap();
 
         let edge_to_add_1 = EdgeCoordinate::new(2, 1);
         adjacency_matrix.add_edge(&edge_to_add_1).unwrap();
@@ -385,8 +1013,13 @@ mod tests {
         let vertex_capacity = 10;
         let edge_type: EdgeType = String::from("Test edge type");
 
-        let mut adjacency_matrix =
-            AdjacencyMatrix::new(&context, edge_type, vertex_capacity).unwrap();
+        let mut adjacency_matrix = AdjacencyMatrix::new(
+            &context,
+            edge_type,
+            vertex_capacity,
+            EdgeWeightType::FloatingPoint64Bit,
+        )
+        .unwrap();
 
         let edge_to_add_1 = EdgeCoordinate::new(2, 1);
         adjacency_matrix.add_edge(&edge_to_add_1).unwrap();
@@ -407,8 +1040,13 @@ mod tests {
         let vertex_capacity = 10;
         let edge_type: EdgeType = String::from("Test edge type");
 
-        let mut adjacency_matrix =
-            AdjacencyMatrix::new(&context, edge_type, vertex_capacity).unwrap();
+        let mut adjacency_matrix = AdjacencyMatrix::new(
+            &context,
+            edge_type,
+            vertex_capacity,
+            EdgeWeightType::FloatingPoint64Bit,
+        )
+        .unwrap();
 
         let edge_to_add_1 = EdgeCoordinate::new(2, 1);
         adjacency_matrix.add_edge(&edge_to_add_1).unwrap();
@@ -427,8 +1065,13 @@ mod tests {
         let vertex_capacity = 10;
         let edge_type: EdgeType = String::from("Test edge type");
 
-        let mut adjacency_matrix =
-            AdjacencyMatrix::new(&context, edge_type, vertex_capacity).unwrap();
+        let mut adjacency_matrix = AdjacencyMatrix::new(
+            &context,
+            edge_type,
+            vertex_capacity,
+            EdgeWeightType::FloatingPoint64Bit,
+        )
+        .unwrap();
 
         let edge_to_add_1 = EdgeCoordinate::new(2, 1);
         adjacency_matrix.add_edge(&edge_to_add_1).unwrap();
@@ -451,8 +1094,13 @@ mod tests {
         let vertex_capacity = 10;
         let edge_type: EdgeType = String::from("Test edge type");
 
-        let mut adjacency_matrix =
-            AdjacencyMatrix::new(&context, edge_type, vertex_capacity).unwrap();
+        let mut adjacency_matrix = AdjacencyMatrix::new(
+            &context,
+            edge_type,
+            vertex_capacity,
+            EdgeWeightType::FloatingPoint64Bit,
+        )
+        .unwrap();
 
         let edge_to_add_1 = EdgeCoordinate::new(2, 1);
         adjacency_matrix.add_edge(&edge_to_add_1).unwrap();
@@ -465,4 +1113,63 @@ mod tests {
         assert!(vertex_indices.contains(&VertexIndex::new(1)));
         assert!(vertex_indices.contains(&VertexIndex::new(2)));
     }
+
+    #[test]
+    fn test_get_connected_vertex_index_masks() {
+        let context = Context::init_ready(Mode::NonBlocking).unwrap();
+        let vertex_capacity = 10;
+        let edge_type: EdgeType = String::from("Test edge type");
+
+        let mut adjacency_matrix = AdjacencyMatrix::new(
+            &context,
+            edge_type,
+            vertex_capacity,
+            EdgeWeightType::FloatingPoint64Bit,
+        )
+        .unwrap();
+        adjacency_matrix
+            .add_edge(&EdgeCoordinate::new(2, 1))
+            .unwrap();
+        adjacency_matrix
+            .add_edge(&EdgeCoordinate::new(3, 4))
+            .unwrap();
+        adjacency_matrix
+            .add_edge(&EdgeCoordinate::new(5, 2))
+            .unwrap();
+
+        let mut vertex_mask = SparseVector::new(&context, &vertex_capacity).unwrap();
+        vertex_mask
+            .set_element(VectorElement::from_pair(2, true))
+            .unwrap();
+        vertex_mask
+            .set_element(VectorElement::from_pair(3, true))
+            .unwrap();
+        vertex_mask
+            .set_element(VectorElement::from_pair(5, false))
+            .unwrap();
+
+        let to_vertex_mask = adjacency_matrix
+            .get_to_vertex_index_mask_for_from_vertices(&vertex_mask)
+            .unwrap();
+        assert_eq!(
+            selected_element_indices(&to_vertex_mask).unwrap(),
+            vec![1, 4]
+        );
+
+        let from_vertex_mask = adjacency_matrix
+            .get_from_vertex_index_mask_for_to_vertices(&vertex_mask)
+            .unwrap();
+        assert_eq!(
+            selected_element_indices(&from_vertex_mask).unwrap(),
+            vec![5]
+        );
+
+        let connected_vertex_mask = adjacency_matrix
+            .get_vertex_index_mask_for_connected_vertices(&vertex_mask)
+            .unwrap();
+        assert_eq!(
+            selected_element_indices(&connected_vertex_mask).unwrap(),
+            vec![1, 4, 5]
+        );
+    }
 }
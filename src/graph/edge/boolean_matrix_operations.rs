@@ -0,0 +1,489 @@
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+
+use graphblas_sparse_linear_algebra::context::Context;
+use graphblas_sparse_linear_algebra::operators::{
+    apply::{UnaryOperatorApplier, UnaryOperatorApplierTrait},
+    binary_operator::First,
+    element_wise_addition::ElementWiseMatrixAdditionMonoidOperator,
+    monoid::{LogicalOr, Plus},
+    multiplication::{MatrixMultiplicationOperator, VectorMatrixMultiplicationOperator},
+    options::OperatorOptions,
+    reduce::MonoidReducer,
+    semiring::PlusTimes,
+    transpose::MatrixTranspose,
+    unary_operator::Identity,
+};
+use graphblas_sparse_linear_algebra::util::ElementIndex;
+use graphblas_sparse_linear_algebra::value_types::sparse_matrix::{
+    FromMatrixElementList, GetMatrixElementList, MatrixElementList, Size, SparseMatrix,
+};
+use graphblas_sparse_linear_algebra::value_types::sparse_vector::{
+    FromVectorElementList, GetVectorElementList, SparseVector, VectorElementList,
+};
+
+use crate::error::GraphComputingError;
+
+// graphblas_sparse_linear_algebra only provides numeric plus-times semirings.
+// The sparsity pattern of a plus-times product is the boolean (any-pair) product of the patterns of its operands:
+// GraphBLAS stores an element for every (i, j) with at least one k where both operands store an element,
+// also when the sum of the products evaluates to zero, e.g. by overflow.
+// The boolean product is therefore computed on structural u8 copies of the operands, and only the pattern of the product is read.
+
+static DEFAULT_GRAPHBLAS_OPERATOR_OPTIONS: Lazy<OperatorOptions> =
+    Lazy::new(OperatorOptions::new_default);
+
+static GRAPHBLAS_OPERATOR_OPTIONS_TRANSPOSE_INPUT0: Lazy<OperatorOptions> =
+    Lazy::new(|| OperatorOptions::new(false, false, false, true, false));
+
+static GRAPHBLAS_OPERATOR_OPTIONS_TRANSPOSE_INPUT1: Lazy<OperatorOptions> =
+    Lazy::new(|| OperatorOptions::new(false, false, false, false, true));
+
+static GRAPHBLAS_OPERATOR_OPTIONS_COMPLEMENT_STRUCTURE_MASK: Lazy<OperatorOptions> =
+    Lazy::new(|| OperatorOptions::new(false, true, true, false, false));
+
+static GRAPHBLAS_OPERATOR_OPTIONS_COMPLEMENT_STRUCTURE_MASK_TRANSPOSE_INPUT1: Lazy<
+    OperatorOptions,
+> = Lazy::new(|| OperatorOptions::new(false, true, true, false, true));
+
+static GRAPHBLAS_STRUCTURE_MULTIPLICATION_OPERATOR: Lazy<MatrixMultiplicationOperator<u8, u8, u8>> =
+    Lazy::new(|| {
+        MatrixMultiplicationOperator::<u8, u8, u8>::new(
+            &PlusTimes::<u8, u8, u8>::new(),
+            &DEFAULT_GRAPHBLAS_OPERATOR_OPTIONS,
+            None,
+        )
+    });
+
+static GRAPHBLAS_STRUCTURE_VECTOR_MULTIPLICATION_OPERATOR: Lazy<
+    VectorMatrixMultiplicationOperator<u8, u8, u8>,
+> = Lazy::new(|| {
+    VectorMatrixMultiplicationOperator::<u8, u8, u8>::new(
+        Box::new(PlusTimes::<u8, u8, u8>::new()),
+        DEFAULT_GRAPHBLAS_OPERATOR_OPTIONS.clone(),
+        None,
+    )
+});
+
+static GRAPHBLAS_STRUCTURE_VECTOR_TRANSPOSED_MATRIX_MULTIPLICATION_OPERATOR: Lazy<
+    VectorMatrixMultiplicationOperator<u8, u8, u8>,
+> = Lazy::new(|| {
+    VectorMatrixMultiplicationOperator::<u8, u8, u8>::new(
+        Box::new(PlusTimes::<u8, u8, u8>::new()),
+        GRAPHBLAS_OPERATOR_OPTIONS_TRANSPOSE_INPUT1.clone(),
+        None,
+    )
+});
+
+static GRAPHBLAS_STRUCTURE_VECTOR_MULTIPLICATION_OPERATOR_WITH_MASK_COMPLEMENT: Lazy<
+    VectorMatrixMultiplicationOperator<u8, u8, u8>,
+> = Lazy::new(|| {
+    VectorMatrixMultiplicationOperator::<u8, u8, u8>::new(
+        Box::new(PlusTimes::<u8, u8, u8>::new()),
+        GRAPHBLAS_OPERATOR_OPTIONS_COMPLEMENT_STRUCTURE_MASK.clone(),
+        None,
+    )
+});
+
+static GRAPHBLAS_STRUCTURE_VECTOR_TRANSPOSED_MATRIX_MULTIPLICATION_OPERATOR_WITH_MASK_COMPLEMENT:
+    Lazy<VectorMatrixMultiplicationOperator<u8, u8, u8>> = Lazy::new(|| {
+    VectorMatrixMultiplicationOperator::<u8, u8, u8>::new(
+        Box::new(PlusTimes::<u8, u8, u8>::new()),
+        GRAPHBLAS_OPERATOR_OPTIONS_COMPLEMENT_STRUCTURE_MASK_TRANSPOSE_INPUT1.clone(),
+        None,
+    )
+});
+
+static GRAPHBLAS_COUNT_MULTIPLICATION_OPERATOR: Lazy<MatrixMultiplicationOperator<u64, u64, u64>> =
+    Lazy::new(|| {
+        MatrixMultiplicationOperator::<u64, u64, u64>::new(
+            &PlusTimes::<u64, u64, u64>::new(),
+            &DEFAULT_GRAPHBLAS_OPERATOR_OPTIONS,
+            None,
+        )
+    });
+
+static GRAPHBLAS_SCORE_VECTOR_MULTIPLICATION_OPERATOR: Lazy<
+    VectorMatrixMultiplicationOperator<f64, f64, f64>,
+> = Lazy::new(|| {
+    VectorMatrixMultiplicationOperator::<f64, f64, f64>::new(
+        Box::new(PlusTimes::<f64, f64, f64>::new()),
+        DEFAULT_GRAPHBLAS_OPERATOR_OPTIONS.clone(),
+        None,
+    )
+});
+
+static GRAPHBLAS_ROW_SUM_OPERATOR: Lazy<MonoidReducer<u64>> = Lazy::new(|| {
+    MonoidReducer::<u64>::new(
+        &Plus::<u64>::new(),
+        &DEFAULT_GRAPHBLAS_OPERATOR_OPTIONS,
+        None,
+    )
+});
+
+static GRAPHBLAS_COLUMN_SUM_OPERATOR: Lazy<MonoidReducer<u64>> = Lazy::new(|| {
+    MonoidReducer::<u64>::new(
+        &Plus::<u64>::new(),
+        &GRAPHBLAS_OPERATOR_OPTIONS_TRANSPOSE_INPUT0,
+        None,
+    )
+});
+
+static GRAPHBLAS_MATRIX_OR_OPERATOR: Lazy<ElementWiseMatrixAdditionMonoidOperator<bool>> =
+    Lazy::new(|| {
+        ElementWiseMatrixAdditionMonoidOperator::<bool>::new(
+            &LogicalOr::<bool>::new(),
+            &DEFAULT_GRAPHBLAS_OPERATOR_OPTIONS,
+            None,
+        )
+    });
+
+static GRAPHBLAS_MATRIX_TRANSPOSE_OPERATOR: Lazy<MatrixTranspose<bool, bool>> =
+    Lazy::new(|| MatrixTranspose::<bool, bool>::new(&DEFAULT_GRAPHBLAS_OPERATOR_OPTIONS, None));
+
+static GRAPHBLAS_COPY_WITHOUT_MASKED_ELEMENTS_OPERATOR: Lazy<UnaryOperatorApplier<bool>> =
+    Lazy::new(|| {
+        UnaryOperatorApplier::<bool>::new(
+            &Identity::<bool>::new(),
+            &GRAPHBLAS_OPERATOR_OPTIONS_COMPLEMENT_STRUCTURE_MASK,
+            None,
+        )
+    });
+
+static FIRST_BOOLEAN_BINARY_OPERATOR: Lazy<First<bool, bool, bool>> =
+    Lazy::new(First::<bool, bool, bool>::new);
+
+static FIRST_U8_BINARY_OPERATOR: Lazy<First<u8, u8, u8>> = Lazy::new(First::<u8, u8, u8>::new);
+
+static FIRST_U64_BINARY_OPERATOR: Lazy<First<u64, u64, u64>> =
+    Lazy::new(First::<u64, u64, u64>::new);
+
+static FIRST_F64_BINARY_OPERATOR: Lazy<First<f64, f64, f64>> =
+    Lazy::new(First::<f64, f64, f64>::new);
+
+/// Selects the (i, j) with a path i -> k -> j through multiplier and multiplicant.
+pub(crate) fn multiply(
+    multiplier: &SparseMatrix<bool>,
+    multiplicant: &SparseMatrix<bool>,
+) -> Result<SparseMatrix<bool>, GraphComputingError> {
+    let mut product = SparseMatrix::<u8>::new(
+        multiplier.context_ref(),
+        &Size::new(multiplier.row_height()?, multiplicant.column_width()?),
+    )?;
+    GRAPHBLAS_STRUCTURE_MULTIPLICATION_OPERATOR.apply(
+        &to_structure_matrix(multiplier)?,
+        &to_structure_matrix(multiplicant)?,
+        &mut product,
+    )?;
+    from_structure_matrix(&product)
+}
+
+/// Selects the j with an element (i, j) in structure_matrix for an i stored in vector.
+/// If is_transposed, selects the i with an element (i, j) for a j stored in vector instead.
+pub(crate) fn multiply_structure_vector(
+    vector: &SparseVector<u8>,
+    structure_matrix: &SparseMatrix<u8>,
+    is_transposed: bool,
+) -> Result<SparseVector<u8>, GraphComputingError> {
+    let mut product = SparseVector::new(vector.context_ref(), &vector.length()?)?;
+    let multiplication_operator = if is_transposed {
+        &*GRAPHBLAS_STRUCTURE_VECTOR_TRANSPOSED_MATRIX_MULTIPLICATION_OPERATOR
+    } else {
+        &*GRAPHBLAS_STRUCTURE_VECTOR_MULTIPLICATION_OPERATOR
+    };
+    multiplication_operator.apply(vector, structure_matrix, &mut product)?;
+    Ok(product)
+}
+
+/// Selects the j with an element (i, j) in structure_matrix for an i stored in vector, except the j stored in excluded.
+/// If is_transposed, selects the i with an element (i, j) for a j stored in vector instead.
+///
+/// Repeated products with the same matrix, e.g. in a breadth-first search, convert it with to_structure_matrix once.
+pub(crate) fn multiply_structure_vector_excluding(
+    vector: &SparseVector<u8>,
+    structure_matrix: &SparseMatrix<u8>,
+    is_transposed: bool,
+    excluded: &SparseVector<bool>,
+) -> Result<SparseVector<u8>, GraphComputingError> {
+    let mut product = SparseVector::new(vector.context_ref(), &vector.length()?)?;
+    let multiplication_operator = if is_transposed {
+        &*GRAPHBLAS_STRUCTURE_VECTOR_TRANSPOSED_MATRIX_MULTIPLICATION_OPERATOR_WITH_MASK_COMPLEMENT
+    } else {
+        &*GRAPHBLAS_STRUCTURE_VECTOR_MULTIPLICATION_OPERATOR_WITH_MASK_COMPLEMENT
+    };
+    multiplication_operator.apply_with_mask(excluded, vector, structure_matrix, &mut product)?;
+    Ok(product)
+}
+
+/// Stores an element at each of the indices.
+pub(crate) fn structure_vector_from_indices(
+    graphblas_context: &Arc<Context>,
+    length: ElementIndex,
+    indices: Vec<ElementIndex>,
+) -> Result<SparseVector<u8>, GraphComputingError> {
+    let values = vec![1u8; indices.len()];
+    Ok(SparseVector::from_element_list(
+        graphblas_context,
+        &length,
+        &VectorElementList::from_vectors(indices, values)?,
+        &*FIRST_U8_BINARY_OPERATOR,
+    )?)
+}
+
+/// Stores true at each index stored in structure_vector.
+pub(crate) fn from_structure_vector(
+    structure_vector: &SparseVector<u8>,
+) -> Result<SparseVector<bool>, GraphComputingError> {
+    let indices = structure_vector.get_element_list()?.indices_ref().to_vec();
+    let values = vec![true; indices.len()];
+    Ok(SparseVector::from_element_list(
+        structure_vector.context_ref(),
+        &structure_vector.length()?,
+        &VectorElementList::from_vectors(indices, values)?,
+        &*FIRST_BOOLEAN_BINARY_OPERATOR,
+    )?)
+}
+
+pub(crate) fn transpose(
+    matrix: &SparseMatrix<bool>,
+) -> Result<SparseMatrix<bool>, GraphComputingError> {
+    let mut transposed_matrix = SparseMatrix::new(
+        matrix.context_ref(),
+        &Size::new(matrix.column_width()?, matrix.row_height()?),
+    )?;
+    GRAPHBLAS_MATRIX_TRANSPOSE_OPERATOR.apply(matrix, &mut transposed_matrix)?;
+    Ok(transposed_matrix)
+}
+
+pub(crate) fn union(
+    matrix: &SparseMatrix<bool>,
+    other_matrix: &SparseMatrix<bool>,
+) -> Result<SparseMatrix<bool>, GraphComputingError> {
+    let mut union = SparseMatrix::new(matrix.context_ref(), &matrix.size()?)?;
+    GRAPHBLAS_MATRIX_OR_OPERATOR.apply(matrix, other_matrix, &mut union)?;
+    Ok(union)
+}
+
+/// Keeps the elements of matrix at the coordinates where elements_to_remove does not store an element.
+pub(crate) fn difference(
+    matrix: &SparseMatrix<bool>,
+    elements_to_remove: &SparseMatrix<bool>,
+) -> Result<SparseMatrix<bool>, GraphComputingError> {
+    let mut difference = SparseMatrix::new(matrix.context_ref(), &matrix.size()?)?;
+    GRAPHBLAS_COPY_WITHOUT_MASKED_ELEMENTS_OPERATOR.apply_to_matrix_with_mask(
+        matrix,
+        &mut difference,
+        elements_to_remove,
+    )?;
+    Ok(difference)
+}
+
+/// Stores true at each (row_indices[i], column_indices[i]).
+pub(crate) fn from_coordinates(
+    graphblas_context: &Arc<Context>,
+    size: &Size,
+    row_indices: Vec<ElementIndex>,
+    column_indices: Vec<ElementIndex>,
+) -> Result<SparseMatrix<bool>, GraphComputingError> {
+    let values = vec![true; row_indices.len()];
+    Ok(SparseMatrix::from_element_list(
+        graphblas_context,
+        size,
+        &MatrixElementList::from_vectors(row_indices, column_indices, values)?,
+        &*FIRST_BOOLEAN_BINARY_OPERATOR,
+    )?)
+}
+
+pub(crate) fn is_empty(matrix: &SparseMatrix<bool>) -> Result<bool, GraphComputingError> {
+    Ok(matrix.number_of_stored_elements()? == 0)
+}
+
+// Stored false values are not part of the structure
+pub(crate) fn to_structure_matrix(
+    matrix: &SparseMatrix<bool>,
+) -> Result<SparseMatrix<u8>, GraphComputingError> {
+    let (row_indices, column_indices) = true_element_coordinates(matrix)?;
+    let values = vec![1u8; row_indices.len()];
+    Ok(SparseMatrix::from_element_list(
+        matrix.context_ref(),
+        &matrix.size()?,
+        &MatrixElementList::from_vectors(row_indices, column_indices, values)?,
+        &*FIRST_U8_BINARY_OPERATOR,
+    )?)
+}
+
+/// A copy of matrix with the value 1 for every true element, to count elements with a plus reduction.
+pub(crate) fn to_count_matrix(
+    matrix: &SparseMatrix<bool>,
+) -> Result<SparseMatrix<u64>, GraphComputingError> {
+    let (row_indices, column_indices) = true_element_coordinates(matrix)?;
+    let values = vec![1u64; row_indices.len()];
+    Ok(SparseMatrix::from_element_list(
+        matrix.context_ref(),
+        &matrix.size()?,
+        &MatrixElementList::from_vectors(row_indices, column_indices, values)?,
+        &*FIRST_U64_BINARY_OPERATOR,
+    )?)
+}
+
+/// The sum of the elements in each row of matrix, or in each column if is_transposed.
+/// Rows without elements are not stored in the result.
+pub(crate) fn sum_per_row(
+    matrix: &SparseMatrix<u64>,
+    is_transposed: bool,
+) -> Result<SparseVector<u64>, GraphComputingError> {
+    if is_transposed {
+        let mut sums = SparseVector::new(matrix.context_ref(), &matrix.column_width()?)?;
+        GRAPHBLAS_COLUMN_SUM_OPERATOR.to_vector(matrix, &mut sums)?;
+        Ok(sums)
+    } else {
+        let mut sums = SparseVector::new(matrix.context_ref(), &matrix.row_height()?)?;
+        GRAPHBLAS_ROW_SUM_OPERATOR.to_vector(matrix, &mut sums)?;
+        Ok(sums)
+    }
+}
+
+/// A copy of matrix in which every true element of row i has the value 1 / (the number of true elements in row i),
+/// so the elements of every row that is not empty sum to 1.
+pub(crate) fn to_row_normalized_matrix(
+    matrix: &SparseMatrix<bool>,
+) -> Result<SparseMatrix<f64>, GraphComputingError> {
+    let row_sums = sum_per_row(&to_count_matrix(matrix)?, false)?.get_element_list()?;
+    let mut number_of_elements_per_row = vec![0u64; matrix.row_height()?];
+    for (row_index, row_sum) in row_sums
+        .indices_ref()
+        .iter()
+        .zip(row_sums.values_ref().iter())
+    {
+        number_of_elements_per_row[*row_index] = *row_sum;
+    }
+
+    let (row_indices, column_indices) = true_element_coordinates(matrix)?;
+    let values = row_indices
+        .iter()
+        .map(|row_index| 1.0 / number_of_elements_per_row[*row_index] as f64)
+        .collect();
+    Ok(SparseMatrix::from_element_list(
+        matrix.context_ref(),
+        &matrix.size()?,
+        &MatrixElementList::from_vectors(row_indices, column_indices, values)?,
+        &*FIRST_F64_BINARY_OPERATOR,
+    )?)
+}
+
+/// Stores values[i] at indices[i].
+pub(crate) fn score_vector_from_elements(
+    graphblas_context: &Arc<Context>,
+    length: ElementIndex,
+    indices: Vec<ElementIndex>,
+    values: Vec<f64>,
+) -> Result<SparseVector<f64>, GraphComputingError> {
+    Ok(SparseVector::from_element_list(
+        graphblas_context,
+        &length,
+        &VectorElementList::from_vectors(indices, values)?,
+        &*FIRST_F64_BINARY_OPERATOR,
+    )?)
+}
+
+/// The plus-times vector-matrix product: element j is the sum over i of vector[i] * matrix[i, j].
+pub(crate) fn multiply_score_vector(
+    vector: &SparseVector<f64>,
+    matrix: &SparseMatrix<f64>,
+) -> Result<SparseVector<f64>, GraphComputingError> {
+    let mut product = SparseVector::new(vector.context_ref(), &matrix.column_width()?)?;
+    GRAPHBLAS_SCORE_VECTOR_MULTIPLICATION_OPERATOR.apply(vector, matrix, &mut product)?;
+    Ok(product)
+}
+
+/// For each element (i, j) of count_matrix, the number of k with elements (i, k) and (k, j).
+/// The product is masked by count_matrix, so the paths i -> k -> j that are not closed by i -> j are never stored.
+pub(crate) fn count_paths_of_length_two_closed_by_an_element(
+    count_matrix: &SparseMatrix<u64>,
+) -> Result<SparseMatrix<u64>, GraphComputingError> {
+    let mut product = SparseMatrix::new(count_matrix.context_ref(), &count_matrix.size()?)?;
+    GRAPHBLAS_COUNT_MULTIPLICATION_OPERATOR.apply_with_mask(
+        count_matrix,
+        count_matrix,
+        count_matrix,
+        &mut product,
+    )?;
+    Ok(product)
+}
+
+fn true_element_coordinates(
+    matrix: &SparseMatrix<bool>,
+) -> Result<(Vec<ElementIndex>, Vec<ElementIndex>), GraphComputingError> {
+    let element_list = matrix.get_element_list()?;
+    let mut row_indices = Vec::with_capacity(element_list.length());
+    let mut column_indices = Vec::with_capacity(element_list.length());
+    for element_index in 0..element_list.length() {
+        if element_list.values_ref()[element_index] {
+            row_indices.push(element_list.row_indices_ref()[element_index]);
+            column_indices.push(element_list.column_indices_ref()[element_index]);
+        }
+    }
+    Ok((row_indices, column_indices))
+}
+
+fn from_structure_matrix(
+    matrix: &SparseMatrix<u8>,
+) -> Result<SparseMatrix<bool>, GraphComputingError> {
+    let element_list = matrix.get_element_list()?;
+    from_coordinates(
+        matrix.context_ref(),
+        &matrix.size()?,
+        element_list.row_indices_ref().to_vec(),
+        element_list.column_indices_ref().to_vec(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use graphblas_sparse_linear_algebra::context::Mode;
+    use graphblas_sparse_linear_algebra::value_types::sparse_matrix::{
+        Coordinate, GetMatrixElementValue, MatrixElement, SetMatrixElement,
+    };
+
+    #[test]
+    fn boolean_product_ignores_overflowing_sums() {
+        let context = Context::init_ready(Mode::NonBlocking).unwrap();
+        let size = Size::new(300, 300);
+
+        // 256 paths from 0 to 299 sum to 0 in u8 arithmetic
+        let mut multiplier = SparseMatrix::<bool>::new(&context, &size).unwrap();
+        let mut multiplicant = SparseMatrix::<bool>::new(&context, &size).unwrap();
+        for intermediate_index in 1..257 {
+            multiplier
+                .set_element(MatrixElement::new(
+                    Coordinate::new(0, intermediate_index),
+                    true,
+                ))
+                .unwrap();
+            multiplicant
+                .set_element(MatrixElement::new(
+                    Coordinate::new(intermediate_index, 299),
+                    true,
+                ))
+                .unwrap();
+        }
+        multiplier
+            .set_element(MatrixElement::new(Coordinate::new(1, 2), false))
+            .unwrap();
+
+        let product = multiply(&multiplier, &multiplicant).unwrap();
+        assert_eq!(product.number_of_stored_elements().unwrap(), 1);
+        assert!(product.get_element_value(&Coordinate::new(0, 299)).unwrap());
+
+        let remaining = difference(&union(&multiplier, &product).unwrap(), &multiplier).unwrap();
+        assert_eq!(remaining.number_of_stored_elements().unwrap(), 1);
+        assert!(transpose(&remaining)
+            .unwrap()
+            .get_element_value(&Coordinate::new(299, 0))
+            .unwrap());
+        assert!(!is_empty(&remaining).unwrap());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::error::{GraphComputingError, LogicError, LogicErrorType};
 use crate::graph::graph::Graph;
-use crate::graph::vertex::{VertexIndex, VertexKey};
+use crate::graph::vertex::{VertexIndex, VertexKey, VertexValue};
 
 use super::adjacency_matrix::EdgeCoordinate;
 use super::edge_type::{EdgeType, EdgeTypeIndex, EdgeTypeRef};
@@ -22,6 +22,31 @@ use super::edge_type::{EdgeType, EdgeTypeIndex, EdgeTypeRef};
 // Such a reference would be an immutable reference to the graph.
 // Then, the graph cannot be modified as long as this edge/vertex exists.
 
+pub type EdgeWeight = f64;
+
+/// The weight of an edge without an explicitly set weight
+pub const DEFAULT_EDGE_WEIGHT: EdgeWeight = 1.0;
+
+pub type IntegerEdgeWeight = i64;
+
+/// The weight of an edge of an integer weighted edge type without an explicitly set weight
+pub const DEFAULT_INTEGER_EDGE_WEIGHT: IntegerEdgeWeight = 1;
+
+/// The value type of the weights of an edge type, declared when the edge type is added.
+/// Numeric weights are stored in a GraphBLAS matrix of their type, next to the boolean adjacency matrix that holds the edges.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EdgeWeightType {
+    /// The weight of an edge is its presence, weights cannot be set
+    Boolean,
+    /// Weights are set with SetEdgeWeight::set_integer_edge_weight_using_keys()
+    Integer64Bit,
+    /// Weights are set with SetEdgeWeight::set_edge_weight_using_keys(), the weight type of an edge type by default
+    FloatingPoint64Bit,
+}
+
+/// A value attached to an individual edge, e.g. the confidence in a relation or the document it was taken from
+pub type EdgeValue = VertexValue;
+
 pub(crate) trait EdgeToEdgeCoordinate {
     fn key_defined_edge_to_edge_coordinate(
         &self,
@@ -104,6 +129,7 @@ pub struct DirectedEdgeDefinedByKeys {
     from_vertex: VertexKey,
     edge_type: EdgeType,
     to_vertex: VertexKey,
+    value: Option<EdgeValue>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -120,6 +146,22 @@ impl DirectedEdgeDefinedByKeys {
             from_vertex,
             edge_type,
             to_vertex,
+            value: None,
+        }
+    }
+
+    /// Adding the edge stores the value with it, see SetEdgeValue.
+    pub fn new_with_value(
+        from_vertex: VertexKey,
+        edge_type: EdgeType,
+        to_vertex: VertexKey,
+        value: EdgeValue,
+    ) -> Self {
+        Self {
+            from_vertex,
+            edge_type,
+            to_vertex,
+            value: Some(value),
         }
     }
 
@@ -132,6 +174,9 @@ impl DirectedEdgeDefinedByKeys {
     pub fn points_to_vertex(&self) -> &VertexKey {
         &self.to_vertex
     }
+    pub fn value_ref(&self) -> Option<&EdgeValue> {
+        self.value.as_ref()
+    }
 }
 
 impl DirectedEdgeDefinedByIndices {
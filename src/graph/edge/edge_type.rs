@@ -89,6 +89,7 @@ mod tests {
     use super::*;
 
     use crate::graph::edge::adjacency_matrix::AdjacencyMatrix;
+    use crate::graph::edge::EdgeWeightType;
     use crate::graph::graph::GraphTrait;
 
     #[test]
@@ -100,6 +101,7 @@ mod tests {
             graph.graphblas_context_ref(),
             edge_type_key_1.clone(),
             graph.vertex_capacity().unwrap(),
+            EdgeWeightType::FloatingPoint64Bit,
         )
         .unwrap();
 
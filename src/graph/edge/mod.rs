@@ -2,6 +2,7 @@ mod edge;
 mod edge_type;
 
 pub(crate) mod adjacency_matrix;
+pub(crate) mod boolean_matrix_operations;
 
 // re-export mod edge to reduce the depth of the public module tree.
 // As long as mod edge remains the only public mod in this module,
@@ -1,19 +1,22 @@
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use graphblas_sparse_linear_algebra::{
     context::{Context as GraphblasContext, Mode as GraphblasMode},
     util::ElementIndex as GraphblasElementIndex,
-    value_types::sparse_vector::SparseVector,
+    value_types::sparse_vector::{GetVectorElementList, SparseVector},
 };
 use hashbrown::HashMap;
 
 use super::edge::{EdgeType, EdgeTypeIndex, EdgeTypeRef};
-use super::vertex::{Vertex, VertexIndex, VertexKey};
+use super::schema::Schema;
+use super::vertex::{Vertex, VertexIndex, VertexKey, VertexKeyRef, VertexLabel};
 use crate::graph::edge::adjacency_matrix::AdjacencyMatrix;
 
 use crate::error::{GraphComputingError, LogicError, LogicErrorType, UserError, UserErrorType};
 use crate::graph::indexed_data_store::data_store::IndexedDataStore;
 use crate::operations::{add_edge_type::AddEdgeType, drop_edge_type::DropEdgeType};
+use crate::persistence::write_ahead_log::WriteAheadLog;
 
 // NOTE: by default, SuiteSparse:GraphBLAS uses Compressed Sparse Row (CSR) format.
 // Row operations should therefore be faster.
@@ -33,23 +36,89 @@ pub trait GraphTrait {
     fn number_of_vertices(&self) -> Result<ElementCount, GraphComputingError>;
     fn number_of_edge_types(&self) -> Result<ElementCount, GraphComputingError>;
     fn vertex_capacity(&self) -> Result<ElementIndex, GraphComputingError>;
-    // TODO: number of edges
-    // TODO: number of edges per edge type, etc
+    fn number_of_edges(&self) -> Result<ElementCount, GraphComputingError>;
+    fn number_of_edges_of_type(
+        &self,
+        edge_type: &EdgeTypeRef,
+    ) -> Result<ElementCount, GraphComputingError>;
+
+    /// The number of edges of any of edge_types that point to vertex_key
+    fn in_degree(
+        &self,
+        vertex_key: &VertexKeyRef,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<ElementCount, GraphComputingError>;
+
+    /// The number of edges of any of edge_types that start at vertex_key
+    fn out_degree(
+        &self,
+        vertex_key: &VertexKeyRef,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<ElementCount, GraphComputingError>;
+
+    /// Maps each in-degree over edge_types to the number of vertices with that in-degree
+    fn in_degree_histogram(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<BTreeMap<ElementCount, ElementCount>, GraphComputingError>;
+
+    /// Maps each out-degree over edge_types to the number of vertices with that out-degree
+    fn out_degree_histogram(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<BTreeMap<ElementCount, ElementCount>, GraphComputingError>;
 }
 
 // pub struct Graph<VertexKey: Hash + Eq + PartialEq, EdgeType: Hash + Eq + PartialEq> {
-#[derive(Clone, Debug)]
+#[derive(Debug)]
 pub struct Graph {
     graphblas_context: Arc<GraphblasContext>,
 
     vertex_store: IndexedDataStore<Vertex>,
-    vertex_key_to_vertex_index_map: HashMap<VertexKey, VertexIndex>, // maps a vertex key to a Vertex
+    // The key maps are shared between clones until modified, as are the vertex store and adjacency matrices
+    vertex_key_to_vertex_index_map: Arc<HashMap<VertexKey, VertexIndex>>, // maps a vertex key to a Vertex
     // vertex_set: FxHashSet<String>,
     // edge_types: IndexedDataStore<EdgeType>,
     adjacency_matrices: IndexedDataStore<AdjacencyMatrix>,
     // edges: IndexedDataStore<Vec<DirectedEdge>>, // first dimension over edge_type, second over adjacency_matrix element index
-    edge_type_to_edge_type_index_map: HashMap<EdgeType, EdgeTypeIndex>, // maps an edge type key to an adjacency matrix
-                                                                        // edge_set: FxHashSet<String>,                // TODO: type, unique connections
+    edge_type_to_edge_type_index_map: Arc<HashMap<EdgeType, EdgeTypeIndex>>, // maps an edge type key to an adjacency matrix
+    // edge_set: FxHashSet<String>,                // TODO: type, unique connections
+    // Like the mask with valid vertex indices, each mask has the length of the vertex capacity
+    vertex_label_masks: Arc<HashMap<VertexLabel, SparseVector<bool>>>,
+    schema: Arc<Schema>,
+    write_ahead_log: Option<WriteAheadLog>,
+}
+
+// A clone is an in-memory copy. Sharing the write-ahead log would interleave the mutations of both graphs.
+// Cloning is cheap, data is only copied when either graph modifies it.
+impl Clone for Graph {
+    fn clone(&self) -> Self {
+        Self {
+            graphblas_context: self.graphblas_context.clone(),
+            vertex_store: self.vertex_store.clone(),
+            vertex_key_to_vertex_index_map: self.vertex_key_to_vertex_index_map.clone(),
+            adjacency_matrices: self.adjacency_matrices.clone(),
+            edge_type_to_edge_type_index_map: self.edge_type_to_edge_type_index_map.clone(),
+            vertex_label_masks: self.vertex_label_masks.clone(),
+            schema: self.schema.clone(),
+            write_ahead_log: None,
+        }
+    }
+}
+
+impl Graph {
+    // A clone shares its GraphBLAS matrices and vectors until either graph modifies them. Replaces them with copies,
+    // such that the clone can be used by another thread. GraphBLAS completes pending operations while copying.
+    pub(crate) fn copy_graphblas_objects(&mut self) -> Result<(), GraphComputingError> {
+        self.vertex_store.copy_mask_with_valid_indices();
+        self.adjacency_matrices.copy_mask_with_valid_indices();
+        self.adjacency_matrices.map_mut_all(|adjacency_matrix| {
+            adjacency_matrix.copy_graphblas_objects();
+            Ok(())
+        })?;
+        self.vertex_label_masks = Arc::new(self.vertex_label_masks.as_ref().clone());
+        Ok(())
+    }
 }
 
 // let mut map: FxHashMap<String, ElementIndex> = FxHashMap::default();
@@ -73,8 +142,68 @@ impl GraphTrait for Graph {
         Ok(self.vertex_store.get_capacity()?)
     }
 
-    // TODO: number of edges
-    // TODO: number of edges for edge type
+    fn number_of_edges(&self) -> Result<ElementCount, GraphComputingError> {
+        let mut number_of_edges = 0;
+        for edge_type_index in self.edge_type_to_edge_type_index_map.values() {
+            number_of_edges += self
+                .adjacency_matrices
+                .get_ref(*edge_type_index)?
+                .number_of_edges()?;
+        }
+        Ok(number_of_edges)
+    }
+
+    fn number_of_edges_of_type(
+        &self,
+        edge_type: &EdgeTypeRef,
+    ) -> Result<ElementCount, GraphComputingError> {
+        self.get_edge_adjacency_matrix_ref(edge_type)?
+            .number_of_edges()
+    }
+
+    fn in_degree(
+        &self,
+        vertex_key: &VertexKeyRef,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<ElementCount, GraphComputingError> {
+        let vertex_index = self.existing_vertex_index(vertex_key)?;
+        let mut degree = 0;
+        for edge_type in edge_types {
+            degree += self
+                .get_edge_adjacency_matrix_ref(edge_type)?
+                .in_degree(vertex_index)?;
+        }
+        Ok(degree)
+    }
+
+    fn out_degree(
+        &self,
+        vertex_key: &VertexKeyRef,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<ElementCount, GraphComputingError> {
+        let vertex_index = self.existing_vertex_index(vertex_key)?;
+        let mut degree = 0;
+        for edge_type in edge_types {
+            degree += self
+                .get_edge_adjacency_matrix_ref(edge_type)?
+                .out_degree(vertex_index)?;
+        }
+        Ok(degree)
+    }
+
+    fn in_degree_histogram(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<BTreeMap<ElementCount, ElementCount>, GraphComputingError> {
+        self.degree_histogram(edge_types, true)
+    }
+
+    fn out_degree_histogram(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+    ) -> Result<BTreeMap<ElementCount, ElementCount>, GraphComputingError> {
+        self.degree_histogram(edge_types, false)
+    }
 }
 
 // impl Graph<VertexKey, EdgeKey> {
@@ -103,7 +232,7 @@ impl<'g> Graph {
                 initial_vertex_capacity,
                 graphblas_context.clone(),
             )?,
-            vertex_key_to_vertex_index_map,
+            vertex_key_to_vertex_index_map: Arc::new(vertex_key_to_vertex_index_map),
 
             // edge_types: IndexedDataStore::with_capacity(&edge_capacity),
             adjacency_matrices: IndexedDataStore::with_capacity(
@@ -111,8 +240,11 @@ impl<'g> Graph {
                 graphblas_context.clone(),
             )?,
             // edges: IndexedDataStore::with_capacity(&initial_edge_capacity), // TODO: consider if this can be made more efficient by reserving less memory
-            edge_type_to_edge_type_index_map,
+            edge_type_to_edge_type_index_map: Arc::new(edge_type_to_edge_type_index_map),
             // edge_set,
+            vertex_label_masks: Arc::new(HashMap::default()),
+            schema: Arc::new(Schema::new()),
+            write_ahead_log: None,
         };
 
         // allocate a dummy adjacency matrix to support self.expand_adjacency_matrices_to_match_target_capacity(),
@@ -124,6 +256,28 @@ impl<'g> Graph {
         Ok(graph)
     }
 
+    // Restores a graph from previously persisted parts, see crate::persistence.
+    pub(crate) fn from_parts(
+        graphblas_context: Arc<GraphblasContext>,
+        vertex_store: IndexedDataStore<Vertex>,
+        vertex_key_to_vertex_index_map: HashMap<VertexKey, VertexIndex>,
+        adjacency_matrices: IndexedDataStore<AdjacencyMatrix>,
+        edge_type_to_edge_type_index_map: HashMap<EdgeType, EdgeTypeIndex>,
+        vertex_label_masks: HashMap<VertexLabel, SparseVector<bool>>,
+        schema: Schema,
+    ) -> Self {
+        Self {
+            graphblas_context,
+            vertex_store,
+            vertex_key_to_vertex_index_map: Arc::new(vertex_key_to_vertex_index_map),
+            adjacency_matrices,
+            edge_type_to_edge_type_index_map: Arc::new(edge_type_to_edge_type_index_map),
+            vertex_label_masks: Arc::new(vertex_label_masks),
+            schema: Arc::new(schema),
+            write_ahead_log: None,
+        }
+    }
+
     pub(crate) fn graphblas_context_ref(&self) -> &Arc<GraphblasContext> {
         &self.graphblas_context
     }
@@ -144,7 +298,7 @@ impl<'g> Graph {
     pub(crate) fn vertex_key_to_vertex_index_map_mut_ref(
         &mut self,
     ) -> &mut HashMap<VertexKey, VertexIndex> {
-        &mut self.vertex_key_to_vertex_index_map
+        Arc::make_mut(&mut self.vertex_key_to_vertex_index_map)
     }
 
     pub(crate) fn adjacency_matrices_ref(&self) -> &IndexedDataStore<AdjacencyMatrix> {
@@ -160,7 +314,30 @@ impl<'g> Graph {
     pub(crate) fn edge_type_to_edge_type_index_map_mut_ref(
         &mut self,
     ) -> &mut HashMap<EdgeType, EdgeTypeIndex> {
-        &mut self.edge_type_to_edge_type_index_map
+        Arc::make_mut(&mut self.edge_type_to_edge_type_index_map)
+    }
+
+    pub(crate) fn vertex_label_masks_ref(&self) -> &HashMap<VertexLabel, SparseVector<bool>> {
+        &self.vertex_label_masks
+    }
+    pub(crate) fn vertex_label_masks_mut_ref(
+        &mut self,
+    ) -> &mut HashMap<VertexLabel, SparseVector<bool>> {
+        Arc::make_mut(&mut self.vertex_label_masks)
+    }
+
+    pub(crate) fn schema_ref(&self) -> &Schema {
+        &self.schema
+    }
+    pub(crate) fn schema_mut_ref(&mut self) -> &mut Schema {
+        Arc::make_mut(&mut self.schema)
+    }
+
+    pub(crate) fn write_ahead_log_ref(&self) -> &Option<WriteAheadLog> {
+        &self.write_ahead_log
+    }
+    pub(crate) fn write_ahead_log_mut_ref(&mut self) -> &mut Option<WriteAheadLog> {
+        &mut self.write_ahead_log
     }
 
     pub(crate) fn expand_adjacency_matrices_to_match_vertex_capacity(
@@ -188,6 +365,23 @@ impl<'g> Graph {
         // }
     }
 
+    pub(crate) fn expand_vertex_label_masks_to_match_vertex_capacity(
+        &mut self,
+    ) -> Result<(), GraphComputingError> {
+        let target_capacity = self.vertex_capacity()?;
+        let mut is_resize_required = false;
+        for vertex_label_mask in self.vertex_label_masks.values() {
+            is_resize_required |= vertex_label_mask.length()? < target_capacity;
+        }
+        if is_resize_required {
+            // Copies the masks that are shared with a clone of the graph
+            for vertex_label_mask in self.vertex_label_masks_mut_ref().values_mut() {
+                vertex_label_mask.resize(target_capacity)?;
+            }
+        }
+        Ok(())
+    }
+
     pub(crate) fn get_edge_adjacency_matrix_ref(
         &self,
         edge_type: &EdgeTypeRef,
@@ -250,15 +444,72 @@ impl<'g> Graph {
         self.vertex_store.mask_with_valid_indices_ref()
     }
 
+    /// The indices of all vertices, in ascending order.
+    /// The mask may store false values for freed indices, these are not included.
+    pub(crate) fn valid_vertex_indices(&self) -> Result<Vec<ElementIndex>, GraphComputingError> {
+        let vertex_mask_elements = self.index_mask_with_all_vertices().get_element_list()?;
+        Ok(vertex_mask_elements
+            .indices_ref()
+            .iter()
+            .zip(vertex_mask_elements.values_ref().iter())
+            .filter(|(_, &is_valid)| is_valid)
+            .map(|(&index, _)| index)
+            .collect())
+    }
+
     pub(crate) fn index_mask_with_all_adjacency_matrices(&self) -> &SparseVector<bool> {
         self.adjacency_matrices.mask_with_valid_indices_ref()
     }
+
+    // The sum of the degrees over edge_types of each vertex with at least one edge
+    fn degrees(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+        is_incoming: bool,
+    ) -> Result<HashMap<ElementIndex, ElementCount>, GraphComputingError> {
+        let mut degrees: HashMap<ElementIndex, ElementCount> = HashMap::new();
+        for edge_type in edge_types {
+            let adjacency_matrix = self.get_edge_adjacency_matrix_ref(edge_type)?;
+            let edge_type_degrees = if is_incoming {
+                adjacency_matrix.in_degrees()?
+            } else {
+                adjacency_matrix.out_degrees()?
+            }
+            .get_element_list()?;
+            for (vertex_index, degree) in edge_type_degrees
+                .indices_ref()
+                .iter()
+                .zip(edge_type_degrees.values_ref().iter())
+            {
+                *degrees.entry(*vertex_index).or_default() += *degree as ElementCount;
+            }
+        }
+        Ok(degrees)
+    }
+
+    fn degree_histogram(
+        &self,
+        edge_types: &[&EdgeTypeRef],
+        is_incoming: bool,
+    ) -> Result<BTreeMap<ElementCount, ElementCount>, GraphComputingError> {
+        let degrees = self.degrees(edge_types, is_incoming)?;
+        let mut histogram = BTreeMap::new();
+        for vertex_index in self.valid_vertex_indices()? {
+            *histogram
+                .entry(degrees.get(&vertex_index).copied().unwrap_or(0))
+                .or_default() += 1;
+        }
+        Ok(histogram)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use crate::error::GraphComputingErrorType;
+    use crate::graph::edge::DirectedEdgeDefinedByKeys;
+    use crate::operations::add_edge::AddEdge;
     use crate::operations::add_vertex::AddVertex;
     use crate::operations::read_vertex_value::ReadVertexValue;
 
@@ -269,6 +520,67 @@ mod tests {
         // assert_eq!(graph.number_of_edge_types().unwrap(), 0); // TODO: fix this
     }
 
+    #[test]
+    fn edge_counts_and_degrees() {
+        let mut graph = Graph::new(10, 20).unwrap();
+        for vertex_key in ["a", "b", "c", "d"] {
+            graph
+                .add_or_replace_vertex(Vertex::new(String::from(vertex_key), 0u8.into()))
+                .unwrap();
+        }
+        for (from_vertex, edge_type, to_vertex) in [
+            ("a", "references", "b"),
+            ("a", "references", "c"),
+            ("b", "references", "c"),
+            ("a", "cites", "c"),
+        ] {
+            graph
+                .add_edge_and_edge_type_using_keys(DirectedEdgeDefinedByKeys::new(
+                    String::from(from_vertex),
+                    String::from(edge_type),
+                    String::from(to_vertex),
+                ))
+                .unwrap();
+        }
+
+        assert_eq!(graph.number_of_edges().unwrap(), 4);
+        assert_eq!(graph.number_of_edges_of_type("references").unwrap(), 3);
+        assert_eq!(graph.out_degree("a", &["references"]).unwrap(), 2);
+        assert_eq!(graph.out_degree("a", &["references", "cites"]).unwrap(), 3);
+        assert_eq!(graph.in_degree("c", &["references", "cites"]).unwrap(), 3);
+        assert_eq!(graph.in_degree("d", &["references"]).unwrap(), 0);
+        assert_eq!(
+            graph
+                .in_degree_histogram(&["references", "cites"])
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![(0, 2), (1, 1), (3, 1)]
+        );
+        assert_eq!(
+            graph
+                .out_degree_histogram(&["references"])
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![(0, 2), (1, 1), (2, 1)]
+        );
+        assert_eq!(
+            graph
+                .in_degree("z", &["references"])
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::VertexKeyNotFound)
+        );
+        assert_eq!(
+            graph
+                .number_of_edges_of_type("extends")
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::EdgeTypeDoesNotExist)
+        );
+    }
+
     #[test]
     fn graph_isolation() {
         let mut graph_1 = Graph::new(10, 20).unwrap();
@@ -21,28 +21,49 @@ pub struct IndexedDataStore<T>
 where
     T: Send + Sync,
 {
-    data: Vec<T>,
+    // Shared between clones until modified, which makes cloning cheap, e.g. for read snapshots and transactions
+    data: Arc<Vec<T>>,
     indices_available_for_reuse: VecDeque<Index>,
 
     _graphblas_context: Arc<GraphBLASContext>,
-    mask_with_valid_indices: SparseVector<bool>,
+    mask_with_valid_indices: Arc<SparseVector<bool>>,
 }
 
-impl<T: Send + Sync> IndexedDataStore<T> {
+impl<T: Clone + Send + Sync> IndexedDataStore<T> {
     pub(crate) fn with_capacity(
         initial_capacity: Index,
         graphblas_context: Arc<GraphBLASContext>,
     ) -> Result<Self, GraphComputingError> {
         Ok(Self {
             // data: RwLock::new(Vec::with_capacity(*initial_capacity)),
-            data: Vec::with_capacity(initial_capacity),
+            data: Arc::new(Vec::with_capacity(initial_capacity)),
             indices_available_for_reuse: VecDeque::new(),
 
             _graphblas_context: graphblas_context.clone(),
-            mask_with_valid_indices: SparseVector::new(&graphblas_context, &initial_capacity)?,
+            mask_with_valid_indices: Arc::new(SparseVector::new(
+                &graphblas_context,
+                &initial_capacity,
+            )?),
         })
     }
 
+    // Restores a store from previously persisted parts, see crate::persistence.
+    // The caller is responsible for the consistency between the parts.
+    pub(crate) fn from_parts(
+        data: Vec<T>,
+        indices_available_for_reuse: VecDeque<Index>,
+        mask_with_valid_indices: SparseVector<bool>,
+        graphblas_context: Arc<GraphBLASContext>,
+    ) -> Self {
+        Self {
+            data: Arc::new(data),
+            indices_available_for_reuse,
+
+            _graphblas_context: graphblas_context,
+            mask_with_valid_indices: Arc::new(mask_with_valid_indices),
+        }
+    }
+
     pub(crate) fn push(
         &mut self,
         data_to_push: T,
@@ -54,31 +75,32 @@ impl<T: Send + Sync> IndexedDataStore<T> {
         // downside: runtime cost; more complexity; no use of speedy pre-allocation; memory is never deallocated
         // let data = self.get_write_locked_data()?;
         if available_index < self.data.len() {
-            self.mask_with_valid_indices
+            self.mask_with_valid_indices_mut_ref()
                 .set_element(VectorElement::from_pair(available_index, true))?;
-            self.data[available_index] = data_to_push;
+            self.data_mut_ref()[available_index] = data_to_push;
         } else {
             if available_index < self.mask_with_valid_indices.length()? {
-                self.mask_with_valid_indices
+                self.mask_with_valid_indices_mut_ref()
                     .set_element(VectorElement::from_pair(available_index, true))?;
-                self.data.push(data_to_push);
+                self.data_mut_ref().push(data_to_push);
             } else {
-                self.data.push(data_to_push);
-                match self.mask_with_valid_indices.resize(self.data.capacity()) {
+                self.data_mut_ref().push(data_to_push);
+                let capacity = self.data.capacity();
+                match self.mask_with_valid_indices_mut_ref().resize(capacity) {
                     Ok(_) => {
                         match self
-                            .mask_with_valid_indices
+                            .mask_with_valid_indices_mut_ref()
                             .set_element(VectorElement::from_pair(available_index, true))
                         {
                             Ok(_) => (),
                             Err(error) => {
-                                self.data.pop();
+                                self.data_mut_ref().pop();
                                 return Err(error.into());
                             }
                         }
                     }
                     Err(error) => {
-                        self.data.pop();
+                        self.data_mut_ref().pop();
                         return Err(error.into());
                     }
                 }
@@ -101,7 +123,7 @@ impl<T: Send + Sync> IndexedDataStore<T> {
         // #[cfg(debug_assertions)]
         self.check_index(&index)?;
 
-        Ok(&mut self.data[index.index()])
+        Ok(&mut self.data_mut_ref()[index.index()])
     }
 
     pub(crate) fn is_valid_index<I: IndexTrait>(
@@ -136,13 +158,42 @@ impl<T: Send + Sync> IndexedDataStore<T> {
         &self.mask_with_valid_indices
     }
 
+    // includes freed elements
+    pub(crate) fn data_ref(&self) -> &Vec<T> {
+        &self.data
+    }
+
+    // Copies shared data before modification. Unlike Arc::make_mut(), the copy keeps the capacity,
+    // which determines the vertex capacity of the graph.
+    fn data_mut_ref(&mut self) -> &mut Vec<T> {
+        if Arc::get_mut(&mut self.data).is_none() {
+            let mut data = Vec::with_capacity(self.data.capacity());
+            data.extend_from_slice(&self.data);
+            self.data = Arc::new(data);
+        }
+        Arc::make_mut(&mut self.data)
+    }
+
+    // Stops sharing the mask with clones, see Graph::copy_graphblas_objects()
+    pub(crate) fn copy_mask_with_valid_indices(&mut self) {
+        self.mask_with_valid_indices = Arc::new(self.mask_with_valid_indices.as_ref().clone());
+    }
+
+    fn mask_with_valid_indices_mut_ref(&mut self) -> &mut SparseVector<bool> {
+        Arc::make_mut(&mut self.mask_with_valid_indices)
+    }
+
+    pub(crate) fn indices_available_for_reuse_ref(&self) -> &VecDeque<Index> {
+        &self.indices_available_for_reuse
+    }
+
     /// Apply function to all stored elements
     pub(crate) fn map_mut_all<F>(&mut self, function_to_apply: F) -> Result<(), GraphComputingError>
     where
         F: Fn(&mut T) -> Result<(), GraphComputingError> + Send + Sync,
     {
         let result: Vec<_> = self
-            .data
+            .data_mut_ref()
             .as_mut_slice()
             .into_par_iter()
             .map(function_to_apply)
@@ -162,7 +213,7 @@ impl<T: Send + Sync> IndexedDataStore<T> {
         // #[cfg(debug_assertions)]
         self.check_index(&index)?;
 
-        self.data[index.index()] = data_to_set;
+        self.data_mut_ref()[index.index()] = data_to_set;
         Ok(())
     }
 
@@ -171,7 +222,7 @@ impl<T: Send + Sync> IndexedDataStore<T> {
         &mut self,
         index: I,
     ) -> Result<(), GraphComputingError> {
-        self.mask_with_valid_indices
+        self.mask_with_valid_indices_mut_ref()
             .drop_element(index.index_ref().clone())?;
         self.indices_available_for_reuse.push_back(index.index());
         Ok(())
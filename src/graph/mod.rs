@@ -1,4 +1,6 @@
 pub mod edge;
 pub mod graph;
 pub(crate) mod indexed_data_store;
+pub mod read_snapshot;
+pub mod schema;
 pub mod vertex;
@@ -0,0 +1,139 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use super::graph::Graph;
+use crate::error::GraphComputingError;
+
+/// An immutable view of a graph, as it was when the snapshot was taken.
+///
+/// Selections run against the snapshot, while the graph it was taken from continues to accept mutations.
+/// A snapshot shares its vertices, keys and edge values with the graph until the graph modifies them (copy-on-write).
+/// Its GraphBLAS matrices and vectors are copied when the snapshot is taken, as GraphBLAS may complete pending
+/// operations on an object while reading it, which is not safe while another thread uses the same object.
+/// Cloning a snapshot is cheap, and snapshots can be sent to other threads.
+#[derive(Clone, Debug)]
+pub struct ReadSnapshot {
+    graph: Arc<Graph>,
+}
+
+impl Deref for ReadSnapshot {
+    type Target = Graph;
+
+    fn deref(&self) -> &Graph {
+        &self.graph
+    }
+}
+
+pub trait Snapshot {
+    fn snapshot(&self) -> Result<ReadSnapshot, GraphComputingError>;
+}
+
+impl Snapshot for Graph {
+    fn snapshot(&self) -> Result<ReadSnapshot, GraphComputingError> {
+        let mut graph = self.clone();
+        graph.copy_graphblas_objects()?;
+        Ok(ReadSnapshot {
+            graph: Arc::new(graph),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::graph::edge::DirectedEdgeDefinedByKeys;
+    use crate::graph::graph::GraphTrait;
+    use crate::graph::vertex::{Vertex, VertexValue};
+    use crate::operations::add_edge::AddEdge;
+    use crate::operations::add_vertex::AddVertex;
+    use crate::operations::delete_edge::DeleteEdge;
+    use crate::operations::delete_vertex::DeleteVertex;
+    use crate::operations::read_edge::ReadEdge;
+    use crate::operations::read_vertex_value::ReadVertexValue;
+    use crate::operations::select_vertex::SelectVertex;
+    use crate::operations::update_vertex::UpdateVertex;
+    use crate::tests::standard_graph_for_testing::standard_graph_for_testing;
+
+    #[test]
+    fn snapshot_is_isolated_from_mutations() {
+        let mut graph = standard_graph_for_testing();
+        let snapshot = graph.snapshot().unwrap();
+        let number_of_vertices = snapshot.number_of_vertices().unwrap();
+
+        let edge = DirectedEdgeDefinedByKeys::new(
+            String::from("-1.1"),
+            String::from("smaller_than"),
+            String::from("-1"),
+        );
+        graph.delete_edge_defined_by_keys(&edge).unwrap();
+        graph
+            .add_edge_using_keys(DirectedEdgeDefinedByKeys::new(
+                String::from("-1"),
+                String::from("larger_than"),
+                String::from("2"),
+            ))
+            .unwrap();
+        graph
+            .update_vertex(Vertex::new(String::from("1"), String::from("one").into()))
+            .unwrap();
+        graph
+            .delete_vertex_and_connected_edges_by_key(String::from("0"))
+            .unwrap();
+        for index in 0..100 {
+            graph
+                .add_new_vertex(Vertex::new(format!("new_{}", index), index.into()))
+                .unwrap();
+        }
+
+        assert_eq!(snapshot.number_of_vertices().unwrap(), number_of_vertices);
+        assert!(snapshot.is_key_defined_edge_in_graph(&edge).unwrap());
+        assert!(!graph.is_key_defined_edge_in_graph(&edge).unwrap());
+        assert_eq!(
+            snapshot.vertex_value(&String::from("1")).unwrap(),
+            &VertexValue::UnsignedInteger8Bit(1)
+        );
+        assert!(snapshot.is_valid_vertex_key(&String::from("0")));
+        assert!(!snapshot.is_valid_vertex_key(&String::from("new_0")));
+
+        let selection = snapshot
+            .select_vertices_connected_to_vertex_by_key(String::from("larger_than"), "2")
+            .unwrap();
+        assert!(!selection
+            .vertex_values_ref()
+            .unwrap()
+            .contains(&&VertexValue::Integer8Bit(-1)));
+        let selection = graph
+            .select_vertices_connected_to_vertex_by_key(String::from("larger_than"), "2")
+            .unwrap();
+        assert!(selection
+            .vertex_values_ref()
+            .unwrap()
+            .contains(&&VertexValue::Integer8Bit(-1)));
+    }
+
+    #[test]
+    fn query_snapshot_in_other_thread() {
+        let mut graph = standard_graph_for_testing();
+        let snapshot = graph.snapshot().unwrap();
+
+        let query = std::thread::spawn(move || {
+            snapshot
+                .select_vertices_connected_to_vertex_by_key(String::from("smaller_than"), "-1")
+                .unwrap()
+                .vertex_values_ref()
+                .unwrap()
+                .into_iter()
+                .cloned()
+                .collect::<Vec<VertexValue>>()
+        });
+        graph
+            .delete_vertex_and_connected_edges_by_key(String::from("-1.1"))
+            .unwrap();
+
+        assert_eq!(
+            query.join().unwrap(),
+            vec![VertexValue::FloatingPoint32Bit(-1.1)]
+        );
+    }
+}
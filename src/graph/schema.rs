@@ -0,0 +1,186 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::graph::edge::{DirectedEdgeDefinedByKeys, EdgeType, EdgeTypeRef};
+use crate::graph::vertex::{VertexKey, VertexLabel, VertexLabelRef, VertexValueType};
+
+/// Restricts the edges of a single edge type.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EdgeTypeConstraint {
+    from_vertex_label: Option<VertexLabel>,
+    to_vertex_label: Option<VertexLabel>,
+    is_functional: bool,
+}
+
+impl EdgeTypeConstraint {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every edge must come from a vertex with vertex_label.
+    pub fn with_from_vertex_label(mut self, vertex_label: &VertexLabelRef) -> Self {
+        self.from_vertex_label = Some(vertex_label.to_owned());
+        self
+    }
+
+    /// Every edge must point to a vertex with vertex_label.
+    pub fn with_to_vertex_label(mut self, vertex_label: &VertexLabelRef) -> Self {
+        self.to_vertex_label = Some(vertex_label.to_owned());
+        self
+    }
+
+    /// A vertex has at most one edge of the edge type, i.e. at most one target.
+    pub fn functional(mut self) -> Self {
+        self.is_functional = true;
+        self
+    }
+
+    pub fn from_vertex_label_ref(&self) -> Option<&VertexLabelRef> {
+        self.from_vertex_label.as_deref()
+    }
+    pub fn to_vertex_label_ref(&self) -> Option<&VertexLabelRef> {
+        self.to_vertex_label.as_deref()
+    }
+    pub fn is_functional(&self) -> bool {
+        self.is_functional
+    }
+}
+
+/// Declares which edges and vertex values are valid, see DefineSchema.
+/// Edge types and labels that the schema does not mention are unrestricted.
+///
+/// ```
+/// use cairn_knowledge_graph::graph::schema::{EdgeTypeConstraint, Schema};
+/// use cairn_knowledge_graph::graph::vertex::VertexValueType;
+///
+/// let schema = Schema::new()
+///     .with_edge_type_constraint(
+///         "verified_by",
+///         EdgeTypeConstraint::new()
+///             .with_from_vertex_label("Requirement")
+///             .with_to_vertex_label("Test")
+///             .functional(),
+///     )
+///     .with_vertex_value_type("Requirement", VertexValueType::String);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Schema {
+    edge_type_constraints: BTreeMap<EdgeType, EdgeTypeConstraint>,
+    vertex_value_types: BTreeMap<VertexLabel, VertexValueType>,
+}
+
+impl Schema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces an earlier constraint for edge_type.
+    pub fn with_edge_type_constraint(
+        mut self,
+        edge_type: &EdgeTypeRef,
+        constraint: EdgeTypeConstraint,
+    ) -> Self {
+        self.edge_type_constraints
+            .insert(edge_type.to_owned(), constraint);
+        self
+    }
+
+    /// The value of every vertex with vertex_label must be of value_type.
+    pub fn with_vertex_value_type(
+        mut self,
+        vertex_label: &VertexLabelRef,
+        value_type: VertexValueType,
+    ) -> Self {
+        self.vertex_value_types
+            .insert(vertex_label.to_owned(), value_type);
+        self
+    }
+
+    pub fn edge_type_constraint(&self, edge_type: &EdgeTypeRef) -> Option<&EdgeTypeConstraint> {
+        self.edge_type_constraints.get(edge_type)
+    }
+    pub fn vertex_value_type(&self, vertex_label: &VertexLabelRef) -> Option<&VertexValueType> {
+        self.vertex_value_types.get(vertex_label)
+    }
+
+    /// Ordered by edge type
+    pub fn edge_type_constraints_ref(&self) -> &BTreeMap<EdgeType, EdgeTypeConstraint> {
+        &self.edge_type_constraints
+    }
+    /// Ordered by vertex label
+    pub fn vertex_value_types_ref(&self) -> &BTreeMap<VertexLabel, VertexValueType> {
+        &self.vertex_value_types
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SchemaViolation {
+    /// The edge comes from a vertex without the required label
+    FromVertexLabel {
+        edge: DirectedEdgeDefinedByKeys,
+        required_vertex_label: VertexLabel,
+    },
+    /// The edge points to a vertex without the required label
+    ToVertexLabel {
+        edge: DirectedEdgeDefinedByKeys,
+        required_vertex_label: VertexLabel,
+    },
+    /// A vertex has edges of a functional edge type to more than one vertex
+    MultipleTargets {
+        edge_type: EdgeType,
+        from_vertex_key: VertexKey,
+        to_vertex_keys: Vec<VertexKey>,
+    },
+    VertexValueType {
+        vertex_key: VertexKey,
+        vertex_label: VertexLabel,
+        required_value_type: VertexValueType,
+        value_type: VertexValueType,
+    },
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SchemaViolation::FromVertexLabel {
+                edge,
+                required_vertex_label,
+            } => write!(
+                f,
+                "Edge type \"{}\" requires label \"{}\" on the vertex it comes from, but vertex \"{}\" does not have it",
+                edge.edge_type_ref(),
+                required_vertex_label,
+                edge.originates_from_vertex()
+            ),
+            SchemaViolation::ToVertexLabel {
+                edge,
+                required_vertex_label,
+            } => write!(
+                f,
+                "Edge type \"{}\" requires label \"{}\" on the vertex it points to, but vertex \"{}\" does not have it",
+                edge.edge_type_ref(),
+                required_vertex_label,
+                edge.points_to_vertex()
+            ),
+            SchemaViolation::MultipleTargets {
+                edge_type,
+                from_vertex_key,
+                to_vertex_keys,
+            } => write!(
+                f,
+                "Edge type \"{}\" allows at most one target, but vertex \"{}\" has edges to {:?}",
+                edge_type, from_vertex_key, to_vertex_keys
+            ),
+            SchemaViolation::VertexValueType {
+                vertex_key,
+                vertex_label,
+                required_value_type,
+                value_type,
+            } => write!(
+                f,
+                "Label \"{}\" requires a value of type {:?}, but vertex \"{}\" has a value of type {:?}",
+                vertex_label, required_value_type, vertex_key, value_type
+            ),
+        }
+    }
+}
@@ -1,6 +1,9 @@
+use std::collections::BTreeMap;
+
 use crate::error::GraphComputingError;
 use crate::error::{LogicError, LogicErrorType};
 use crate::error::{SystemError, SystemErrorType};
+use crate::error::{UserError, UserErrorType};
 use crate::graph::graph::Graph;
 
 use super::graph::ElementIndex;
@@ -8,6 +11,15 @@ use super::graph::ElementIndex;
 pub type VertexKey = String;
 pub type VertexKeyRef = str;
 
+/// A vertex can have several labels, e.g. the kind of element it represents
+pub type VertexLabel = String;
+pub type VertexLabelRef = str;
+
+pub type PropertyName = String;
+pub type PropertyNameRef = str;
+/// Named values of a vertex, in addition to its value. Ordered by name.
+pub type VertexProperties = BTreeMap<PropertyName, VertexValue>;
+
 // Use a struct instead of a type to discourage using and/or generating indices that are not coming from the pblic API.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct VertexIndex {
@@ -46,6 +58,7 @@ impl VertexIndex {
 pub struct Vertex {
     key: VertexKey,
     value: VertexValue,
+    properties: VertexProperties,
 }
 
 impl From<Vertex> for VertexKey {
@@ -77,7 +90,27 @@ impl From<(VertexKey, VertexValue)> for Vertex {
 
 impl Vertex {
     pub fn new(key: VertexKey, value: VertexValue) -> Self {
-        Self { key, value }
+        Self {
+            key,
+            value,
+            properties: VertexProperties::new(),
+        }
+    }
+
+    /// ```
+    /// # use cairn_knowledge_graph::graph::vertex::{Vertex, VertexValue};
+    /// let requirement = Vertex::new(String::from("REQ-1"), String::from("Braking distance").into())
+    ///     .with_property(String::from("status"), String::from("approved").into())
+    ///     .with_property(String::from("priority"), 2u8.into());
+    /// assert_eq!(
+    ///     requirement.property("status"),
+    ///     Some(&VertexValue::String(String::from("approved")))
+    /// );
+    /// assert_eq!(requirement.property("owner"), None);
+    /// ```
+    pub fn with_property(mut self, property_name: PropertyName, value: VertexValue) -> Self {
+        self.properties.insert(property_name, value);
+        self
     }
     // pub fn key(&self) -> &VertexKey {
     //     &self.key
@@ -91,6 +124,12 @@ impl Vertex {
     pub fn value(self) -> VertexValue {
         self.value
     }
+    pub fn property(&self, property_name: &PropertyNameRef) -> Option<&VertexValue> {
+        self.properties.get(property_name)
+    }
+    pub fn properties_ref(&self) -> &VertexProperties {
+        &self.properties
+    }
 
     pub fn update_value(&mut self, new_value: VertexValue) {
         self.value = new_value;
@@ -98,6 +137,18 @@ impl Vertex {
     pub fn update_key(&mut self, new_key: VertexKey) {
         self.key = new_key;
     }
+    /// Returns the replaced value, None if the vertex did not have the property.
+    pub fn update_property(
+        &mut self,
+        property_name: PropertyName,
+        value: VertexValue,
+    ) -> Option<VertexValue> {
+        self.properties.insert(property_name, value)
+    }
+    /// Returns the removed value, None if the vertex did not have the property.
+    pub fn remove_property(&mut self, property_name: &PropertyNameRef) -> Option<VertexValue> {
+        self.properties.remove(property_name)
+    }
 
     // TO REVIEW: converting Vertex to an enum would make the vertex immutable, but introduce runtime cost
     // It should not be possible to access/reach a deleted vertex
@@ -145,6 +196,28 @@ pub enum VertexValue {
     FloatingPoint64Bit(f64),
 }
 
+impl VertexValue {
+    pub fn value_type(&self) -> VertexValueType {
+        match self {
+            VertexValue::None => VertexValueType::None,
+            VertexValue::String(_) => VertexValueType::String,
+            VertexValue::Boolean(_) => VertexValueType::Boolean,
+            VertexValue::Integer8Bit(_) => VertexValueType::Integer8Bit,
+            VertexValue::Integer16Bit(_) => VertexValueType::Integer16Bit,
+            VertexValue::Integer32Bit(_) => VertexValueType::Integer32Bit,
+            VertexValue::Integer64Bit(_) => VertexValueType::Integer64Bit,
+            VertexValue::Integer128Bit(_) => VertexValueType::Integer128Bit,
+            VertexValue::UnsignedInteger8Bit(_) => VertexValueType::UnsignedInteger8Bit,
+            VertexValue::UnsignedInteger16Bit(_) => VertexValueType::UnsignedInteger16Bit,
+            VertexValue::UnsignedInteger32Bit(_) => VertexValueType::UnsignedInteger32Bit,
+            VertexValue::UnsignedInteger64Bit(_) => VertexValueType::UnsignedInteger64Bit,
+            VertexValue::UnsignedInteger128Bit(_) => VertexValueType::UnsignedInteger128Bit,
+            VertexValue::FloatingPoint32Bit(_) => VertexValueType::FloatingPoint32Bit,
+            VertexValue::FloatingPoint64Bit(_) => VertexValueType::FloatingPoint64Bit,
+        }
+    }
+}
+
 macro_rules! implement_from_type {
     ($value_type:ty, $vertex_property_enum_value:ident) => {
         impl From<$value_type> for VertexValue {
@@ -217,6 +290,24 @@ impl VertexKeyAndIndexConversion for Graph {
     }
 }
 
+impl Graph {
+    /// Returns a UserError of type VertexKeyNotFound if there is no vertex with vertex_key.
+    pub(crate) fn existing_vertex_index(
+        &self,
+        vertex_key: &VertexKeyRef,
+    ) -> Result<ElementIndex, GraphComputingError> {
+        match self.vertex_key_to_vertex_index_map_ref().get(vertex_key) {
+            None => Err(UserError::new(
+                UserErrorType::VertexKeyNotFound,
+                format!("No vertex found for key \"{}\"", vertex_key),
+                None,
+            )
+            .into()),
+            Some(vertex_index) => Ok(vertex_index.index()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,6 +1,9 @@
+pub mod algorithms;
 pub mod error;
 pub mod graph;
 pub mod operations;
+pub mod persistence;
+pub mod query;
 
 #[cfg(test)]
 mod tests;
@@ -1,12 +1,22 @@
 use crate::error::{GraphComputingError, LogicError, LogicErrorType, SystemError, SystemErrorType};
 
-use crate::graph::edge::adjacency_matrix::EdgeCoordinate;
+use graphblas_sparse_linear_algebra::value_types::sparse_matrix::{
+    GetMatrixElementList, Size, SparseMatrix,
+};
+
+use crate::graph::edge::adjacency_matrix::{edge_coordinates, EdgeCoordinate};
+use crate::graph::edge::boolean_matrix_operations::from_coordinates;
 use crate::graph::edge::{
     DirectedEdgeDefinedByIndices, DirectedEdgeDefinedByKeys, EdgeToEdgeCoordinate, EdgeTypeIndex,
+    EdgeValue,
 };
-use crate::graph::graph::Graph;
+use crate::graph::graph::{ElementIndex, Graph, GraphTrait};
+use crate::graph::vertex::VertexIndex;
+use crate::persistence::write_ahead_log::GraphMutation;
 
 use super::add_edge_type::AddEdgeType;
+use super::set_edge_value::SetEdgeValue;
+use super::transaction::Transaction;
 
 pub trait AddEdge {
     fn add_edge_using_keys(
@@ -50,7 +60,11 @@ impl AddEdge for Graph {
             }
         }
         let edge_coordinate = self.key_defined_edge_to_edge_coordinate(&edge)?;
-        self.set_edge_in_adjacency_matrix(&edge_coordinate, edge_type_index.clone())?; // TODO: by index, and by key
+        self.set_edge_and_value_in_adjacency_matrix(
+            &edge_coordinate,
+            edge_type_index.clone(),
+            edge.value_ref(),
+        )?; // TODO: by index, and by key
         Ok(())
     }
 
@@ -73,7 +87,11 @@ impl AddEdge for Graph {
             }
         }
         let edge_coordinate = self.key_defined_edge_to_edge_coordinate(&edge)?;
-        self.set_edge_in_adjacency_matrix(&edge_coordinate, edge_type_index.clone())?;
+        self.set_edge_and_value_in_adjacency_matrix(
+            &edge_coordinate,
+            edge_type_index.clone(),
+            edge.value_ref(),
+        )?;
         Ok(edge_type_index)
     }
 
@@ -88,11 +106,37 @@ impl AddEdge for Graph {
 }
 
 impl Graph {
+    // An existing value is kept if the edge has no value
+    fn set_edge_and_value_in_adjacency_matrix(
+        &mut self,
+        edge_coordinate: &EdgeCoordinate,
+        edge_type_index: EdgeTypeIndex,
+        value: Option<&EdgeValue>,
+    ) -> Result<(), GraphComputingError> {
+        match value {
+            None => self.set_edge_in_adjacency_matrix(edge_coordinate, edge_type_index),
+            // The edge and its value are appended to the write-ahead log as a single record
+            Some(value) => self.transaction(|graph| {
+                graph.set_edge_in_adjacency_matrix(edge_coordinate, edge_type_index)?;
+                graph.set_edge_value_using_indices(
+                    &DirectedEdgeDefinedByIndices::new(
+                        VertexIndex::new(edge_coordinate.row_index()),
+                        edge_type_index,
+                        VertexIndex::new(edge_coordinate.column_index()),
+                    ),
+                    value.clone(),
+                )
+            }),
+        }
+    }
+
     fn set_edge_in_adjacency_matrix(
         &mut self,
         edge_coordinate: &EdgeCoordinate,
         edge_type_index: EdgeTypeIndex,
     ) -> Result<(), GraphComputingError> {
+        self.check_write_ahead_log_is_writable()?;
+        self.check_new_edges_against_schema(edge_type_index, &[*edge_coordinate])?;
         match self
             .adjacency_matrices_mut_ref()
             .get_mut_ref(edge_type_index)
@@ -113,7 +157,54 @@ impl Graph {
                 .into());
             }
         }
-        Ok(())
+        self.append_to_write_ahead_log(|_| {
+            Ok(GraphMutation::AddEdge {
+                edge_type_index: edge_type_index.index(),
+                from_vertex_index: edge_coordinate.row_index(),
+                to_vertex_index: edge_coordinate.column_index(),
+            })
+        })
+    }
+}
+
+impl Graph {
+    // Adds the edges as a single record in the write-ahead log
+    pub(crate) fn add_edges_from_matrix(
+        &mut self,
+        edge_type_index: EdgeTypeIndex,
+        edges: &SparseMatrix<bool>,
+    ) -> Result<(), GraphComputingError> {
+        self.check_write_ahead_log_is_writable()?;
+        if !self.schema_ref().edge_type_constraints_ref().is_empty() {
+            self.check_new_edges_against_schema(edge_type_index, &edge_coordinates(edges)?)?;
+        }
+        self.adjacency_matrices_mut_ref()
+            .get_mut_ref(edge_type_index)?
+            .add_edges(edges)?;
+        self.append_to_write_ahead_log(|_| {
+            let element_list = edges.get_element_list()?;
+            Ok(GraphMutation::AddEdges {
+                edge_type_index: edge_type_index.index(),
+                from_vertex_indices: element_list.row_indices_ref().to_vec(),
+                to_vertex_indices: element_list.column_indices_ref().to_vec(),
+            })
+        })
+    }
+
+    pub(crate) fn add_edges_using_indices(
+        &mut self,
+        edge_type_index: EdgeTypeIndex,
+        from_vertex_indices: Vec<ElementIndex>,
+        to_vertex_indices: Vec<ElementIndex>,
+    ) -> Result<(), GraphComputingError> {
+        let vertex_capacity = self.vertex_capacity()?;
+        let edges = from_coordinates(
+            self.graphblas_context_ref(),
+            &Size::new(vertex_capacity, vertex_capacity),
+            from_vertex_indices,
+            to_vertex_indices,
+        )?;
+        self.add_edges_from_matrix(edge_type_index, &edges)
     }
 }
 
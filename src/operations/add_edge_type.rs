@@ -2,17 +2,28 @@ use crate::error::GraphComputingError;
 use crate::error::{LogicError, LogicErrorType};
 
 use crate::graph::edge::adjacency_matrix::AdjacencyMatrix;
-use crate::graph::edge::{EdgeType, EdgeTypeIndex, EdgeTypeKeyAndIndexConversion};
+use crate::graph::edge::{EdgeType, EdgeTypeIndex, EdgeTypeKeyAndIndexConversion, EdgeWeightType};
 use crate::graph::graph::Graph;
+use crate::persistence::write_ahead_log::GraphMutation;
 
 use crate::operations::read_edge::ReadEdge;
 
 pub trait AddEdgeType {
+    /// The edge type has weights of type Boolean: an edge weighs DEFAULT_EDGE_WEIGHT. Weighted edge types are added with
+    /// add_new_edge_type_with_weight_type().
     fn add_new_edge_type(
         &mut self,
         edge_type_key: EdgeType,
     ) -> Result<EdgeTypeIndex, GraphComputingError>;
 
+    /// The weight type determines the type of the values in the matrix that stores the edge weights.
+    /// Edges of a Boolean edge type have no weight besides their presence.
+    fn add_new_edge_type_with_weight_type(
+        &mut self,
+        edge_type: EdgeType,
+        edge_weight_type: EdgeWeightType,
+    ) -> Result<EdgeTypeIndex, GraphComputingError>;
+
     /// If the EdgeType already exits, returns a duplicate of its EdgeTypeIndex
     fn add_new_edge_type_or_return_index(
         &mut self,
@@ -25,16 +36,15 @@ impl<'g> AddEdgeType for Graph {
         &mut self,
         edge_type: EdgeType,
     ) -> Result<EdgeTypeIndex, GraphComputingError> {
-        if !self.is_edge_type_in_graph(edge_type.as_str())? {
-            add_edge_type(self, edge_type)
-        } else {
-            Err(LogicError::new(
-                LogicErrorType::EdgeTypeAlreadyExists,
-                format!("Edge type \"{}\" already exists", edge_type),
-                None,
-            )
-            .into())
-        }
+        self.add_new_edge_type_with_weight_type(edge_type, EdgeWeightType::Boolean)
+    }
+
+    fn add_new_edge_type_with_weight_type(
+        &mut self,
+        edge_type: EdgeType,
+        edge_weight_type: EdgeWeightType,
+    ) -> Result<EdgeTypeIndex, GraphComputingError> {
+        add_edge_type(self, edge_type, edge_weight_type)
     }
 
     fn add_new_edge_type_or_return_index(
@@ -46,7 +56,7 @@ impl<'g> AddEdgeType for Graph {
                 .edge_type_ref_to_edge_type_index_ref(edge_type.as_str())?
                 .clone())
         } else {
-            add_edge_type(self, edge_type)
+            add_edge_type(self, edge_type, EdgeWeightType::Boolean)
         }
     }
 }
@@ -54,12 +64,15 @@ impl<'g> AddEdgeType for Graph {
 fn add_edge_type(
     graph: &mut Graph,
     edge_type: EdgeType,
+    edge_weight_type: EdgeWeightType,
 ) -> Result<EdgeTypeIndex, GraphComputingError> {
+    graph.check_write_ahead_log_is_writable()?;
     if !graph.is_edge_type_in_graph(edge_type.as_str())? {
         let new_adjacency_matrix = AdjacencyMatrix::new(
             &graph.graphblas_context_ref(),
             edge_type.clone(),
             graph.vertex_store_ref().get_capacity()?,
+            edge_weight_type,
         )?;
         let edge_type_index: EdgeTypeIndex = graph
             .adjacency_matrices_mut_ref()
@@ -67,7 +80,13 @@ fn add_edge_type(
             .into();
         graph
             .edge_type_to_edge_type_index_map_mut_ref()
-            .insert(edge_type, edge_type_index.clone());
+            .insert(edge_type.clone(), edge_type_index.clone());
+        graph.append_to_write_ahead_log(|_| {
+            Ok(GraphMutation::AddEdgeType {
+                edge_type,
+                edge_weight_type,
+            })
+        })?;
         Ok(edge_type_index)
     } else {
         Err(LogicError::new(
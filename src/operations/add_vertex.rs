@@ -2,6 +2,7 @@ use crate::error::{GraphComputingError, UserError, UserErrorType};
 
 use crate::graph::graph::Graph;
 use crate::graph::vertex::{Vertex, VertexIndex};
+use crate::persistence::write_ahead_log::GraphMutation;
 
 use super::update_vertex::UpdateVertex;
 
@@ -45,6 +46,7 @@ impl AddVertex for Graph {
         &mut self,
         new_vertex: Vertex,
     ) -> Result<VertexIndex, GraphComputingError> {
+        self.check_write_ahead_log_is_writable()?;
         let key_ref_of_new_vertex = new_vertex.key_ref();
 
         let vertex_index: VertexIndex =
@@ -54,6 +56,8 @@ impl AddVertex for Graph {
             .insert(key_ref_of_new_vertex.to_owned(), vertex_index.clone());
 
         self.expand_adjacency_matrices_to_match_vertex_capacity()?;
+        self.expand_vertex_label_masks_to_match_vertex_capacity()?;
+        self.append_to_write_ahead_log(|_| Ok(GraphMutation::AddOrReplaceVertex(new_vertex)))?;
         Ok(vertex_index)
     }
 
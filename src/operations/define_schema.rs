@@ -0,0 +1,528 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use graphblas_sparse_linear_algebra::value_types::sparse_vector::{
+    GetVectorElementList, SetVectorElement, SparseVector, VectorElement,
+};
+
+use crate::error::{GraphComputingError, UserError, UserErrorType};
+
+use crate::graph::edge::adjacency_matrix::{AdjacencyMatrix, EdgeCoordinate};
+use crate::graph::edge::{DirectedEdgeDefinedByKeys, EdgeTypeIndex, EdgeTypeRef};
+use crate::graph::graph::{ElementIndex, Graph, GraphTrait};
+use crate::graph::schema::{EdgeTypeConstraint, Schema, SchemaViolation};
+use crate::graph::vertex::{VertexIndex, VertexKeyAndIndexConversion, VertexLabelRef, VertexValue};
+use crate::persistence::write_ahead_log::GraphMutation;
+
+pub trait DefineSchema {
+    /// Replaces the schema. From then on, adding an edge, adding or removing a vertex label,
+    /// or updating a vertex value returns a UserError of type SchemaViolation if the result would violate the schema.
+    /// Existing vertices and edges are not checked, see validate_schema().
+    fn set_schema(&mut self, schema: Schema) -> Result<(), GraphComputingError>;
+
+    fn schema(&self) -> &Schema;
+
+    /// All violations of the schema by the current vertices and edges, ordered by edge type and by label.
+    fn validate_schema(&self) -> Result<Vec<SchemaViolation>, GraphComputingError>;
+}
+
+impl DefineSchema for Graph {
+    fn set_schema(&mut self, schema: Schema) -> Result<(), GraphComputingError> {
+        self.check_write_ahead_log_is_writable()?;
+        *self.schema_mut_ref() = schema.clone();
+        self.append_to_write_ahead_log(|_| Ok(GraphMutation::SetSchema(schema)))
+    }
+
+    fn schema(&self) -> &Schema {
+        self.schema_ref()
+    }
+
+    fn validate_schema(&self) -> Result<Vec<SchemaViolation>, GraphComputingError> {
+        let mut violations = Vec::new();
+        for (edge_type, constraint) in self.schema_ref().edge_type_constraints_ref().iter() {
+            // A constraint may be declared before the edge type is added
+            let edge_type_index = match self.edge_type_to_edge_type_index_map_ref().get(edge_type) {
+                None => continue,
+                Some(edge_type_index) => *edge_type_index,
+            };
+            let edge_coordinates = self
+                .adjacency_matrices_ref()
+                .get_ref(edge_type_index)?
+                .get_edge_coordinates()?;
+
+            let mut targets: BTreeMap<ElementIndex, BTreeSet<ElementIndex>> = BTreeMap::new();
+            for edge_coordinate in edge_coordinates.iter() {
+                violations.append(&mut self.vertex_label_violations(
+                    edge_type,
+                    constraint,
+                    edge_coordinate,
+                )?);
+                targets
+                    .entry(edge_coordinate.row_index())
+                    .or_default()
+                    .insert(edge_coordinate.column_index());
+            }
+            if constraint.is_functional() {
+                for (from_vertex_index, to_vertex_indices) in targets.iter() {
+                    if to_vertex_indices.len() > 1 {
+                        violations.push(self.multiple_targets_violation(
+                            edge_type,
+                            *from_vertex_index,
+                            to_vertex_indices,
+                        )?);
+                    }
+                }
+            }
+        }
+
+        for vertex_label in self.schema_ref().vertex_value_types_ref().keys() {
+            if let Some(vertex_label_mask) = self.vertex_label_masks_ref().get(vertex_label) {
+                for vertex_index in vertex_label_mask.get_element_list()?.indices_ref() {
+                    let vertex_value = self
+                        .vertex_store_ref()
+                        .get_ref(VertexIndex::new(*vertex_index))?
+                        .value_ref();
+                    if let Some(violation) =
+                        self.vertex_value_violation(*vertex_index, vertex_label, vertex_value)?
+                    {
+                        violations.push(violation);
+                    }
+                }
+            }
+        }
+        Ok(violations)
+    }
+}
+
+// The checks below are done before a mutation, such that a rejected mutation leaves the graph unchanged.
+impl Graph {
+    pub(crate) fn check_new_edges_against_schema(
+        &self,
+        edge_type_index: EdgeTypeIndex,
+        edge_coordinates: &[EdgeCoordinate],
+    ) -> Result<(), GraphComputingError> {
+        if self.schema_ref().edge_type_constraints_ref().is_empty() {
+            return Ok(());
+        }
+        let adjacency_matrix = self.adjacency_matrices_ref().get_ref(edge_type_index)?;
+        let edge_type = adjacency_matrix.edge_type_ref();
+        let constraint = match self.schema_ref().edge_type_constraint(edge_type) {
+            None => return Ok(()),
+            Some(constraint) => constraint,
+        };
+
+        let mut targets: BTreeMap<ElementIndex, BTreeSet<ElementIndex>> = BTreeMap::new();
+        for edge_coordinate in edge_coordinates.iter() {
+            if let Some(violation) = self
+                .vertex_label_violations(edge_type, constraint, edge_coordinate)?
+                .into_iter()
+                .next()
+            {
+                return Err(schema_violation_error(violation));
+            }
+            targets
+                .entry(edge_coordinate.row_index())
+                .or_default()
+                .insert(edge_coordinate.column_index());
+        }
+        if constraint.is_functional() {
+            for (from_vertex_index, to_vertex_indices) in targets.iter_mut() {
+                to_vertex_indices.extend(self.targets_of(adjacency_matrix, *from_vertex_index)?);
+                if to_vertex_indices.len() > 1 {
+                    return Err(schema_violation_error(self.multiple_targets_violation(
+                        edge_type,
+                        *from_vertex_index,
+                        to_vertex_indices,
+                    )?));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn check_vertex_value_against_schema(
+        &self,
+        vertex_index: ElementIndex,
+        vertex_value: &VertexValue,
+    ) -> Result<(), GraphComputingError> {
+        for vertex_label in self.schema_ref().vertex_value_types_ref().keys() {
+            if self.has_vertex_label_by_index(vertex_index, vertex_label)? {
+                if let Some(violation) =
+                    self.vertex_value_violation(vertex_index, vertex_label, vertex_value)?
+                {
+                    return Err(schema_violation_error(violation));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn check_new_vertex_label_against_schema(
+        &self,
+        vertex_index: ElementIndex,
+        vertex_label: &VertexLabelRef,
+    ) -> Result<(), GraphComputingError> {
+        let vertex_value = self
+            .vertex_store_ref()
+            .get_ref(VertexIndex::new(vertex_index))?
+            .value_ref();
+        match self.vertex_value_violation(vertex_index, vertex_label, vertex_value)? {
+            Some(violation) => Err(schema_violation_error(violation)),
+            None => Ok(()),
+        }
+    }
+
+    // Removing a label must not leave an edge without a label that its edge type requires
+    pub(crate) fn check_removed_vertex_label_against_schema(
+        &self,
+        vertex_index: ElementIndex,
+        vertex_label: &VertexLabelRef,
+    ) -> Result<(), GraphComputingError> {
+        for (edge_type, constraint) in self.schema_ref().edge_type_constraints_ref().iter() {
+            let edge_type_index = match self.edge_type_to_edge_type_index_map_ref().get(edge_type) {
+                None => continue,
+                Some(edge_type_index) => *edge_type_index,
+            };
+            let adjacency_matrix = self.adjacency_matrices_ref().get_ref(edge_type_index)?;
+            if constraint.from_vertex_label_ref() == Some(vertex_label) {
+                if let Some(to_vertex_index) =
+                    self.targets_of(adjacency_matrix, vertex_index)?.first()
+                {
+                    return Err(schema_violation_error(SchemaViolation::FromVertexLabel {
+                        edge: self.key_defined_edge(
+                            edge_type,
+                            &EdgeCoordinate::new(vertex_index, *to_vertex_index),
+                        )?,
+                        required_vertex_label: vertex_label.to_owned(),
+                    }));
+                }
+            }
+            if constraint.to_vertex_label_ref() == Some(vertex_label) {
+                if let Some(from_vertex_index) =
+                    self.sources_of(adjacency_matrix, vertex_index)?.first()
+                {
+                    return Err(schema_violation_error(SchemaViolation::ToVertexLabel {
+                        edge: self.key_defined_edge(
+                            edge_type,
+                            &EdgeCoordinate::new(*from_vertex_index, vertex_index),
+                        )?,
+                        required_vertex_label: vertex_label.to_owned(),
+                    }));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn vertex_label_violations(
+        &self,
+        edge_type: &EdgeTypeRef,
+        constraint: &EdgeTypeConstraint,
+        edge_coordinate: &EdgeCoordinate,
+    ) -> Result<Vec<SchemaViolation>, GraphComputingError> {
+        let mut violations = Vec::new();
+        if let Some(vertex_label) = constraint.from_vertex_label_ref() {
+            if !self.has_vertex_label_by_index(edge_coordinate.row_index(), vertex_label)? {
+                violations.push(SchemaViolation::FromVertexLabel {
+                    edge: self.key_defined_edge(edge_type, edge_coordinate)?,
+                    required_vertex_label: vertex_label.to_owned(),
+                });
+            }
+        }
+        if let Some(vertex_label) = constraint.to_vertex_label_ref() {
+            if !self.has_vertex_label_by_index(edge_coordinate.column_index(), vertex_label)? {
+                violations.push(SchemaViolation::ToVertexLabel {
+                    edge: self.key_defined_edge(edge_type, edge_coordinate)?,
+                    required_vertex_label: vertex_label.to_owned(),
+                });
+            }
+        }
+        Ok(violations)
+    }
+
+    fn multiple_targets_violation(
+        &self,
+        edge_type: &EdgeTypeRef,
+        from_vertex_index: ElementIndex,
+        to_vertex_indices: &BTreeSet<ElementIndex>,
+    ) -> Result<SchemaViolation, GraphComputingError> {
+        let mut to_vertex_keys = Vec::with_capacity(to_vertex_indices.len());
+        for to_vertex_index in to_vertex_indices.iter() {
+            to_vertex_keys.push(
+                self.vertex_index_to_vertex_key_ref(VertexIndex::new(*to_vertex_index))?
+                    .to_owned(),
+            );
+        }
+        to_vertex_keys.sort();
+        Ok(SchemaViolation::MultipleTargets {
+            edge_type: edge_type.to_owned(),
+            from_vertex_key: self
+                .vertex_index_to_vertex_key_ref(VertexIndex::new(from_vertex_index))?
+                .to_owned(),
+            to_vertex_keys,
+        })
+    }
+
+    fn vertex_value_violation(
+        &self,
+        vertex_index: ElementIndex,
+        vertex_label: &VertexLabelRef,
+        vertex_value: &VertexValue,
+    ) -> Result<Option<SchemaViolation>, GraphComputingError> {
+        match self.schema_ref().vertex_value_type(vertex_label) {
+            Some(required_value_type) if *required_value_type != vertex_value.value_type() => {
+                Ok(Some(SchemaViolation::VertexValueType {
+                    vertex_key: self
+                        .vertex_index_to_vertex_key_ref(VertexIndex::new(vertex_index))?
+                        .to_owned(),
+                    vertex_label: vertex_label.to_owned(),
+                    required_value_type: required_value_type.clone(),
+                    value_type: vertex_value.value_type(),
+                }))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn key_defined_edge(
+        &self,
+        edge_type: &EdgeTypeRef,
+        edge_coordinate: &EdgeCoordinate,
+    ) -> Result<DirectedEdgeDefinedByKeys, GraphComputingError> {
+        Ok(DirectedEdgeDefinedByKeys::new(
+            self.vertex_index_to_vertex_key_ref(VertexIndex::new(edge_coordinate.row_index()))?
+                .to_owned(),
+            edge_type.to_owned(),
+            self.vertex_index_to_vertex_key_ref(VertexIndex::new(edge_coordinate.column_index()))?
+                .to_owned(),
+        ))
+    }
+
+    fn targets_of(
+        &self,
+        adjacency_matrix: &AdjacencyMatrix,
+        from_vertex_index: ElementIndex,
+    ) -> Result<Vec<ElementIndex>, GraphComputingError> {
+        Ok(adjacency_matrix
+            .get_to_vertex_index_mask_for_from_vertices(
+                &self.single_vertex_mask(from_vertex_index)?,
+            )?
+            .get_element_list()?
+            .indices_ref()
+            .to_vec())
+    }
+
+    fn sources_of(
+        &self,
+        adjacency_matrix: &AdjacencyMatrix,
+        to_vertex_index: ElementIndex,
+    ) -> Result<Vec<ElementIndex>, GraphComputingError> {
+        Ok(adjacency_matrix
+            .get_from_vertex_index_mask_for_to_vertices(&self.single_vertex_mask(to_vertex_index)?)?
+            .get_element_list()?
+            .indices_ref()
+            .to_vec())
+    }
+
+    fn single_vertex_mask(
+        &self,
+        vertex_index: ElementIndex,
+    ) -> Result<SparseVector<bool>, GraphComputingError> {
+        let mut vertex_mask =
+            SparseVector::<bool>::new(self.graphblas_context_ref(), &self.vertex_capacity()?)?;
+        vertex_mask.set_element(VectorElement::new(vertex_index, true))?;
+        Ok(vertex_mask)
+    }
+}
+
+fn schema_violation_error(violation: SchemaViolation) -> GraphComputingError {
+    UserError::new(UserErrorType::SchemaViolation, violation.to_string(), None).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::GraphComputingErrorType;
+    use crate::graph::vertex::{Vertex, VertexValueType};
+    use crate::operations::add_edge::AddEdge;
+    use crate::operations::add_vertex::AddVertex;
+    use crate::operations::label_vertex::LabelVertex;
+    use crate::operations::transitive_closure::TransitiveClosure;
+    use crate::operations::update_vertex::UpdateVertex;
+    use crate::tests::edge;
+
+    fn traceability_schema() -> Schema {
+        Schema::new()
+            .with_edge_type_constraint(
+                "verified_by",
+                EdgeTypeConstraint::new()
+                    .with_from_vertex_label("Requirement")
+                    .with_to_vertex_label("Test")
+                    .functional(),
+            )
+            .with_vertex_value_type("Requirement", VertexValueType::String)
+    }
+
+    fn labelled_graph() -> Graph {
+        let mut graph = Graph::new(10, 10).unwrap();
+        for (vertex_key, vertex_label) in [
+            ("REQ-1", "Requirement"),
+            ("REQ-2", "Requirement"),
+            ("TEST-1", "Test"),
+            ("TEST-2", "Test"),
+        ] {
+            graph
+                .add_new_vertex(Vertex::new(
+                    String::from(vertex_key),
+                    String::from("text").into(),
+                ))
+                .unwrap();
+            graph
+                .add_vertex_label(vertex_key, String::from(vertex_label))
+                .unwrap();
+        }
+        graph
+            .add_new_vertex(Vertex::new(String::from("DESIGN-1"), 0u8.into()))
+            .unwrap();
+        graph
+    }
+
+    fn error_type(result: Result<(), GraphComputingError>) -> GraphComputingErrorType {
+        result.unwrap_err().error_type()
+    }
+
+    const SCHEMA_VIOLATION: GraphComputingErrorType =
+        GraphComputingErrorType::UserErrorType(UserErrorType::SchemaViolation);
+
+    #[test]
+    fn reject_edges_that_violate_schema() {
+        let mut graph = labelled_graph();
+        graph.set_schema(traceability_schema()).unwrap();
+
+        graph
+            .add_edge_and_edge_type_using_keys(edge("REQ-1", "verified_by", "TEST-1"))
+            .unwrap();
+        // Adding an existing edge does not add a second target
+        graph
+            .add_edge_using_keys(edge("REQ-1", "verified_by", "TEST-1"))
+            .unwrap();
+        assert_eq!(
+            error_type(graph.add_edge_using_keys(edge("REQ-1", "verified_by", "TEST-2"))),
+            SCHEMA_VIOLATION
+        );
+        assert_eq!(
+            error_type(graph.add_edge_using_keys(edge("DESIGN-1", "verified_by", "TEST-2"))),
+            SCHEMA_VIOLATION
+        );
+        assert_eq!(
+            error_type(graph.add_edge_using_keys(edge("REQ-2", "verified_by", "DESIGN-1"))),
+            SCHEMA_VIOLATION
+        );
+        assert_eq!(graph.number_of_edges().unwrap(), 1);
+
+        // Edge types without a constraint are unrestricted, also when their edges are added in bulk
+        graph
+            .add_edge_and_edge_type_using_keys(edge("REQ-2", "refines", "REQ-1"))
+            .unwrap();
+        graph
+            .add_edge_using_keys(edge("DESIGN-1", "refines", "REQ-2"))
+            .unwrap();
+        graph
+            .set_schema(traceability_schema().with_edge_type_constraint(
+                "refines_transitively",
+                EdgeTypeConstraint::new().functional(),
+            ))
+            .unwrap();
+        assert_eq!(
+            graph
+                .transitive_closure("refines", "refines_transitively")
+                .unwrap_err()
+                .error_type(),
+            SCHEMA_VIOLATION
+        );
+        assert_eq!(graph.number_of_edges().unwrap(), 3);
+    }
+
+    #[test]
+    fn reject_vertex_changes_that_violate_schema() {
+        let mut graph = labelled_graph();
+        graph
+            .add_edge_and_edge_type_using_keys(edge("REQ-1", "verified_by", "TEST-1"))
+            .unwrap();
+        graph.set_schema(traceability_schema()).unwrap();
+
+        assert_eq!(
+            error_type(
+                graph
+                    .add_or_update_vertex(Vertex::new(String::from("REQ-1"), 1u8.into()))
+                    .map(|_| ())
+            ),
+            SCHEMA_VIOLATION
+        );
+        assert_eq!(
+            error_type(graph.update_vertex(Vertex::new(String::from("REQ-2"), true.into()))),
+            SCHEMA_VIOLATION
+        );
+        graph
+            .update_vertex(Vertex::new(String::from("TEST-1"), true.into()))
+            .unwrap();
+
+        assert_eq!(
+            error_type(graph.add_vertex_label("DESIGN-1", String::from("Requirement"))),
+            SCHEMA_VIOLATION
+        );
+        assert_eq!(
+            graph
+                .remove_vertex_label("TEST-1", "Test")
+                .unwrap_err()
+                .error_type(),
+            SCHEMA_VIOLATION
+        );
+        assert!(graph.remove_vertex_label("TEST-2", "Test").unwrap());
+        assert!(graph.validate_schema().unwrap().is_empty());
+    }
+
+    #[test]
+    fn validate_existing_vertices_and_edges() {
+        let mut graph = labelled_graph();
+        graph
+            .add_edge_and_edge_type_using_keys(edge("REQ-1", "verified_by", "TEST-1"))
+            .unwrap();
+        graph
+            .add_edge_using_keys(edge("REQ-1", "verified_by", "TEST-2"))
+            .unwrap();
+        graph
+            .add_edge_using_keys(edge("DESIGN-1", "verified_by", "REQ-2"))
+            .unwrap();
+        graph
+            .update_vertex(Vertex::new(String::from("REQ-2"), 2u8.into()))
+            .unwrap();
+
+        // Existing violations do not prevent setting the schema
+        graph.set_schema(traceability_schema()).unwrap();
+        assert_eq!(
+            graph.validate_schema().unwrap(),
+            vec![
+                SchemaViolation::FromVertexLabel {
+                    edge: edge("DESIGN-1", "verified_by", "REQ-2"),
+                    required_vertex_label: String::from("Requirement"),
+                },
+                SchemaViolation::ToVertexLabel {
+                    edge: edge("DESIGN-1", "verified_by", "REQ-2"),
+                    required_vertex_label: String::from("Test"),
+                },
+                SchemaViolation::MultipleTargets {
+                    edge_type: String::from("verified_by"),
+                    from_vertex_key: String::from("REQ-1"),
+                    to_vertex_keys: vec![String::from("TEST-1"), String::from("TEST-2")],
+                },
+                SchemaViolation::VertexValueType {
+                    vertex_key: String::from("REQ-2"),
+                    vertex_label: String::from("Requirement"),
+                    required_value_type: VertexValueType::String,
+                    value_type: VertexValueType::UnsignedInteger8Bit,
+                },
+            ]
+        );
+    }
+}
@@ -1,9 +1,11 @@
 use crate::error::GraphComputingError;
 
+use crate::graph::edge::EdgeTypeKeyAndIndexConversion;
 use crate::graph::edge::{
     DirectedEdgeDefinedByIndices, DirectedEdgeDefinedByKeys, EdgeToEdgeCoordinate,
 };
 use crate::graph::graph::Graph;
+use crate::persistence::write_ahead_log::GraphMutation;
 
 pub trait DeleteEdge {
     fn delete_edge_defined_by_keys(
@@ -22,24 +24,40 @@ impl DeleteEdge for Graph {
         &mut self,
         edge_to_delete: &DirectedEdgeDefinedByKeys,
     ) -> Result<(), GraphComputingError> {
+        self.check_write_ahead_log_is_writable()?;
         let edge_coordinate_to_delete = self.key_defined_edge_to_edge_coordinate(edge_to_delete)?;
         let adjacency_matrix_of_edge_to_delete =
             self.get_edge_adjacency_matrix_mut_ref(edge_to_delete.edge_type_ref())?;
         adjacency_matrix_of_edge_to_delete.delete_edge(&edge_coordinate_to_delete)?;
-        Ok(())
+        self.append_to_write_ahead_log(|graph| {
+            Ok(GraphMutation::DeleteEdge {
+                edge_type_index: graph
+                    .edge_type_ref_to_edge_type_index_ref(edge_to_delete.edge_type_ref())?
+                    .index(),
+                from_vertex_index: edge_coordinate_to_delete.row_index(),
+                to_vertex_index: edge_coordinate_to_delete.column_index(),
+            })
+        })
     }
 
     fn delete_edge_defined_by_indices(
         &mut self,
         edge_to_delete: &DirectedEdgeDefinedByIndices,
     ) -> Result<(), GraphComputingError> {
+        self.check_write_ahead_log_is_writable()?;
         let edge_coordinate_to_delete =
             self.index_defined_edge_to_edge_coordinate(edge_to_delete)?;
         let adjacency_matrix_of_edge_to_delete = self
             .adjacency_matrices_mut_ref()
             .get_mut_ref(edge_to_delete.edge_type().clone())?;
         adjacency_matrix_of_edge_to_delete.delete_edge(&edge_coordinate_to_delete)?;
-        Ok(())
+        self.append_to_write_ahead_log(|_| {
+            Ok(GraphMutation::DeleteEdge {
+                edge_type_index: edge_to_delete.edge_type().index(),
+                from_vertex_index: edge_coordinate_to_delete.row_index(),
+                to_vertex_index: edge_coordinate_to_delete.column_index(),
+            })
+        })
     }
 }
 
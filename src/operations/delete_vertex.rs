@@ -9,6 +9,7 @@ use graphblas_sparse_linear_algebra::{
         options::OperatorOptions,
     },
     util::ElementIndexSelector,
+    value_types::sparse_matrix::SparseMatrix,
     value_types::sparse_vector::SparseVector,
 };
 
@@ -17,6 +18,7 @@ use crate::error::GraphComputingError;
 use crate::graph::edge::adjacency_matrix::AdjacencyMatrix;
 use crate::graph::graph::Graph;
 use crate::graph::vertex::{VertexIndex, VertexKey, VertexKeyAndIndexConversion};
+use crate::persistence::write_ahead_log::GraphMutation;
 
 static DEFAULT_GRAPHBLAS_OPERATOR_OPTIONS: Lazy<OperatorOptions> =
     Lazy::new(|| OperatorOptions::new_default());
@@ -74,6 +76,7 @@ impl Graph {
         vertex_index: VertexIndex,
         vertex_key: VertexKey,
     ) -> Result<(), GraphComputingError> {
+        self.check_write_ahead_log_is_writable()?;
         self.vertex_store_mut_ref().free(vertex_index.clone())?;
         self.vertex_key_to_vertex_index_map_mut_ref()
             .remove_entry(&vertex_key);
@@ -84,26 +87,35 @@ impl Graph {
         )?;
 
         // TODO: is inserting an empty vector the fastest way to delete a row/column?
-        let delete_connected_edges =
-            |adjacency_matrix: &mut AdjacencyMatrix| -> Result<(), GraphComputingError> {
+        let delete_row_and_column =
+            |sparse_matrix: &mut SparseMatrix<bool>| -> Result<(), GraphComputingError> {
                 INSERT_VECTOR_INTO_COLUMN_OPERATOR.apply(
-                    adjacency_matrix.as_mut_sparse_matrix(),
+                    sparse_matrix,
                     &ElementIndexSelector::All,
                     vertex_index.index_ref(),
                     &empty_column,
                 )?;
                 INSERT_VECTOR_INTO_ROW_OPERATOR.apply(
-                    adjacency_matrix.as_mut_sparse_matrix(),
+                    sparse_matrix,
                     &ElementIndexSelector::All,
                     vertex_index.index_ref(),
                     &empty_column,
                 )?;
                 Ok(())
             };
+        let delete_connected_edges =
+            |adjacency_matrix: &mut AdjacencyMatrix| -> Result<(), GraphComputingError> {
+                delete_row_and_column(adjacency_matrix.as_mut_sparse_matrix())?;
+                delete_row_and_column(adjacency_matrix.as_mut_inferred_edges_sparse_matrix())?;
+                adjacency_matrix.delete_edge_values_of_vertex(vertex_index.index());
+                adjacency_matrix.delete_edge_weights_of_vertex(vertex_index.index())
+            };
 
         // TODO: some matrices may have been freed and do not need to be updated, potentially saving time.
         self.adjacency_matrices_mut_ref()
-            .map_mut_all(delete_connected_edges)
+            .map_mut_all(delete_connected_edges)?;
+        self.remove_all_vertex_labels(vertex_index.index())?;
+        self.append_to_write_ahead_log(|_| Ok(GraphMutation::DeleteVertex(vertex_index.index())))
     }
 }
 
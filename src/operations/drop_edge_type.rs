@@ -2,6 +2,7 @@ use crate::error::GraphComputingError;
 
 use crate::graph::edge::{EdgeTypeIndex, EdgeTypeKeyAndIndexConversion, EdgeTypeRef};
 use crate::graph::graph::Graph;
+use crate::persistence::write_ahead_log::GraphMutation;
 
 pub trait DropEdgeType {
     /// Deletes the edge type, and all its edges
@@ -20,6 +21,7 @@ impl DropEdgeType for Graph {
         &mut self,
         edge_type: &EdgeTypeRef,
     ) -> Result<(), GraphComputingError> {
+        self.check_write_ahead_log_is_writable()?;
         let edge_type_index;
         match self
             .edge_type_to_edge_type_index_map_mut_ref()
@@ -28,20 +30,27 @@ impl DropEdgeType for Graph {
             Some(index) => edge_type_index = index,
             None => return Ok(()),
         }
-        self.adjacency_matrices_mut_ref().free(edge_type_index)
+        self.adjacency_matrices_mut_ref().free(edge_type_index)?;
+        self.append_to_write_ahead_log(|_| Ok(GraphMutation::DropEdgeType(edge_type_index.index())))
     }
 
     fn drop_edge_type_with_index(
         &mut self,
         edge_type_index: &EdgeTypeIndex,
     ) -> Result<(), GraphComputingError> {
+        self.check_write_ahead_log_is_writable()?;
         let edge_type = self
             .edge_type_index_to_edge_type_ref(edge_type_index.clone())?
             .to_owned();
         self.edge_type_to_edge_type_index_map_mut_ref()
             .remove(edge_type.as_str());
         self.adjacency_matrices_mut_ref()
-            .free(edge_type_index.to_owned())
+            .free(edge_type_index.to_owned())?;
+        self.append_to_write_ahead_log(|_| {
+            Ok(GraphMutation::DropEdgeType(
+                edge_type_index.to_owned().index(),
+            ))
+        })
     }
 }
 
@@ -0,0 +1,418 @@
+use graphblas_sparse_linear_algebra::value_types::sparse_matrix::{
+    GetMatrixElementList, Size, SparseMatrix,
+};
+use hashbrown::HashMap;
+
+use crate::error::{GraphComputingError, UserError, UserErrorType};
+use crate::graph::edge::adjacency_matrix::edge_coordinates;
+use crate::graph::edge::boolean_matrix_operations::{
+    difference, from_coordinates, is_empty, multiply, transpose, union,
+};
+use crate::graph::edge::{
+    DirectedEdgeDefinedByKeys, EdgeToEdgeCoordinate, EdgeType, EdgeTypeIndex,
+    EdgeTypeKeyAndIndexConversion, EdgeTypeRef,
+};
+use crate::graph::graph::{ElementIndex, Graph, GraphTrait};
+use crate::operations::add_edge_type::AddEdgeType;
+use crate::operations::transaction::Transaction;
+use crate::persistence::write_ahead_log::GraphMutation;
+
+/// Matches the edges of an edge type in the body of an InferenceRule.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RuleAtom {
+    edge_type: EdgeType,
+    is_inverse: bool,
+}
+
+impl RuleAtom {
+    /// Matches an edge of edge_type from the preceding variable to the next variable.
+    pub fn new(edge_type: &EdgeTypeRef) -> Self {
+        Self {
+            edge_type: edge_type.to_owned(),
+            is_inverse: false,
+        }
+    }
+
+    /// Matches an edge of edge_type from the next variable to the preceding variable.
+    pub fn inverse(edge_type: &EdgeTypeRef) -> Self {
+        Self {
+            edge_type: edge_type.to_owned(),
+            is_inverse: true,
+        }
+    }
+
+    pub fn edge_type_ref(&self) -> &EdgeTypeRef {
+        self.edge_type.as_str()
+    }
+
+    pub fn is_inverse(&self) -> bool {
+        self.is_inverse
+    }
+}
+
+/// The Datalog rule `target(x0, xn) :- atom_1(x0, x1), atom_2(x1, x2), ..., atom_n(xn-1, xn)`.
+///
+/// Each pair of vertices connected by a path that matches the body gets an edge of the target edge type.
+/// For example, `grandparent_of(x, z) :- child_of(y, x), child_of(z, y)` is
+/// `InferenceRule::new("grandparent_of", vec![RuleAtom::inverse("child_of"), RuleAtom::inverse("child_of")])`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InferenceRule {
+    target_edge_type: EdgeType,
+    body: Vec<RuleAtom>,
+}
+
+impl InferenceRule {
+    pub fn new(
+        target_edge_type: &EdgeTypeRef,
+        body: Vec<RuleAtom>,
+    ) -> Result<Self, GraphComputingError> {
+        if body.is_empty() {
+            return Err(UserError::new(
+                UserErrorType::InvalidInferenceRule,
+                format!(
+                    "The rule for edge type {} must have at least one atom in its body",
+                    target_edge_type
+                ),
+                None,
+            )
+            .into());
+        }
+        Ok(Self {
+            target_edge_type: target_edge_type.to_owned(),
+            body,
+        })
+    }
+
+    pub fn target_edge_type_ref(&self) -> &EdgeTypeRef {
+        self.target_edge_type.as_str()
+    }
+
+    pub fn body_ref(&self) -> &[RuleAtom] {
+        self.body.as_slice()
+    }
+}
+
+pub trait InferEdges {
+    /// Evaluates the rules to fixpoint, and adds the derived edges that are not in the graph yet as inferred edges.
+    /// Returns the number of inferred edges that were added.
+    ///
+    /// Rules may be recursive, and may use the target edge types of each other. Target edge types that do not exist are added.
+    /// The other edge types in the rule bodies must exist. All changes are made in a single transaction.
+    ///
+    /// NOTE: inferred edges are not maintained when the edges they were derived from are deleted.
+    /// Delete the inferred edges and infer them again to update them.
+    fn infer_edges(&mut self, rules: &[InferenceRule]) -> Result<usize, GraphComputingError>;
+
+    /// Returns false for asserted edges, and for edges that are not in the graph.
+    fn is_inferred_edge_defined_by_keys(
+        &self,
+        edge: &DirectedEdgeDefinedByKeys,
+    ) -> Result<bool, GraphComputingError>;
+
+    /// Deletes all inferred edges of all edge types. Asserted edges remain.
+    fn delete_inferred_edges(&mut self) -> Result<(), GraphComputingError>;
+}
+
+impl InferEdges for Graph {
+    fn infer_edges(&mut self, rules: &[InferenceRule]) -> Result<usize, GraphComputingError> {
+        self.transaction(|graph| graph.evaluate_inference_rules(rules))
+    }
+
+    fn is_inferred_edge_defined_by_keys(
+        &self,
+        edge: &DirectedEdgeDefinedByKeys,
+    ) -> Result<bool, GraphComputingError> {
+        let edge_coordinate = self.key_defined_edge_to_edge_coordinate(edge)?;
+        self.get_edge_adjacency_matrix_ref(edge.edge_type_ref())?
+            .is_inferred_edge(&edge_coordinate)
+    }
+
+    fn delete_inferred_edges(&mut self) -> Result<(), GraphComputingError> {
+        self.check_write_ahead_log_is_writable()?;
+        self.adjacency_matrices_mut_ref()
+            .map_mut_all(|adjacency_matrix| {
+                if adjacency_matrix.number_of_inferred_edges()? > 0 {
+                    adjacency_matrix.delete_inferred_edges()?;
+                }
+                Ok(())
+            })?;
+        self.append_to_write_ahead_log(|_| Ok(GraphMutation::DeleteInferredEdges))
+    }
+}
+
+impl Graph {
+    // Semi-naive evaluation: each iteration derives edges from paths with at least one edge that was derived in the previous iteration.
+    // A derivation is therefore not repeated in later iterations, and the evaluation stops when an iteration derives no new edges.
+    // The first iteration considers all edges in the graph to be new.
+    fn evaluate_inference_rules(
+        &mut self,
+        rules: &[InferenceRule],
+    ) -> Result<usize, GraphComputingError> {
+        let mut target_edge_types: Vec<EdgeType> = Vec::new();
+        for rule in rules.iter() {
+            if !target_edge_types.contains(&rule.target_edge_type) {
+                self.add_new_edge_type_or_return_index(rule.target_edge_type.clone())?;
+                target_edge_types.push(rule.target_edge_type.clone());
+            }
+        }
+
+        let mut relations: HashMap<EdgeType, SparseMatrix<bool>> = HashMap::new();
+        for rule in rules.iter() {
+            for edge_type in std::iter::once(&rule.target_edge_type)
+                .chain(rule.body.iter().map(|atom| &atom.edge_type))
+            {
+                if !relations.contains_key(edge_type) {
+                    let relation = self
+                        .get_edge_adjacency_matrix_ref(edge_type)?
+                        .as_sparse_matrix()
+                        .clone();
+                    relations.insert(edge_type.clone(), relation);
+                }
+            }
+        }
+
+        let mut new_edges_of_previous_iteration = relations.clone();
+        let mut inferred_edges: HashMap<EdgeType, SparseMatrix<bool>> = HashMap::new();
+        while !new_edges_of_previous_iteration.is_empty() {
+            let mut new_edges: HashMap<EdgeType, SparseMatrix<bool>> = HashMap::new();
+            for rule in rules.iter() {
+                for new_edge_position in 0..rule.body.len() {
+                    let new_edges_of_atom = match new_edges_of_previous_iteration
+                        .get(&rule.body[new_edge_position].edge_type)
+                    {
+                        Some(new_edges_of_atom) => new_edges_of_atom,
+                        None => continue,
+                    };
+
+                    let mut path_ends: Option<SparseMatrix<bool>> = None;
+                    for (position, atom) in rule.body.iter().enumerate() {
+                        let edges = if position == new_edge_position {
+                            new_edges_of_atom
+                        } else {
+                            &relations[&atom.edge_type]
+                        };
+                        let transposed_edges;
+                        let edges = if atom.is_inverse {
+                            transposed_edges = transpose(edges)?;
+                            &transposed_edges
+                        } else {
+                            edges
+                        };
+                        path_ends = Some(match path_ends {
+                            None => edges.clone(),
+                            Some(path_ends) => multiply(&path_ends, edges)?,
+                        });
+                    }
+
+                    // The body is not empty, InferenceRule::new checks this
+                    if let Some(path_ends) = path_ends {
+                        let mut derived_edges =
+                            difference(&path_ends, &relations[&rule.target_edge_type])?;
+                        if let Some(new_edges_of_target) = new_edges.get(&rule.target_edge_type) {
+                            derived_edges = union(new_edges_of_target, &derived_edges)?;
+                        }
+                        new_edges.insert(rule.target_edge_type.clone(), derived_edges);
+                    }
+                }
+            }
+
+            new_edges_of_previous_iteration = HashMap::new();
+            for (edge_type, edges) in new_edges.into_iter() {
+                if is_empty(&edges)? {
+                    continue;
+                }
+                let relation = union(&relations[&edge_type], &edges)?;
+                relations.insert(edge_type.clone(), relation);
+                let inferred_edges_of_type = match inferred_edges.get(&edge_type) {
+                    Some(inferred_edges_of_type) => union(inferred_edges_of_type, &edges)?,
+                    None => edges.clone(),
+                };
+                inferred_edges.insert(edge_type.clone(), inferred_edges_of_type);
+                new_edges_of_previous_iteration.insert(edge_type, edges);
+            }
+        }
+
+        let mut number_of_inferred_edges = 0;
+        for edge_type in target_edge_types.iter() {
+            if let Some(inferred_edges_of_type) = inferred_edges.get(edge_type) {
+                number_of_inferred_edges += inferred_edges_of_type.number_of_stored_elements()?;
+                let edge_type_index = *self.edge_type_ref_to_edge_type_index_ref(edge_type)?;
+                self.add_inferred_edges(edge_type_index, inferred_edges_of_type)?;
+            }
+        }
+        Ok(number_of_inferred_edges)
+    }
+
+    fn add_inferred_edges(
+        &mut self,
+        edge_type_index: EdgeTypeIndex,
+        inferred_edges: &SparseMatrix<bool>,
+    ) -> Result<(), GraphComputingError> {
+        self.check_write_ahead_log_is_writable()?;
+        if !self.schema_ref().edge_type_constraints_ref().is_empty() {
+            self.check_new_edges_against_schema(
+                edge_type_index,
+                &edge_coordinates(inferred_edges)?,
+            )?;
+        }
+        self.adjacency_matrices_mut_ref()
+            .get_mut_ref(edge_type_index)?
+            .add_inferred_edges(inferred_edges)?;
+        self.append_to_write_ahead_log(|_| {
+            let element_list = inferred_edges.get_element_list()?;
+            Ok(GraphMutation::AddInferredEdges {
+                edge_type_index: edge_type_index.index(),
+                from_vertex_indices: element_list.row_indices_ref().to_vec(),
+                to_vertex_indices: element_list.column_indices_ref().to_vec(),
+            })
+        })
+    }
+
+    pub(crate) fn add_inferred_edges_using_indices(
+        &mut self,
+        edge_type_index: EdgeTypeIndex,
+        from_vertex_indices: Vec<ElementIndex>,
+        to_vertex_indices: Vec<ElementIndex>,
+    ) -> Result<(), GraphComputingError> {
+        let vertex_capacity = self.vertex_capacity()?;
+        let inferred_edges = from_coordinates(
+            self.graphblas_context_ref(),
+            &Size::new(vertex_capacity, vertex_capacity),
+            from_vertex_indices,
+            to_vertex_indices,
+        )?;
+        self.add_inferred_edges(edge_type_index, &inferred_edges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::GraphComputingErrorType;
+    use crate::graph::schema::{EdgeTypeConstraint, Schema};
+    use crate::operations::add_edge::AddEdge;
+    use crate::operations::define_schema::DefineSchema;
+    use crate::operations::read_edge::ReadEdge;
+    use crate::tests::{edge, graph_with_vertices};
+
+    // a -> b -> c -> d
+    fn family_graph() -> Graph {
+        let mut graph = graph_with_vertices(&["a", "b", "c", "d"]);
+        graph
+            .add_edge_and_edge_type_using_keys(edge("a", "parent_of", "b"))
+            .unwrap();
+        graph
+            .add_edge_using_keys(edge("b", "parent_of", "c"))
+            .unwrap();
+        graph
+            .add_edge_using_keys(edge("c", "parent_of", "d"))
+            .unwrap();
+        graph
+    }
+
+    fn ancestor_rules() -> Vec<InferenceRule> {
+        vec![
+            InferenceRule::new("ancestor_of", vec![RuleAtom::new("parent_of")]).unwrap(),
+            InferenceRule::new(
+                "ancestor_of",
+                vec![RuleAtom::new("parent_of"), RuleAtom::new("ancestor_of")],
+            )
+            .unwrap(),
+        ]
+    }
+
+    #[test]
+    fn infer_recursive_rules_to_fixpoint() {
+        let mut graph = family_graph();
+
+        assert_eq!(graph.infer_edges(&ancestor_rules()).unwrap(), 6);
+        assert!(graph
+            .is_key_defined_edge_in_graph(&edge("a", "ancestor_of", "d"))
+            .unwrap());
+        assert!(graph
+            .is_inferred_edge_defined_by_keys(&edge("b", "ancestor_of", "d"))
+            .unwrap());
+        assert!(!graph
+            .is_key_defined_edge_in_graph(&edge("d", "ancestor_of", "a"))
+            .unwrap());
+
+        // a fixpoint derives nothing new
+        assert_eq!(graph.infer_edges(&ancestor_rules()).unwrap(), 0);
+    }
+
+    #[test]
+    fn distinguish_inferred_and_asserted_edges() {
+        let mut graph = family_graph();
+        graph
+            .add_edge_and_edge_type_using_keys(edge("b", "child_of", "a"))
+            .unwrap();
+
+        let rules =
+            vec![InferenceRule::new("child_of", vec![RuleAtom::inverse("parent_of")]).unwrap()];
+        assert_eq!(graph.infer_edges(&rules).unwrap(), 2);
+        assert!(!graph
+            .is_inferred_edge_defined_by_keys(&edge("b", "child_of", "a"))
+            .unwrap());
+        assert!(graph
+            .is_inferred_edge_defined_by_keys(&edge("c", "child_of", "b"))
+            .unwrap());
+
+        // asserting an inferred edge keeps it when the inferred edges are deleted
+        graph
+            .add_edge_using_keys(edge("c", "child_of", "b"))
+            .unwrap();
+        graph.delete_inferred_edges().unwrap();
+        assert!(graph
+            .is_key_defined_edge_in_graph(&edge("b", "child_of", "a"))
+            .unwrap());
+        assert!(graph
+            .is_key_defined_edge_in_graph(&edge("c", "child_of", "b"))
+            .unwrap());
+        assert!(!graph
+            .is_key_defined_edge_in_graph(&edge("d", "child_of", "c"))
+            .unwrap());
+    }
+
+    #[test]
+    fn reject_invalid_rules() {
+        assert_eq!(
+            InferenceRule::new("ancestor_of", Vec::new())
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::InvalidInferenceRule)
+        );
+
+        let mut graph = family_graph();
+        let rules =
+            vec![InferenceRule::new("ancestor_of", vec![RuleAtom::new("does_not_exist")]).unwrap()];
+        assert_eq!(
+            graph.infer_edges(&rules).unwrap_err().error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::EdgeTypeDoesNotExist)
+        );
+        assert!(!graph.is_edge_type_in_graph("ancestor_of").unwrap());
+    }
+
+    #[test]
+    fn reject_inferred_edges_that_violate_schema() {
+        let mut graph = family_graph();
+        graph
+            .set_schema(
+                Schema::new().with_edge_type_constraint(
+                    "ancestor_of",
+                    EdgeTypeConstraint::new().functional(),
+                ),
+            )
+            .unwrap();
+
+        // a is an ancestor of b, c and d
+        assert_eq!(
+            graph
+                .infer_edges(&ancestor_rules())
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::SchemaViolation)
+        );
+        assert!(!graph.is_edge_type_in_graph("ancestor_of").unwrap());
+    }
+}
@@ -0,0 +1,316 @@
+use graphblas_sparse_linear_algebra::value_types::sparse_vector::{
+    GetVectorElementValue, SetVectorElement, SparseVector, VectorElement,
+};
+
+use crate::error::GraphComputingError;
+
+use crate::graph::graph::{ElementIndex, Graph, GraphTrait};
+use crate::graph::vertex::{VertexIndex, VertexKeyRef, VertexLabel, VertexLabelRef};
+use crate::persistence::write_ahead_log::GraphMutation;
+
+pub trait LabelVertex {
+    /// Adding a label that the vertex already has does nothing.
+    fn add_vertex_label(
+        &mut self,
+        vertex_key: &VertexKeyRef,
+        vertex_label: VertexLabel,
+    ) -> Result<(), GraphComputingError>;
+    fn add_vertex_label_by_index(
+        &mut self,
+        vertex_index: VertexIndex,
+        vertex_label: VertexLabel,
+    ) -> Result<(), GraphComputingError>;
+
+    /// Returns false if the vertex did not have the label.
+    fn remove_vertex_label(
+        &mut self,
+        vertex_key: &VertexKeyRef,
+        vertex_label: &VertexLabelRef,
+    ) -> Result<bool, GraphComputingError>;
+    fn remove_vertex_label_by_index(
+        &mut self,
+        vertex_index: VertexIndex,
+        vertex_label: &VertexLabelRef,
+    ) -> Result<bool, GraphComputingError>;
+
+    fn has_vertex_label(
+        &self,
+        vertex_key: &VertexKeyRef,
+        vertex_label: &VertexLabelRef,
+    ) -> Result<bool, GraphComputingError>;
+
+    /// The labels of the vertex, in alphabetical order.
+    fn vertex_labels(
+        &self,
+        vertex_key: &VertexKeyRef,
+    ) -> Result<Vec<&VertexLabelRef>, GraphComputingError>;
+}
+
+impl LabelVertex for Graph {
+    fn add_vertex_label(
+        &mut self,
+        vertex_key: &VertexKeyRef,
+        vertex_label: VertexLabel,
+    ) -> Result<(), GraphComputingError> {
+        let vertex_index = self.existing_vertex_index(vertex_key)?;
+        self.add_vertex_label_by_index(VertexIndex::new(vertex_index), vertex_label)
+    }
+
+    fn add_vertex_label_by_index(
+        &mut self,
+        vertex_index: VertexIndex,
+        vertex_label: VertexLabel,
+    ) -> Result<(), GraphComputingError> {
+        self.check_write_ahead_log_is_writable()?;
+        // Returns an error for a vertex index that is not in use
+        self.vertex_store_ref().get_ref(vertex_index)?;
+        if self.has_vertex_label_by_index(vertex_index.index(), &vertex_label)? {
+            return Ok(());
+        }
+        self.check_new_vertex_label_against_schema(vertex_index.index(), &vertex_label)?;
+
+        if !self.vertex_label_masks_ref().contains_key(&vertex_label) {
+            let vertex_label_mask =
+                SparseVector::<bool>::new(self.graphblas_context_ref(), &self.vertex_capacity()?)?;
+            self.vertex_label_masks_mut_ref()
+                .insert(vertex_label.clone(), vertex_label_mask);
+        }
+        self.vertex_label_masks_mut_ref()
+            .get_mut(&vertex_label)
+            .unwrap()
+            .set_element(VectorElement::new(vertex_index.index(), true))?;
+        self.append_to_write_ahead_log(|_| {
+            Ok(GraphMutation::AddVertexLabel {
+                vertex_index: vertex_index.index(),
+                vertex_label,
+            })
+        })
+    }
+
+    fn remove_vertex_label(
+        &mut self,
+        vertex_key: &VertexKeyRef,
+        vertex_label: &VertexLabelRef,
+    ) -> Result<bool, GraphComputingError> {
+        let vertex_index = self.existing_vertex_index(vertex_key)?;
+        self.remove_vertex_label_by_index(VertexIndex::new(vertex_index), vertex_label)
+    }
+
+    fn remove_vertex_label_by_index(
+        &mut self,
+        vertex_index: VertexIndex,
+        vertex_label: &VertexLabelRef,
+    ) -> Result<bool, GraphComputingError> {
+        self.check_write_ahead_log_is_writable()?;
+        self.vertex_store_ref().get_ref(vertex_index)?;
+        if !self.has_vertex_label_by_index(vertex_index.index(), vertex_label)? {
+            return Ok(false);
+        }
+        self.check_removed_vertex_label_against_schema(vertex_index.index(), vertex_label)?;
+
+        self.remove_vertex_label_from_mask(vertex_index.index(), vertex_label)?;
+        self.append_to_write_ahead_log(|_| {
+            Ok(GraphMutation::RemoveVertexLabel {
+                vertex_index: vertex_index.index(),
+                vertex_label: vertex_label.to_owned(),
+            })
+        })?;
+        Ok(true)
+    }
+
+    fn has_vertex_label(
+        &self,
+        vertex_key: &VertexKeyRef,
+        vertex_label: &VertexLabelRef,
+    ) -> Result<bool, GraphComputingError> {
+        let vertex_index = self.existing_vertex_index(vertex_key)?;
+        self.has_vertex_label_by_index(vertex_index, vertex_label)
+    }
+
+    fn vertex_labels(
+        &self,
+        vertex_key: &VertexKeyRef,
+    ) -> Result<Vec<&VertexLabelRef>, GraphComputingError> {
+        let vertex_index = self.existing_vertex_index(vertex_key)?;
+        let mut vertex_labels = Vec::new();
+        for (vertex_label, vertex_label_mask) in self.vertex_label_masks_ref().iter() {
+            if vertex_label_mask.get_element_value(&vertex_index)? {
+                vertex_labels.push(vertex_label.as_str());
+            }
+        }
+        vertex_labels.sort_unstable();
+        Ok(vertex_labels)
+    }
+}
+
+impl Graph {
+    pub(crate) fn has_vertex_label_by_index(
+        &self,
+        vertex_index: ElementIndex,
+        vertex_label: &VertexLabelRef,
+    ) -> Result<bool, GraphComputingError> {
+        match self.vertex_label_masks_ref().get(vertex_label) {
+            None => Ok(false),
+            Some(vertex_label_mask) => Ok(vertex_label_mask.get_element_value(&vertex_index)?),
+        }
+    }
+
+    /// Called when the vertex is deleted, the deletion is logged instead.
+    pub(crate) fn remove_all_vertex_labels(
+        &mut self,
+        vertex_index: ElementIndex,
+    ) -> Result<(), GraphComputingError> {
+        let mut vertex_labels = Vec::new();
+        for (vertex_label, vertex_label_mask) in self.vertex_label_masks_ref().iter() {
+            if vertex_label_mask.get_element_value(&vertex_index)? {
+                vertex_labels.push(vertex_label.to_owned());
+            }
+        }
+        for vertex_label in vertex_labels {
+            self.remove_vertex_label_from_mask(vertex_index, &vertex_label)?;
+        }
+        Ok(())
+    }
+
+    // A mask without vertices is dropped, such that the label is no longer listed
+    fn remove_vertex_label_from_mask(
+        &mut self,
+        vertex_index: ElementIndex,
+        vertex_label: &VertexLabelRef,
+    ) -> Result<(), GraphComputingError> {
+        if let Some(vertex_label_mask) = self.vertex_label_masks_mut_ref().get_mut(vertex_label) {
+            vertex_label_mask.drop_element(vertex_index)?;
+            if vertex_label_mask.number_of_stored_elements()? == 0 {
+                self.vertex_label_masks_mut_ref().remove(vertex_label);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::{GraphComputingErrorType, UserErrorType};
+    use crate::graph::vertex::Vertex;
+    use crate::operations::add_vertex::AddVertex;
+    use crate::operations::delete_vertex::DeleteVertex;
+    use crate::operations::select_vertex::SelectVertex;
+    use crate::operations::selection::operators::and::AndOperator;
+    use crate::operations::selection::operators::or::OrOperator;
+    use crate::tests::graph_with_vertices;
+
+    fn graph_with_labels() -> Graph {
+        let mut graph = graph_with_vertices(&["REQ-1", "REQ-2", "TEST-1", "TEST-2"]);
+        for (vertex_key, vertex_label) in [
+            ("REQ-1", "Requirement"),
+            ("REQ-2", "Requirement"),
+            ("REQ-2", "Safety"),
+            ("TEST-1", "Test"),
+            ("TEST-2", "Test"),
+            ("TEST-2", "Safety"),
+        ] {
+            graph
+                .add_vertex_label(vertex_key, String::from(vertex_label))
+                .unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn add_and_remove_vertex_labels() {
+        let mut graph = graph_with_labels();
+        assert_eq!(
+            graph.vertex_labels("REQ-2").unwrap(),
+            vec!["Requirement", "Safety"]
+        );
+        assert!(graph.has_vertex_label("TEST-1", "Test").unwrap());
+        assert!(!graph.has_vertex_label("TEST-1", "Requirement").unwrap());
+        assert!(!graph.has_vertex_label("TEST-1", "Design").unwrap());
+
+        graph
+            .add_vertex_label("REQ-2", String::from("Safety"))
+            .unwrap();
+        assert!(graph.remove_vertex_label("REQ-2", "Safety").unwrap());
+        assert!(!graph.remove_vertex_label("REQ-2", "Safety").unwrap());
+        assert_eq!(graph.vertex_labels("REQ-2").unwrap(), vec!["Requirement"]);
+
+        assert_eq!(
+            graph
+                .add_vertex_label("REQ-3", String::from("Requirement"))
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::VertexKeyNotFound)
+        );
+    }
+
+    #[test]
+    fn select_vertices_with_label() {
+        let mut graph = graph_with_labels();
+        let requirements = graph.select_vertices_with_label("Requirement").unwrap();
+        assert_eq!(
+            requirements.vertex_keys_ref().unwrap(),
+            vec!["REQ-1", "REQ-2"]
+        );
+
+        let safety = graph.select_vertices_with_label("Safety").unwrap();
+        assert_eq!(
+            requirements
+                .and(&safety)
+                .unwrap()
+                .vertex_keys_ref()
+                .unwrap(),
+            vec!["REQ-2"]
+        );
+        let tests = graph.select_vertices_with_label("Test").unwrap();
+        assert_eq!(
+            requirements.or(&tests).unwrap().vertex_keys_ref().unwrap(),
+            vec!["REQ-1", "REQ-2", "TEST-1", "TEST-2"]
+        );
+        assert!(graph
+            .select_vertices_with_label("Design")
+            .unwrap()
+            .vertex_keys_ref()
+            .unwrap()
+            .is_empty());
+
+        // Deleting a vertex removes its labels, also when its index is reused
+        graph
+            .delete_vertex_and_connected_edges_by_key(String::from("TEST-2"))
+            .unwrap();
+        graph
+            .add_new_vertex(Vertex::new(String::from("TEST-3"), 0u8.into()))
+            .unwrap();
+        assert!(graph.vertex_labels("TEST-3").unwrap().is_empty());
+        assert_eq!(
+            graph
+                .select_vertices_with_label("Safety")
+                .unwrap()
+                .vertex_keys_ref()
+                .unwrap(),
+            vec!["REQ-2"]
+        );
+    }
+
+    #[test]
+    fn label_masks_grow_with_vertex_capacity() {
+        let mut graph = graph_with_labels();
+        for vertex_index in 0..10 {
+            graph
+                .add_new_vertex(Vertex::new(format!("REQ-{}", vertex_index + 3), 0u8.into()))
+                .unwrap();
+        }
+        graph
+            .add_vertex_label("REQ-12", String::from("Requirement"))
+            .unwrap();
+        assert_eq!(
+            graph
+                .select_vertices_with_label("Requirement")
+                .unwrap()
+                .vertex_keys_ref()
+                .unwrap(),
+            vec!["REQ-1", "REQ-2", "REQ-12"]
+        );
+    }
+}
@@ -1,12 +1,20 @@
 pub mod add_edge;
 pub mod add_edge_type;
 pub mod add_vertex;
+pub mod define_schema;
 pub mod delete_edge;
 pub mod delete_vertex;
 pub mod drop_edge_type;
+pub mod infer_edges;
+pub mod label_vertex;
 pub mod read_edge;
 pub mod read_vertex_value;
 pub mod select_edge_type;
 pub mod select_vertex;
 pub mod selection;
+pub mod set_edge_value;
+pub mod set_edge_weight;
+pub mod step;
+pub mod transaction;
+pub mod transitive_closure;
 pub mod update_vertex;
@@ -1,8 +1,8 @@
-use crate::error::GraphComputingError;
+use crate::error::{GraphComputingError, UserError, UserErrorType};
 
 use crate::graph::edge::{
     DirectedEdgeDefinedByIndices, DirectedEdgeDefinedByKeys, EdgeToEdgeCoordinate, EdgeTypeIndex,
-    EdgeTypeRef,
+    EdgeTypeRef, EdgeValue, EdgeWeight, EdgeWeightType, IntegerEdgeWeight,
 };
 use crate::graph::graph::Graph;
 use crate::operations::read_vertex_value::ReadVertexValue;
@@ -17,6 +17,31 @@ pub trait ReadEdge {
         edge: &DirectedEdgeDefinedByIndices,
     ) -> Result<bool, GraphComputingError>;
 
+    /// The weight set with SetEdgeWeight, or DEFAULT_EDGE_WEIGHT. The edge must exist.
+    /// Integer weights are converted, the weight of an edge of a Boolean edge type is DEFAULT_EDGE_WEIGHT.
+    fn edge_weight_defined_by_keys(
+        &self,
+        edge: &DirectedEdgeDefinedByKeys,
+    ) -> Result<EdgeWeight, GraphComputingError>;
+
+    /// The weight set with SetEdgeWeight, or DEFAULT_INTEGER_EDGE_WEIGHT. The edge must exist.
+    /// Returns a UserError of type EdgeWeightTypeMismatch if the edge type does not have weights of type Integer64Bit.
+    fn integer_edge_weight_defined_by_keys(
+        &self,
+        edge: &DirectedEdgeDefinedByKeys,
+    ) -> Result<IntegerEdgeWeight, GraphComputingError>;
+
+    fn edge_weight_type(
+        &self,
+        edge_type: &EdgeTypeRef,
+    ) -> Result<EdgeWeightType, GraphComputingError>;
+
+    /// The value stored with the edge, None if the edge has no value. The edge must exist.
+    fn edge_value_defined_by_keys(
+        &self,
+        edge: &DirectedEdgeDefinedByKeys,
+    ) -> Result<Option<&EdgeValue>, GraphComputingError>;
+
     // TODO: review placement of this function
     fn is_edge_type_in_graph(&self, edge_type: &EdgeTypeRef) -> Result<bool, GraphComputingError>;
     fn is_index_defined_edge_type_in_graph(
@@ -57,6 +82,66 @@ impl ReadEdge for Graph {
         }
     }
 
+    fn edge_weight_defined_by_keys(
+        &self,
+        edge: &DirectedEdgeDefinedByKeys,
+    ) -> Result<EdgeWeight, GraphComputingError> {
+        let edge_coordinate = self.key_defined_edge_to_edge_coordinate(edge)?;
+        let edge_adjacency_matrix = self.get_edge_adjacency_matrix_ref(edge.edge_type_ref())?;
+        if !edge_adjacency_matrix.is_edge(&edge_coordinate)? {
+            return Err(UserError::new(
+                UserErrorType::EdgeDoesNotExist,
+                format!("Edge {:?} does not exist", edge),
+                None,
+            )
+            .into());
+        }
+        edge_adjacency_matrix.edge_weight(&edge_coordinate)
+    }
+
+    fn integer_edge_weight_defined_by_keys(
+        &self,
+        edge: &DirectedEdgeDefinedByKeys,
+    ) -> Result<IntegerEdgeWeight, GraphComputingError> {
+        let edge_coordinate = self.key_defined_edge_to_edge_coordinate(edge)?;
+        let edge_adjacency_matrix = self.get_edge_adjacency_matrix_ref(edge.edge_type_ref())?;
+        if !edge_adjacency_matrix.is_edge(&edge_coordinate)? {
+            return Err(UserError::new(
+                UserErrorType::EdgeDoesNotExist,
+                format!("Edge {:?} does not exist", edge),
+                None,
+            )
+            .into());
+        }
+        edge_adjacency_matrix.integer_edge_weight(&edge_coordinate)
+    }
+
+    fn edge_weight_type(
+        &self,
+        edge_type: &EdgeTypeRef,
+    ) -> Result<EdgeWeightType, GraphComputingError> {
+        Ok(self
+            .get_edge_adjacency_matrix_ref(edge_type)?
+            .edge_weight_type())
+    }
+
+    fn edge_value_defined_by_keys(
+        &self,
+        edge: &DirectedEdgeDefinedByKeys,
+    ) -> Result<Option<&EdgeValue>, GraphComputingError> {
+        let edge_coordinate = self.key_defined_edge_to_edge_coordinate(edge)?;
+        let edge_adjacency_matrix = self.get_edge_adjacency_matrix_ref(edge.edge_type_ref())?;
+        if !edge_adjacency_matrix.is_edge(&edge_coordinate)? {
+            return Err(UserError::new(
+                UserErrorType::EdgeDoesNotExist,
+                format!("Edge {:?} does not exist", edge),
+                None,
+            )
+            .into());
+        }
+        Ok(edge_adjacency_matrix.edge_value_ref(&edge_coordinate))
+    }
+
     fn is_edge_type_in_graph(&self, edge_type: &EdgeTypeRef) -> Result<bool, GraphComputingError> {
         Ok(self
             .edge_type_to_edge_type_index_map_ref()
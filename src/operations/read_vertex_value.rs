@@ -2,7 +2,7 @@ use crate::error::GraphComputingError;
 use crate::error::{UserError, UserErrorType};
 
 use crate::graph::graph::Graph;
-use crate::graph::vertex::{VertexIndex, VertexKey, VertexValue};
+use crate::graph::vertex::{PropertyNameRef, VertexIndex, VertexKey, VertexValue};
 
 pub trait ReadVertexValue {
     fn is_valid_vertex_key(&self, vertex_key: &VertexKey) -> bool;
@@ -12,6 +12,12 @@ pub trait ReadVertexValue {
         &self,
         vertex_index: VertexIndex,
     ) -> Result<&VertexValue, GraphComputingError>;
+    /// None if the vertex does not have the property.
+    fn vertex_property(
+        &self,
+        vertex_key: &VertexKey,
+        property_name: &PropertyNameRef,
+    ) -> Result<Option<&VertexValue>, GraphComputingError>;
 }
 
 impl ReadVertexValue for Graph {
@@ -35,6 +41,25 @@ impl ReadVertexValue for Graph {
         Ok(vertex.value_ref())
     }
 
+    fn vertex_property(
+        &self,
+        vertex_key: &VertexKey,
+        property_name: &PropertyNameRef,
+    ) -> Result<Option<&VertexValue>, GraphComputingError> {
+        match self.vertex_key_to_vertex_index_map_ref().get(vertex_key) {
+            None => Err(UserError::new(
+                UserErrorType::VertexKeyNotFound,
+                format!("No vertex found for key \"{}\")", vertex_key),
+                None,
+            )
+            .into()),
+            Some(&vertex_index) => Ok(self
+                .vertex_store_ref()
+                .get_ref(vertex_index)?
+                .property(property_name)),
+        }
+    }
+
     fn is_valid_vertex_key(&self, vertex_key: &VertexKey) -> bool {
         match self.vertex_key_to_vertex_index_map_ref().get(vertex_key) {
             None => false,
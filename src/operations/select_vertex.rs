@@ -6,11 +6,15 @@ use graphblas_sparse_linear_algebra::value_types::sparse_vector::{
     FromVectorElementList, SetVectorElement, SparseVector, VectorElement, VectorElementList,
 };
 
+use crate::algorithms::breadth_first_search::TraversalDirection;
 use crate::error::GraphComputingError;
 
-use crate::graph::edge::{EdgeType, EdgeTypeIndex};
+use crate::graph::edge::{EdgeType, EdgeTypeIndex, EdgeTypeRef};
 use crate::graph::graph::{Graph, GraphTrait};
-use crate::graph::vertex::{VertexIndex, VertexKeyAndIndexConversion, VertexKeyRef};
+use crate::graph::vertex::{
+    PropertyNameRef, Vertex, VertexIndex, VertexKeyAndIndexConversion, VertexKeyRef,
+    VertexLabelRef, VertexValue,
+};
 use crate::operations::selection::vertex_selection::VertexSelection;
 
 use super::select_edge_type::EdgeTypeSelectorTrait;
@@ -18,6 +22,14 @@ use super::select_edge_type::EdgeTypeSelectorTrait;
 static SECOND_BINARY_OPERATOR: Lazy<Second<bool, bool, bool>> =
     Lazy::new(|| Second::<bool, bool, bool>::new());
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HopRange {
+    /// All vertices within k hops, including the vertices that the search starts from
+    Within,
+    /// Only the vertices at a distance of exactly k hops
+    Exactly,
+}
+
 pub trait SelectVertex {
     fn select_vertex_by_key(
         &self,
@@ -58,6 +70,35 @@ pub trait SelectVertex {
         edge_type: EdgeTypeIndex,
         from_vertex_index: &VertexIndex,
     ) -> Result<VertexSelection, GraphComputingError>;
+
+    /// Selects the vertices that have the property, regardless of its value.
+    fn select_vertices_with_property(
+        &self,
+        property_name: &PropertyNameRef,
+    ) -> Result<VertexSelection<'_>, GraphComputingError>;
+    /// Selects the vertices of which the property equals value. Values of different types are never equal.
+    fn select_vertices_with_property_value(
+        &self,
+        property_name: &PropertyNameRef,
+        value: &VertexValue,
+    ) -> Result<VertexSelection<'_>, GraphComputingError>;
+
+    /// Selects the vertices that have the label. The selection is empty for a label that no vertex has.
+    fn select_vertices_with_label(
+        &self,
+        vertex_label: &VertexLabelRef,
+    ) -> Result<VertexSelection<'_>, GraphComputingError>;
+
+    /// Selects the vertices that are reached from the vertices in start within k hops,
+    /// following edges of any of edge_types in direction.
+    fn select_k_hop_neighbourhood(
+        &self,
+        start: &VertexSelection,
+        edge_types: &[&EdgeTypeRef],
+        direction: TraversalDirection,
+        k: usize,
+        hop_range: HopRange,
+    ) -> Result<VertexSelection<'_>, GraphComputingError>;
 }
 
 impl SelectVertex for Graph {
@@ -165,13 +206,86 @@ impl SelectVertex for Graph {
         let edge_selection = self.select_edge_type_by_index(edge_type)?;
         edge_selection.select_vertices_connected_from_vertex_by_index(to_vertex_index)
     }
+
+    fn select_vertices_with_property(
+        &self,
+        property_name: &PropertyNameRef,
+    ) -> Result<VertexSelection<'_>, GraphComputingError> {
+        self.select_vertices_matching(|vertex| vertex.property(property_name).is_some())
+    }
+
+    fn select_vertices_with_property_value(
+        &self,
+        property_name: &PropertyNameRef,
+        value: &VertexValue,
+    ) -> Result<VertexSelection<'_>, GraphComputingError> {
+        self.select_vertices_matching(|vertex| vertex.property(property_name) == Some(value))
+    }
+
+    fn select_vertices_with_label(
+        &self,
+        vertex_label: &VertexLabelRef,
+    ) -> Result<VertexSelection<'_>, GraphComputingError> {
+        match self.vertex_label_masks_ref().get(vertex_label) {
+            Some(vertex_label_mask) => VertexSelection::new(self, vertex_label_mask.clone()),
+            None => VertexSelection::new(
+                self,
+                SparseVector::<bool>::new(self.graphblas_context_ref(), &self.vertex_capacity()?)?,
+            ),
+        }
+    }
+
+    fn select_k_hop_neighbourhood(
+        &self,
+        start: &VertexSelection,
+        edge_types: &[&EdgeTypeRef],
+        direction: TraversalDirection,
+        k: usize,
+        hop_range: HopRange,
+    ) -> Result<VertexSelection<'_>, GraphComputingError> {
+        let start_vertex_indices = start
+            .vertex_indices_ref()?
+            .into_iter()
+            .map(|vertex_index| vertex_index.index())
+            .collect();
+        let (vertices_within_k_hops, vertices_at_k_hops) = self.k_hop_neighbourhood(
+            start_vertex_indices,
+            &self.traversed_edges(edge_types, direction)?,
+            k,
+        )?;
+        match hop_range {
+            HopRange::Within => VertexSelection::new(self, vertices_within_k_hops),
+            HopRange::Exactly => VertexSelection::new(self, vertices_at_k_hops),
+        }
+    }
+}
+
+impl Graph {
+    // Properties are not indexed, every vertex is visited
+    fn select_vertices_matching(
+        &self,
+        is_match: impl Fn(&Vertex) -> bool,
+    ) -> Result<VertexSelection<'_>, GraphComputingError> {
+        let mut vertex_mask =
+            SparseVector::<bool>::new(self.graphblas_context_ref(), &self.vertex_capacity()?)?;
+        for vertex_index in self.valid_vertex_indices()? {
+            if is_match(
+                self.vertex_store_ref()
+                    .get_ref(VertexIndex::new(vertex_index))?,
+            ) {
+                vertex_mask.set_element(VectorElement::new(vertex_index, true))?;
+            }
+        }
+        VertexSelection::new(self, vertex_mask)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use crate::graph::vertex::VertexValue;
+    use crate::operations::add_vertex::AddVertex;
+    use crate::operations::selection::operators::and::AndOperator;
     use crate::tests::standard_graph_for_testing::standard_graph_for_testing;
 
     #[test]
@@ -216,6 +330,54 @@ mod tests {
         assert_eq!(selected_values, vec![&1u8.into(), &2u8.into()])
     }
 
+    #[test]
+    fn select_k_hop_neighbourhood() {
+        let graph = standard_graph_for_testing();
+        let start = graph.select_vertex_by_key("-1.1").unwrap();
+        let selected_keys = |hops: usize, hop_range: HopRange| -> Vec<String> {
+            let mut vertex_keys: Vec<String> = graph
+                .select_k_hop_neighbourhood(
+                    &start,
+                    &["sign", "is_a"],
+                    TraversalDirection::Both,
+                    hops,
+                    hop_range,
+                )
+                .unwrap()
+                .vertex_keys_ref()
+                .unwrap()
+                .into_iter()
+                .map(String::from)
+                .collect();
+            vertex_keys.sort();
+            vertex_keys
+        };
+
+        assert_eq!(selected_keys(0, HopRange::Within), vec!["-1.1"]);
+        assert_eq!(
+            selected_keys(1, HopRange::Within),
+            vec!["-1.1", "negative", "real_number"]
+        );
+        // -1.1 -sign-> negative <-sign- -1, and -1.1 -is_a-> real_number <-is_a- 0, 1, ...
+        assert!(selected_keys(2, HopRange::Exactly).contains(&String::from("-1")));
+        assert!(!selected_keys(2, HopRange::Exactly).contains(&String::from("negative")));
+        assert!(selected_keys(3, HopRange::Exactly).contains(&String::from("integer")));
+
+        let vertices_within_one_hop = graph
+            .select_k_hop_neighbourhood(
+                &start,
+                &["smaller_than"],
+                TraversalDirection::Incoming,
+                1,
+                HopRange::Within,
+            )
+            .unwrap();
+        assert_eq!(
+            vertices_within_one_hop.vertex_keys_ref().unwrap(),
+            vec!["-1.1"]
+        );
+    }
+
     #[test]
     fn test_select_vertices_connected_to_vertex() {
         let graph = standard_graph_for_testing();
@@ -249,4 +411,38 @@ mod tests {
             vec!(&VertexValue::UnsignedInteger8Bit(2))
         );
     }
+
+    #[test]
+    fn select_vertices_with_property() {
+        let mut graph = Graph::new(5, 5).unwrap();
+        for (vertex_key, status, owner) in [
+            ("REQ-1", "approved", Some("alice")),
+            ("REQ-2", "draft", Some("bob")),
+            ("REQ-3", "approved", None),
+        ] {
+            let mut vertex = Vertex::new(String::from(vertex_key), 0u8.into())
+                .with_property(String::from("status"), String::from(status).into());
+            if let Some(owner) = owner {
+                vertex = vertex.with_property(String::from("owner"), String::from(owner).into());
+            }
+            graph.add_new_vertex(vertex).unwrap();
+        }
+
+        let approved = graph
+            .select_vertices_with_property_value("status", &String::from("approved").into())
+            .unwrap();
+        assert_eq!(approved.vertex_keys_ref().unwrap(), vec!["REQ-1", "REQ-3"]);
+
+        let owned = graph.select_vertices_with_property("owner").unwrap();
+        assert_eq!(
+            approved.and(&owned).unwrap().vertex_keys_ref().unwrap(),
+            vec!["REQ-1"]
+        );
+        assert!(graph
+            .select_vertices_with_property_value("status", &1u8.into())
+            .unwrap()
+            .vertex_keys_ref()
+            .unwrap()
+            .is_empty());
+    }
 }
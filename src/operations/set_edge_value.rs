@@ -0,0 +1,183 @@
+use crate::error::GraphComputingError;
+
+use crate::graph::edge::{
+    DirectedEdgeDefinedByIndices, DirectedEdgeDefinedByKeys, EdgeToEdgeCoordinate,
+    EdgeTypeKeyAndIndexConversion, EdgeValue,
+};
+use crate::graph::graph::Graph;
+use crate::graph::vertex::VertexIndex;
+use crate::persistence::write_ahead_log::GraphMutation;
+
+pub trait SetEdgeValue {
+    /// The edge must exist. An existing value is replaced.
+    /// The value is deleted together with the edge.
+    fn set_edge_value_using_keys(
+        &mut self,
+        edge: &DirectedEdgeDefinedByKeys,
+        value: EdgeValue,
+    ) -> Result<(), GraphComputingError>;
+
+    fn set_edge_value_using_indices(
+        &mut self,
+        edge: &DirectedEdgeDefinedByIndices,
+        value: EdgeValue,
+    ) -> Result<(), GraphComputingError>;
+
+    /// The edge must exist. Returns the deleted value, None if the edge had no value.
+    fn delete_edge_value_using_keys(
+        &mut self,
+        edge: &DirectedEdgeDefinedByKeys,
+    ) -> Result<Option<EdgeValue>, GraphComputingError>;
+
+    fn delete_edge_value_using_indices(
+        &mut self,
+        edge: &DirectedEdgeDefinedByIndices,
+    ) -> Result<Option<EdgeValue>, GraphComputingError>;
+}
+
+impl SetEdgeValue for Graph {
+    fn set_edge_value_using_keys(
+        &mut self,
+        edge: &DirectedEdgeDefinedByKeys,
+        value: EdgeValue,
+    ) -> Result<(), GraphComputingError> {
+        let edge = self.key_defined_edge_to_index_defined_edge(edge)?;
+        self.set_edge_value_using_indices(&edge, value)
+    }
+
+    fn set_edge_value_using_indices(
+        &mut self,
+        edge: &DirectedEdgeDefinedByIndices,
+        value: EdgeValue,
+    ) -> Result<(), GraphComputingError> {
+        self.check_write_ahead_log_is_writable()?;
+        let edge_coordinate = self.existing_edge_coordinate(edge, "set the value of")?;
+        self.adjacency_matrices_mut_ref()
+            .get_mut_ref(*edge.edge_type())?
+            .set_edge_value(&edge_coordinate, value.clone());
+        self.append_to_write_ahead_log(|_| {
+            Ok(GraphMutation::SetEdgeValue {
+                edge_type_index: edge.edge_type().index(),
+                from_vertex_index: edge_coordinate.row_index(),
+                to_vertex_index: edge_coordinate.column_index(),
+                value,
+            })
+        })
+    }
+
+    fn delete_edge_value_using_keys(
+        &mut self,
+        edge: &DirectedEdgeDefinedByKeys,
+    ) -> Result<Option<EdgeValue>, GraphComputingError> {
+        let edge = self.key_defined_edge_to_index_defined_edge(edge)?;
+        self.delete_edge_value_using_indices(&edge)
+    }
+
+    fn delete_edge_value_using_indices(
+        &mut self,
+        edge: &DirectedEdgeDefinedByIndices,
+    ) -> Result<Option<EdgeValue>, GraphComputingError> {
+        self.check_write_ahead_log_is_writable()?;
+        let edge_coordinate = self.existing_edge_coordinate(edge, "delete the value of")?;
+        let deleted_value = self
+            .adjacency_matrices_mut_ref()
+            .get_mut_ref(*edge.edge_type())?
+            .delete_edge_value(&edge_coordinate);
+        if deleted_value.is_some() {
+            self.append_to_write_ahead_log(|_| {
+                Ok(GraphMutation::DeleteEdgeValue {
+                    edge_type_index: edge.edge_type().index(),
+                    from_vertex_index: edge_coordinate.row_index(),
+                    to_vertex_index: edge_coordinate.column_index(),
+                })
+            })?;
+        }
+        Ok(deleted_value)
+    }
+}
+
+impl Graph {
+    pub(crate) fn key_defined_edge_to_index_defined_edge(
+        &self,
+        edge: &DirectedEdgeDefinedByKeys,
+    ) -> Result<DirectedEdgeDefinedByIndices, GraphComputingError> {
+        let edge_coordinate = self.key_defined_edge_to_edge_coordinate(edge)?;
+        // Returns a UserError for an edge type that does not exist
+        self.get_edge_adjacency_matrix_ref(edge.edge_type_ref())?;
+        let edge_type_index = *self.edge_type_ref_to_edge_type_index_ref(edge.edge_type_ref())?;
+        Ok(DirectedEdgeDefinedByIndices::new(
+            VertexIndex::new(edge_coordinate.row_index()),
+            edge_type_index,
+            VertexIndex::new(edge_coordinate.column_index()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::operations::add_edge::AddEdge;
+    use crate::operations::read_edge::ReadEdge;
+    use crate::tests::{edge, graph_with_vertices};
+
+    fn graph_with_traces() -> Graph {
+        let mut graph = graph_with_vertices(&["requirement", "design", "test"]);
+        graph
+            .add_edge_and_edge_type_using_keys(DirectedEdgeDefinedByKeys::new_with_value(
+                String::from("requirement"),
+                String::from("traces_to"),
+                String::from("design"),
+                String::from("spec.md").into(),
+            ))
+            .unwrap();
+        graph
+            .add_edge_using_keys(edge("requirement", "traces_to", "test"))
+            .unwrap();
+        graph
+    }
+
+    #[test]
+    fn add_set_and_delete_edge_value() {
+        let mut graph = graph_with_traces();
+        assert_eq!(
+            graph
+                .edge_value_defined_by_keys(&edge("requirement", "traces_to", "design"))
+                .unwrap(),
+            Some(&EdgeValue::String(String::from("spec.md")))
+        );
+        assert_eq!(
+            graph
+                .edge_value_defined_by_keys(&edge("requirement", "traces_to", "test"))
+                .unwrap(),
+            None
+        );
+
+        graph
+            .set_edge_value_using_keys(&edge("requirement", "traces_to", "test"), 0.75f64.into())
+            .unwrap();
+        assert_eq!(
+            graph
+                .edge_value_defined_by_keys(&edge("requirement", "traces_to", "test"))
+                .unwrap(),
+            Some(&EdgeValue::FloatingPoint64Bit(0.75))
+        );
+
+        // Adding an existing edge without a value keeps its value
+        graph
+            .add_edge_using_keys(edge("requirement", "traces_to", "design"))
+            .unwrap();
+        assert_eq!(
+            graph
+                .delete_edge_value_using_keys(&edge("requirement", "traces_to", "design"))
+                .unwrap(),
+            Some(EdgeValue::String(String::from("spec.md")))
+        );
+        assert_eq!(
+            graph
+                .delete_edge_value_using_keys(&edge("requirement", "traces_to", "design"))
+                .unwrap(),
+            None
+        );
+    }
+}
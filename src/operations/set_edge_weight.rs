@@ -0,0 +1,335 @@
+use crate::error::{GraphComputingError, UserError, UserErrorType};
+
+use crate::graph::edge::adjacency_matrix::EdgeCoordinate;
+use crate::graph::edge::{
+    DirectedEdgeDefinedByIndices, DirectedEdgeDefinedByKeys, EdgeToEdgeCoordinate, EdgeWeight,
+    IntegerEdgeWeight,
+};
+use crate::graph::graph::Graph;
+use crate::persistence::write_ahead_log::GraphMutation;
+
+pub trait SetEdgeWeight {
+    /// The edge must exist. Edges without a weight weigh DEFAULT_EDGE_WEIGHT.
+    /// The weight is deleted together with the edge.
+    /// Returns a UserError of type EdgeWeightTypeMismatch if the edge type does not have weights of type FloatingPoint64Bit.
+    fn set_edge_weight_using_keys(
+        &mut self,
+        edge: &DirectedEdgeDefinedByKeys,
+        weight: EdgeWeight,
+    ) -> Result<(), GraphComputingError>;
+
+    fn set_edge_weight_using_indices(
+        &mut self,
+        edge: &DirectedEdgeDefinedByIndices,
+        weight: EdgeWeight,
+    ) -> Result<(), GraphComputingError>;
+
+    /// The edge must exist. Edges without a weight weigh DEFAULT_INTEGER_EDGE_WEIGHT.
+    /// Returns a UserError of type EdgeWeightTypeMismatch if the edge type does not have weights of type Integer64Bit.
+    fn set_integer_edge_weight_using_keys(
+        &mut self,
+        edge: &DirectedEdgeDefinedByKeys,
+        weight: IntegerEdgeWeight,
+    ) -> Result<(), GraphComputingError>;
+
+    fn set_integer_edge_weight_using_indices(
+        &mut self,
+        edge: &DirectedEdgeDefinedByIndices,
+        weight: IntegerEdgeWeight,
+    ) -> Result<(), GraphComputingError>;
+}
+
+impl SetEdgeWeight for Graph {
+    fn set_edge_weight_using_keys(
+        &mut self,
+        edge: &DirectedEdgeDefinedByKeys,
+        weight: EdgeWeight,
+    ) -> Result<(), GraphComputingError> {
+        let edge = self.key_defined_edge_to_index_defined_edge(edge)?;
+        self.set_edge_weight_using_indices(&edge, weight)
+    }
+
+    fn set_edge_weight_using_indices(
+        &mut self,
+        edge: &DirectedEdgeDefinedByIndices,
+        weight: EdgeWeight,
+    ) -> Result<(), GraphComputingError> {
+        self.check_write_ahead_log_is_writable()?;
+        let edge_coordinate = self.existing_edge_coordinate(edge, "set the weight of")?;
+        self.adjacency_matrices_mut_ref()
+            .get_mut_ref(*edge.edge_type())?
+            .set_edge_weight(&edge_coordinate, weight)?;
+        self.append_to_write_ahead_log(|_| {
+            Ok(GraphMutation::SetEdgeWeight {
+                edge_type_index: edge.edge_type().index(),
+                from_vertex_index: edge_coordinate.row_index(),
+                to_vertex_index: edge_coordinate.column_index(),
+                weight,
+            })
+        })
+    }
+
+    fn set_integer_edge_weight_using_keys(
+        &mut self,
+        edge: &DirectedEdgeDefinedByKeys,
+        weight: IntegerEdgeWeight,
+    ) -> Result<(), GraphComputingError> {
+        let edge = self.key_defined_edge_to_index_defined_edge(edge)?;
+        self.set_integer_edge_weight_using_indices(&edge, weight)
+    }
+
+    fn set_integer_edge_weight_using_indices(
+        &mut self,
+        edge: &DirectedEdgeDefinedByIndices,
+        weight: IntegerEdgeWeight,
+    ) -> Result<(), GraphComputingError> {
+        self.check_write_ahead_log_is_writable()?;
+        let edge_coordinate = self.existing_edge_coordinate(edge, "set the weight of")?;
+        self.adjacency_matrices_mut_ref()
+            .get_mut_ref(*edge.edge_type())?
+            .set_integer_edge_weight(&edge_coordinate, weight)?;
+        self.append_to_write_ahead_log(|_| {
+            Ok(GraphMutation::SetIntegerEdgeWeight {
+                edge_type_index: edge.edge_type().index(),
+                from_vertex_index: edge_coordinate.row_index(),
+                to_vertex_index: edge_coordinate.column_index(),
+                weight,
+            })
+        })
+    }
+}
+
+impl Graph {
+    /// Returns a UserError of type EdgeDoesNotExist if the edge does not exist,
+    /// explaining that the edge is required to perform operation, e.g. "set the weight of".
+    pub(crate) fn existing_edge_coordinate(
+        &self,
+        edge: &DirectedEdgeDefinedByIndices,
+        operation: &str,
+    ) -> Result<EdgeCoordinate, GraphComputingError> {
+        let edge_coordinate = self.index_defined_edge_to_edge_coordinate(edge)?;
+        if !self
+            .adjacency_matrices_ref()
+            .get_ref(*edge.edge_type())?
+            .is_edge(&edge_coordinate)?
+        {
+            return Err(UserError::new(
+                UserErrorType::EdgeDoesNotExist,
+                format!(
+                    "Cannot {} edge {:?}, the edge does not exist",
+                    operation, edge
+                ),
+                None,
+            )
+            .into());
+        }
+        Ok(edge_coordinate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::GraphComputingErrorType;
+    use crate::graph::edge::{EdgeWeightType, DEFAULT_EDGE_WEIGHT, DEFAULT_INTEGER_EDGE_WEIGHT};
+    use crate::graph::vertex::Vertex;
+    use crate::operations::add_edge::AddEdge;
+    use crate::operations::add_edge_type::AddEdgeType;
+    use crate::operations::add_vertex::AddVertex;
+    use crate::operations::delete_edge::DeleteEdge;
+    use crate::operations::delete_vertex::DeleteVertex;
+    use crate::operations::read_edge::ReadEdge;
+    use crate::tests::{edge, graph_with_vertices};
+
+    fn graph_with_costs() -> Graph {
+        let mut graph = graph_with_vertices(&["a", "b", "c"]);
+        graph
+            .add_new_edge_type_with_weight_type(
+                String::from("costs"),
+                EdgeWeightType::FloatingPoint64Bit,
+            )
+            .unwrap();
+        graph.add_edge_using_keys(edge("a", "costs", "b")).unwrap();
+        graph.add_edge_using_keys(edge("b", "costs", "c")).unwrap();
+        graph
+    }
+
+    #[test]
+    fn set_and_read_edge_weight() {
+        let mut graph = graph_with_costs();
+        assert_eq!(
+            graph
+                .edge_weight_defined_by_keys(&edge("a", "costs", "b"))
+                .unwrap(),
+            DEFAULT_EDGE_WEIGHT
+        );
+
+        graph
+            .set_edge_weight_using_keys(&edge("a", "costs", "b"), 0.0)
+            .unwrap();
+        graph
+            .set_edge_weight_using_keys(&edge("b", "costs", "c"), 2.5)
+            .unwrap();
+        assert_eq!(
+            graph
+                .edge_weight_defined_by_keys(&edge("a", "costs", "b"))
+                .unwrap(),
+            0.0
+        );
+        assert_eq!(
+            graph
+                .edge_weight_defined_by_keys(&edge("b", "costs", "c"))
+                .unwrap(),
+            2.5
+        );
+    }
+
+    #[test]
+    fn weight_is_deleted_with_edge() {
+        let mut graph = graph_with_costs();
+        graph
+            .set_edge_weight_using_keys(&edge("a", "costs", "b"), 3.0)
+            .unwrap();
+        graph
+            .set_edge_weight_using_keys(&edge("b", "costs", "c"), 4.0)
+            .unwrap();
+
+        graph
+            .delete_edge_defined_by_keys(&edge("a", "costs", "b"))
+            .unwrap();
+        graph.add_edge_using_keys(edge("a", "costs", "b")).unwrap();
+        assert_eq!(
+            graph
+                .edge_weight_defined_by_keys(&edge("a", "costs", "b"))
+                .unwrap(),
+            DEFAULT_EDGE_WEIGHT
+        );
+
+        graph
+            .delete_vertex_and_connected_edges_by_key(String::from("c"))
+            .unwrap();
+        graph
+            .add_new_vertex(Vertex::new(String::from("c"), 0u8.into()))
+            .unwrap();
+        graph.add_edge_using_keys(edge("b", "costs", "c")).unwrap();
+        assert_eq!(
+            graph
+                .edge_weight_defined_by_keys(&edge("b", "costs", "c"))
+                .unwrap(),
+            DEFAULT_EDGE_WEIGHT
+        );
+    }
+
+    #[test]
+    fn edge_must_exist() {
+        let mut graph = graph_with_costs();
+        assert_eq!(
+            graph
+                .set_edge_weight_using_keys(&edge("a", "costs", "c"), 1.0)
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::EdgeDoesNotExist)
+        );
+        assert_eq!(
+            graph
+                .set_edge_weight_using_keys(&edge("a", "risk", "b"), 1.0)
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::EdgeTypeDoesNotExist)
+        );
+    }
+
+    #[test]
+    fn set_and_read_integer_edge_weight() {
+        let mut graph = graph_with_costs();
+        graph
+            .add_new_edge_type_with_weight_type(
+                String::from("occurrences"),
+                EdgeWeightType::Integer64Bit,
+            )
+            .unwrap();
+        graph
+            .add_edge_using_keys(edge("a", "occurrences", "b"))
+            .unwrap();
+        graph
+            .add_edge_using_keys(edge("a", "occurrences", "c"))
+            .unwrap();
+        assert_eq!(
+            graph.edge_weight_type("occurrences").unwrap(),
+            EdgeWeightType::Integer64Bit
+        );
+
+        graph
+            .set_integer_edge_weight_using_keys(&edge("a", "occurrences", "b"), i64::MAX)
+            .unwrap();
+        assert_eq!(
+            graph
+                .integer_edge_weight_defined_by_keys(&edge("a", "occurrences", "b"))
+                .unwrap(),
+            i64::MAX
+        );
+        assert_eq!(
+            graph
+                .integer_edge_weight_defined_by_keys(&edge("a", "occurrences", "c"))
+                .unwrap(),
+            DEFAULT_INTEGER_EDGE_WEIGHT
+        );
+        graph
+            .set_integer_edge_weight_using_keys(&edge("a", "occurrences", "c"), -3)
+            .unwrap();
+        assert_eq!(
+            graph
+                .edge_weight_defined_by_keys(&edge("a", "occurrences", "c"))
+                .unwrap(),
+            -3.0
+        );
+
+        assert_eq!(
+            graph
+                .set_edge_weight_using_keys(&edge("a", "occurrences", "c"), 1.5)
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::EdgeWeightTypeMismatch)
+        );
+        assert_eq!(
+            graph
+                .set_integer_edge_weight_using_keys(&edge("a", "costs", "b"), 2)
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::EdgeWeightTypeMismatch)
+        );
+    }
+
+    #[test]
+    fn boolean_edge_type_has_no_weights() {
+        let mut graph = graph_with_costs();
+        graph.add_new_edge_type(String::from("blocks")).unwrap();
+        graph.add_edge_using_keys(edge("a", "blocks", "c")).unwrap();
+
+        assert_eq!(
+            graph
+                .edge_weight_defined_by_keys(&edge("a", "blocks", "c"))
+                .unwrap(),
+            DEFAULT_EDGE_WEIGHT
+        );
+        assert_eq!(
+            graph
+                .set_edge_weight_using_keys(&edge("a", "blocks", "c"), 2.0)
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::EdgeWeightTypeMismatch)
+        );
+        assert_eq!(
+            graph
+                .integer_edge_weight_defined_by_keys(&edge("a", "blocks", "c"))
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::EdgeWeightTypeMismatch)
+        );
+        assert_eq!(
+            graph.edge_weight_type("blocks").unwrap(),
+            EdgeWeightType::Boolean
+        );
+    }
+}
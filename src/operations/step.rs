@@ -1 +1,255 @@
-// Trait for graph-traversal-step
\ No newline at end of file
+use graphblas_sparse_linear_algebra::value_types::sparse_vector::{
+    SetVectorElement, SparseVector, VectorElement,
+};
+
+use crate::error::GraphComputingError;
+use crate::graph::edge::adjacency_matrix::selected_element_indices;
+use crate::graph::edge::EdgeType;
+use crate::graph::graph::{Graph, GraphTrait};
+use crate::graph::vertex::{
+    Vertex, VertexIndex, VertexKey, VertexKeyAndIndexConversion, VertexKeyRef, VertexValue,
+};
+use crate::operations::selection::vertex_selection::VertexSelection;
+
+// Steps follow the naming of the Gremlin traversal language,
+// https://tinkerpop.apache.org/docs/3.5.1/reference/#graph-traversal-steps
+#[derive(Clone, Debug)]
+enum Step {
+    V(VertexKey),
+    Out(EdgeType),
+    In(EdgeType),
+    Both(EdgeType),
+    HasValue(VertexValue),
+    Dedup,
+}
+
+/// A traversal starts at a set of vertices, and moves along edges to select the connected vertices.
+///
+/// Steps are recorded by the builder, and executed when the result is requested.
+/// Each step that moves along edges is a single operation on the vertex mask and the adjacency matrix of the edge type,
+/// independent of the number of vertices the traversal is at.
+///
+/// The traversal is at a set of vertices. A vertex reached by multiple paths is selected once.
+#[derive(Clone, Debug)]
+pub struct Traversal<'g> {
+    graph: &'g Graph,
+    steps: Vec<Step>,
+}
+
+pub trait Traverse {
+    fn traverse(&self) -> Traversal<'_>;
+}
+
+impl Traverse for Graph {
+    fn traverse(&self) -> Traversal<'_> {
+        Traversal {
+            graph: self,
+            steps: Vec::new(),
+        }
+    }
+}
+
+impl<'g> Traversal<'g> {
+    /// Starts the traversal at the vertex with vertex_key. A key that is not in the graph selects no vertex.
+    #[allow(non_snake_case)]
+    pub fn V(mut self, vertex_key: &VertexKeyRef) -> Self {
+        self.steps.push(Step::V(vertex_key.to_owned()));
+        self
+    }
+
+    /// Moves to the vertices with an edge of edge_type from the current vertices.
+    pub fn out(mut self, edge_type: &str) -> Self {
+        self.steps.push(Step::Out(edge_type.to_owned()));
+        self
+    }
+
+    /// Moves to the vertices with an edge of edge_type to the current vertices.
+    pub fn in_(mut self, edge_type: &str) -> Self {
+        self.steps.push(Step::In(edge_type.to_owned()));
+        self
+    }
+
+    /// Moves to the vertices with an edge of edge_type to or from the current vertices.
+    pub fn both(mut self, edge_type: &str) -> Self {
+        self.steps.push(Step::Both(edge_type.to_owned()));
+        self
+    }
+
+    /// Keeps the current vertices with a value equal to vertex_value.
+    pub fn has_value(mut self, vertex_value: VertexValue) -> Self {
+        self.steps.push(Step::HasValue(vertex_value));
+        self
+    }
+
+    /// Provided for compatibility with Gremlin. The traversal is at a set of vertices, such that its vertices are always distinct.
+    pub fn dedup(mut self) -> Self {
+        self.steps.push(Step::Dedup);
+        self
+    }
+
+    pub fn select(&self) -> Result<VertexSelection<'g>, GraphComputingError> {
+        VertexSelection::new(self.graph, self.execute()?)
+    }
+
+    pub fn vertices(&self) -> Result<Vec<&'g Vertex>, GraphComputingError> {
+        let mut vertices = Vec::new();
+        for vertex_index in selected_element_indices(&self.execute()?)?
+            .into_iter()
+            .map(VertexIndex::new)
+        {
+            vertices.push(self.graph.vertex_store_ref().get_ref(vertex_index)?);
+        }
+        Ok(vertices)
+    }
+
+    pub fn keys(&self) -> Result<Vec<&'g VertexKeyRef>, GraphComputingError> {
+        Ok(self
+            .vertices()?
+            .into_iter()
+            .map(|vertex| vertex.key_ref())
+            .collect())
+    }
+
+    pub fn values(&self) -> Result<Vec<&'g VertexValue>, GraphComputingError> {
+        Ok(self
+            .vertices()?
+            .into_iter()
+            .map(|vertex| vertex.value_ref())
+            .collect())
+    }
+
+    fn execute(&self) -> Result<SparseVector<bool>, GraphComputingError> {
+        let mut vertex_mask = self.new_vertex_mask()?;
+        for step in self.steps.iter() {
+            vertex_mask = match step {
+                Step::V(vertex_key) => {
+                    let mut start_vertex_mask = self.new_vertex_mask()?;
+                    if let Ok(vertex_index) =
+                        self.graph.vertex_key_ref_to_vertex_index_ref(vertex_key)
+                    {
+                        start_vertex_mask
+                            .set_element(VectorElement::new(vertex_index.index(), true))?;
+                    }
+                    start_vertex_mask
+                }
+                Step::Out(edge_type) => self
+                    .graph
+                    .get_edge_adjacency_matrix_ref(edge_type)?
+                    .get_to_vertex_index_mask_for_from_vertices(&vertex_mask)?,
+                Step::In(edge_type) => self
+                    .graph
+                    .get_edge_adjacency_matrix_ref(edge_type)?
+                    .get_from_vertex_index_mask_for_to_vertices(&vertex_mask)?,
+                Step::Both(edge_type) => self
+                    .graph
+                    .get_edge_adjacency_matrix_ref(edge_type)?
+                    .get_vertex_index_mask_for_connected_vertices(&vertex_mask)?,
+                Step::HasValue(vertex_value) => {
+                    let mut filtered_vertex_mask = self.new_vertex_mask()?;
+                    for vertex_index in selected_element_indices(&vertex_mask)?
+                        .into_iter()
+                        .map(VertexIndex::new)
+                    {
+                        let vertex = self.graph.vertex_store_ref().get_ref(vertex_index)?;
+                        if vertex.value_ref() == vertex_value {
+                            filtered_vertex_mask
+                                .set_element(VectorElement::new(vertex_index.index(), true))?;
+                        }
+                    }
+                    filtered_vertex_mask
+                }
+                Step::Dedup => vertex_mask,
+            }
+        }
+        Ok(vertex_mask)
+    }
+
+    fn new_vertex_mask(&self) -> Result<SparseVector<bool>, GraphComputingError> {
+        Ok(SparseVector::new(
+            self.graph.graphblas_context_ref(),
+            &self.graph.vertex_capacity()?,
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::{GraphComputingErrorType, UserErrorType};
+    use crate::tests::standard_graph_for_testing::standard_graph_for_testing;
+
+    #[test]
+    fn traverse_out_and_in() {
+        let graph = standard_graph_for_testing();
+
+        let mut keys = graph
+            .traverse()
+            .V("-1.1")
+            .out("is_a")
+            .in_("is_a")
+            .keys()
+            .unwrap();
+        keys.sort_unstable();
+        assert_eq!(
+            keys,
+            vec!["-1", "-1.1", "0", "1", "1.1", "1.2", "1_duplicate", "2"]
+        );
+    }
+
+    #[test]
+    fn traverse_with_value_filter() {
+        let graph = standard_graph_for_testing();
+
+        let values = graph
+            .traverse()
+            .V("negative")
+            .in_("sign")
+            .has_value(VertexValue::Integer8Bit(-1))
+            .dedup()
+            .values()
+            .unwrap();
+        assert_eq!(values, vec![&VertexValue::Integer8Bit(-1)]);
+    }
+
+    #[test]
+    fn traverse_both_directions() {
+        let graph = standard_graph_for_testing();
+
+        let selection = graph
+            .traverse()
+            .V("-1")
+            .both("smaller_than")
+            .select()
+            .unwrap();
+        let mut keys = selection.vertex_keys_ref().unwrap();
+        keys.sort_unstable();
+        assert_eq!(
+            keys,
+            vec!["-1.1", "0", "1", "1.1", "1.2", "1_duplicate", "2"]
+        );
+    }
+
+    #[test]
+    fn traverse_from_unknown_vertex() {
+        let graph = standard_graph_for_testing();
+
+        assert!(graph
+            .traverse()
+            .V("does_not_exist")
+            .out("is_a")
+            .keys()
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            graph
+                .traverse()
+                .V("1")
+                .out("does_not_exist")
+                .keys()
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::EdgeTypeDoesNotExist)
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use crate::error::GraphComputingError;
+
+use crate::graph::graph::Graph;
+
+pub trait Transaction {
+    /// Applies the operations as a single atomic unit. If the operations return an error,
+    /// all changes made to the graph by the operations are rolled back, and the error is returned.
+    ///
+    /// The transaction exposes the graph itself, such that all operation traits are available.
+    /// Transactions can be nested; rolling back an inner transaction keeps the changes made by the outer transaction.
+    /// A durable graph appends the changes of the outermost transaction to its write-ahead log as a single record,
+    /// after the operations succeeded.
+    ///
+    /// NOTE: rollback is implemented by restoring a copy-on-write clone of the graph made at the start of the transaction.
+    /// The first modification of the vertices, the key maps or the adjacency matrices within the transaction copies
+    /// the whole collection: all vertices, a whole key map, or the Vec of all adjacency matrices.
+    /// Copying the adjacency matrices only copies their handles; the GraphBLAS matrix of an edge type is copied
+    /// when that edge type is first modified.
+    fn transaction<T, F>(&mut self, operations: F) -> Result<T, GraphComputingError>
+    where
+        F: FnOnce(&mut Graph) -> Result<T, GraphComputingError>;
+}
+
+impl Transaction for Graph {
+    fn transaction<T, F>(&mut self, operations: F) -> Result<T, GraphComputingError>
+    where
+        F: FnOnce(&mut Graph) -> Result<T, GraphComputingError>,
+    {
+        let graph_before_transaction = self.clone();
+        let write_ahead_log_savepoint = self.begin_write_ahead_log_transaction();
+
+        let result = match operations(self) {
+            Ok(value) => self
+                .commit_write_ahead_log_transaction(write_ahead_log_savepoint)
+                .map(|_| value),
+            Err(error) => Err(error),
+        };
+
+        match result {
+            Ok(value) => Ok(value),
+            Err(error) => {
+                self.roll_back_write_ahead_log_transaction(write_ahead_log_savepoint);
+                // The clone does not own the write-ahead log, it remains with the current graph
+                let write_ahead_log = self.write_ahead_log_mut_ref().take();
+                *self = graph_before_transaction;
+                *self.write_ahead_log_mut_ref() = write_ahead_log;
+                Err(error)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::{GraphComputingErrorType, LogicErrorType};
+    use crate::graph::edge::DirectedEdgeDefinedByKeys;
+    use crate::graph::graph::GraphTrait;
+    use crate::graph::vertex::Vertex;
+    use crate::operations::add_edge::AddEdge;
+    use crate::operations::add_vertex::AddVertex;
+    use crate::operations::read_edge::ReadEdge;
+    use crate::operations::read_vertex_value::ReadVertexValue;
+    use crate::tests::standard_graph_for_testing::standard_graph_for_testing;
+
+    #[test]
+    fn commit_transaction() {
+        let mut graph = standard_graph_for_testing();
+        let number_of_vertices = graph.number_of_vertices().unwrap();
+
+        let edge = DirectedEdgeDefinedByKeys::new(
+            String::from("3"),
+            String::from("larger_than"),
+            String::from("2"),
+        );
+        graph
+            .transaction(|transaction| {
+                transaction.add_new_vertex(Vertex::new(String::from("3"), 3u8.into()))?;
+                transaction.add_edge_using_keys(edge.clone())
+            })
+            .unwrap();
+
+        assert_eq!(graph.number_of_vertices().unwrap(), number_of_vertices + 1);
+        assert!(graph.is_key_defined_edge_in_graph(&edge).unwrap());
+    }
+
+    #[test]
+    fn roll_back_transaction() {
+        let mut graph = standard_graph_for_testing();
+        let number_of_vertices = graph.number_of_vertices().unwrap();
+        let number_of_edge_types = graph.number_of_edge_types().unwrap();
+
+        let result = graph.transaction(|transaction| {
+            transaction.add_new_vertex(Vertex::new(String::from("3"), 3u8.into()))?;
+            transaction.add_edge_and_edge_type_using_keys(DirectedEdgeDefinedByKeys::new(
+                String::from("3"),
+                String::from("new_edge_type"),
+                String::from("2"),
+            ))?;
+            transaction.add_edge_and_edge_type_using_keys(DirectedEdgeDefinedByKeys::new(
+                String::from("3"),
+                String::from("new_edge_type"),
+                String::from("does_not_exist"),
+            ))
+        });
+
+        assert_eq!(
+            result.unwrap_err().error_type(),
+            GraphComputingErrorType::LogicErrorType(LogicErrorType::VertexMustExist)
+        );
+        assert_eq!(graph.number_of_vertices().unwrap(), number_of_vertices);
+        assert_eq!(graph.number_of_edge_types().unwrap(), number_of_edge_types);
+        assert!(!graph.is_valid_vertex_key(&String::from("3")));
+        assert!(!graph.is_edge_type_in_graph("new_edge_type").unwrap());
+
+        // indices freed by the rollback are available again
+        let vertex = Vertex::new(String::from("4"), 4u8.into());
+        let mut graph_without_transaction = standard_graph_for_testing();
+        assert_eq!(
+            graph.add_new_vertex(vertex.clone()).unwrap(),
+            graph_without_transaction.add_new_vertex(vertex).unwrap()
+        );
+    }
+
+    #[test]
+    fn roll_back_nested_transaction() {
+        let mut graph = standard_graph_for_testing();
+
+        graph
+            .transaction(|transaction| {
+                transaction.add_new_vertex(Vertex::new(String::from("3"), 3u8.into()))?;
+                let inner_result = transaction.transaction(|inner_transaction| {
+                    inner_transaction.add_new_vertex(Vertex::new(String::from("4"), 4u8.into()))?;
+                    inner_transaction.add_new_vertex(Vertex::new(String::from("3"), 3u8.into()))
+                });
+                assert!(inner_result.is_err());
+                Ok(())
+            })
+            .unwrap();
+
+        assert!(graph.is_valid_vertex_key(&String::from("3")));
+        assert!(!graph.is_valid_vertex_key(&String::from("4")));
+    }
+}
@@ -0,0 +1,171 @@
+use graphblas_sparse_linear_algebra::value_types::sparse_matrix::{Size, SparseMatrix};
+
+use crate::error::GraphComputingError;
+use crate::graph::edge::boolean_matrix_operations::{
+    difference, from_coordinates, is_empty, multiply, union,
+};
+use crate::graph::edge::{EdgeTypeIndex, EdgeTypeRef};
+use crate::graph::graph::{Graph, GraphTrait};
+use crate::operations::add_edge_type::AddEdgeType;
+use crate::operations::transaction::Transaction;
+
+pub trait TransitiveClosure {
+    /// Adds closure_edge_type, with an edge from each vertex to every vertex it reaches by one or more edges of edge_type.
+    /// closure_edge_type must not exist yet. The closure is not updated when edges of edge_type change.
+    fn transitive_closure(
+        &mut self,
+        edge_type: &EdgeTypeRef,
+        closure_edge_type: &EdgeTypeRef,
+    ) -> Result<EdgeTypeIndex, GraphComputingError>;
+
+    /// Like transitive_closure, and additionally connects every vertex to itself,
+    /// i.e. a vertex reaches the vertices connected by zero or more edges of edge_type.
+    fn reflexive_transitive_closure(
+        &mut self,
+        edge_type: &EdgeTypeRef,
+        closure_edge_type: &EdgeTypeRef,
+    ) -> Result<EdgeTypeIndex, GraphComputingError>;
+}
+
+impl TransitiveClosure for Graph {
+    fn transitive_closure(
+        &mut self,
+        edge_type: &EdgeTypeRef,
+        closure_edge_type: &EdgeTypeRef,
+    ) -> Result<EdgeTypeIndex, GraphComputingError> {
+        self.transaction(|graph| graph.add_transitive_closure(edge_type, closure_edge_type, false))
+    }
+
+    fn reflexive_transitive_closure(
+        &mut self,
+        edge_type: &EdgeTypeRef,
+        closure_edge_type: &EdgeTypeRef,
+    ) -> Result<EdgeTypeIndex, GraphComputingError> {
+        self.transaction(|graph| graph.add_transitive_closure(edge_type, closure_edge_type, true))
+    }
+}
+
+impl Graph {
+    fn add_transitive_closure(
+        &mut self,
+        edge_type: &EdgeTypeRef,
+        closure_edge_type: &EdgeTypeRef,
+        is_reflexive: bool,
+    ) -> Result<EdgeTypeIndex, GraphComputingError> {
+        let mut closure = transitive_closure(
+            self.get_edge_adjacency_matrix_ref(edge_type)?
+                .as_sparse_matrix(),
+        )?;
+        if is_reflexive {
+            closure = union(&closure, &self.identity_matrix_of_vertices()?)?;
+        }
+
+        let closure_edge_type_index = self.add_new_edge_type(closure_edge_type.to_owned())?;
+        self.add_edges_from_matrix(closure_edge_type_index, &closure)?;
+        Ok(closure_edge_type_index)
+    }
+
+    fn identity_matrix_of_vertices(&self) -> Result<SparseMatrix<bool>, GraphComputingError> {
+        let vertex_indices = self.valid_vertex_indices()?;
+        let vertex_capacity = self.vertex_capacity()?;
+        from_coordinates(
+            self.graphblas_context_ref(),
+            &Size::new(vertex_capacity, vertex_capacity),
+            vertex_indices.clone(),
+            vertex_indices,
+        )
+    }
+}
+
+// Repeated squaring: after k iterations, the closure holds all paths of length up to 2^k.
+// The number of matrix multiplications is therefore logarithmic in the length of the longest shortest path.
+fn transitive_closure(
+    adjacency_matrix: &SparseMatrix<bool>,
+) -> Result<SparseMatrix<bool>, GraphComputingError> {
+    let mut closure = adjacency_matrix.clone();
+    loop {
+        let new_paths = difference(&multiply(&closure, &closure)?, &closure)?;
+        if is_empty(&new_paths)? {
+            return Ok(closure);
+        }
+        closure = union(&closure, &new_paths)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::{GraphComputingErrorType, LogicErrorType};
+    use crate::operations::add_edge::AddEdge;
+    use crate::operations::read_edge::ReadEdge;
+    use crate::tests::{edge, graph_with_vertices};
+
+    // a -> b -> c -> d, e <-> f, g
+    fn graph_with_chain_and_cycle() -> Graph {
+        let mut graph = graph_with_vertices(&["a", "b", "c", "d", "e", "f", "g"]);
+        graph
+            .add_edge_and_edge_type_using_keys(edge("a", "is_a", "b"))
+            .unwrap();
+        for (from_vertex, to_vertex) in [("b", "c"), ("c", "d"), ("e", "f"), ("f", "e")] {
+            graph
+                .add_edge_using_keys(edge(from_vertex, "is_a", to_vertex))
+                .unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn add_transitive_closure() {
+        let mut graph = graph_with_chain_and_cycle();
+        graph.transitive_closure("is_a", "is_a_transitive").unwrap();
+
+        let closure = graph
+            .get_edge_adjacency_matrix_ref("is_a_transitive")
+            .unwrap();
+        assert_eq!(closure.get_edge_coordinates().unwrap().len(), 10);
+        for (from_vertex, to_vertex) in [("a", "d"), ("b", "d"), ("e", "e"), ("f", "f")] {
+            assert!(graph
+                .is_key_defined_edge_in_graph(&edge(from_vertex, "is_a_transitive", to_vertex))
+                .unwrap());
+        }
+        assert!(!graph
+            .is_key_defined_edge_in_graph(&edge("a", "is_a_transitive", "a"))
+            .unwrap());
+        assert_eq!(
+            graph
+                .get_edge_adjacency_matrix_ref("is_a")
+                .unwrap()
+                .get_edge_coordinates()
+                .unwrap()
+                .len(),
+            5
+        );
+    }
+
+    #[test]
+    fn add_reflexive_transitive_closure() {
+        let mut graph = graph_with_chain_and_cycle();
+        graph
+            .reflexive_transitive_closure("is_a", "is_a_or_self")
+            .unwrap();
+
+        let closure = graph.get_edge_adjacency_matrix_ref("is_a_or_self").unwrap();
+        assert_eq!(closure.get_edge_coordinates().unwrap().len(), 15);
+        assert!(graph
+            .is_key_defined_edge_in_graph(&edge("g", "is_a_or_self", "g"))
+            .unwrap());
+    }
+
+    #[test]
+    fn closure_edge_type_must_be_new() {
+        let mut graph = graph_with_chain_and_cycle();
+        assert_eq!(
+            graph
+                .transitive_closure("is_a", "is_a")
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::LogicErrorType(LogicErrorType::EdgeTypeAlreadyExists)
+        );
+    }
+}
@@ -2,19 +2,50 @@ use crate::error::{GraphComputingError, SystemError, SystemErrorType, UserError,
 
 use super::add_vertex::AddVertex;
 use crate::graph::graph::Graph;
-use crate::graph::vertex::{Vertex, VertexIndex, VertexValue};
+use crate::graph::vertex::{
+    PropertyName, PropertyNameRef, Vertex, VertexIndex, VertexKeyRef, VertexValue,
+};
+use crate::persistence::write_ahead_log::GraphMutation;
 
 pub trait UpdateVertex {
     fn update_or_add_vertex(
         &mut self,
         vertex_to_set: Vertex,
     ) -> Result<Option<VertexIndex>, GraphComputingError>; // REVIEW update vs set
+    /// Replaces the value and the properties of the vertex with the same key
     fn update_vertex(&mut self, vertex_to_update: Vertex) -> Result<(), GraphComputingError>;
+    /// Keeps the properties of the vertex
     fn update_vertex_value_by_index(
         &mut self,
         vertex_index: VertexIndex,
         vertex_value: VertexValue,
     ) -> Result<(), GraphComputingError>;
+
+    /// Adds or replaces a single property. Returns the replaced value, None if the vertex did not have the property.
+    fn update_vertex_property(
+        &mut self,
+        vertex_key: &VertexKeyRef,
+        property_name: PropertyName,
+        value: VertexValue,
+    ) -> Result<Option<VertexValue>, GraphComputingError>;
+    fn update_vertex_property_by_index(
+        &mut self,
+        vertex_index: VertexIndex,
+        property_name: PropertyName,
+        value: VertexValue,
+    ) -> Result<Option<VertexValue>, GraphComputingError>;
+
+    /// Returns the removed value, None if the vertex did not have the property.
+    fn remove_vertex_property(
+        &mut self,
+        vertex_key: &VertexKeyRef,
+        property_name: &PropertyNameRef,
+    ) -> Result<Option<VertexValue>, GraphComputingError>;
+    fn remove_vertex_property_by_index(
+        &mut self,
+        vertex_index: VertexIndex,
+        property_name: &PropertyNameRef,
+    ) -> Result<Option<VertexValue>, GraphComputingError>;
 }
 
 impl UpdateVertex for Graph {
@@ -22,13 +53,19 @@ impl UpdateVertex for Graph {
         &mut self,
         vertex_to_set: Vertex,
     ) -> Result<Option<VertexIndex>, GraphComputingError> {
+        self.check_write_ahead_log_is_writable()?;
         let vertex_index = self
-            .vertex_key_to_vertex_index_map_mut_ref()
+            .vertex_key_to_vertex_index_map_ref()
             .get(vertex_to_set.key_ref());
         match vertex_index {
             Some(&vertex_index) => {
+                self.check_vertex_value_against_schema(
+                    vertex_index.index(),
+                    vertex_to_set.value_ref(),
+                )?;
                 self.vertex_store_mut_ref()
-                    .update(vertex_index, vertex_to_set)?;
+                    .update(vertex_index, vertex_to_set.clone())?;
+                self.append_to_write_ahead_log(|_| Ok(GraphMutation::UpdateVertex(vertex_to_set)))?;
                 Ok(None)
             }
             None => Ok(Some(self.add_or_replace_vertex(vertex_to_set)?)),
@@ -37,13 +74,21 @@ impl UpdateVertex for Graph {
 
     // TODO: is there a use-case for returning the VertexIndex?
     fn update_vertex(&mut self, vertex_to_update: Vertex) -> Result<(), GraphComputingError> {
+        self.check_write_ahead_log_is_writable()?;
         let vertex_index = self
-            .vertex_key_to_vertex_index_map_mut_ref()
+            .vertex_key_to_vertex_index_map_ref()
             .get(vertex_to_update.key_ref());
         match vertex_index {
             Some(&vertex_index) => {
+                self.check_vertex_value_against_schema(
+                    vertex_index.index(),
+                    vertex_to_update.value_ref(),
+                )?;
                 self.vertex_store_mut_ref()
-                    .update(vertex_index, vertex_to_update)?;
+                    .update(vertex_index, vertex_to_update.clone())?;
+                self.append_to_write_ahead_log(|_| {
+                    Ok(GraphMutation::UpdateVertex(vertex_to_update))
+                })?;
                 Ok(())
             }
             None => Err(UserError::new(
@@ -63,6 +108,8 @@ impl UpdateVertex for Graph {
         vertex_index: VertexIndex,
         vertex_value: VertexValue,
     ) -> Result<(), GraphComputingError> {
+        self.check_write_ahead_log_is_writable()?;
+        self.check_vertex_value_against_schema(vertex_index.index(), &vertex_value)?;
         let vertex_zo_update = self.vertex_store_mut_ref().get_mut_ref(vertex_index);
 
         match vertex_zo_update {
@@ -77,7 +124,84 @@ impl UpdateVertex for Graph {
                 .into());
             }
         };
-        Ok(())
+        self.append_to_write_ahead_log(|graph| graph.describe_vertex_value_update(vertex_index))
+    }
+
+    fn update_vertex_property(
+        &mut self,
+        vertex_key: &VertexKeyRef,
+        property_name: PropertyName,
+        value: VertexValue,
+    ) -> Result<Option<VertexValue>, GraphComputingError> {
+        let vertex_index = VertexIndex::new(self.existing_vertex_index(vertex_key)?);
+        self.update_vertex_property_by_index(vertex_index, property_name, value)
+    }
+
+    fn update_vertex_property_by_index(
+        &mut self,
+        vertex_index: VertexIndex,
+        property_name: PropertyName,
+        value: VertexValue,
+    ) -> Result<Option<VertexValue>, GraphComputingError> {
+        self.check_write_ahead_log_is_writable()?;
+        let replaced_value = self
+            .vertex_store_mut_ref()
+            .get_mut_ref(vertex_index)?
+            .update_property(property_name.clone(), value.clone());
+        self.append_to_write_ahead_log(|_| {
+            Ok(GraphMutation::UpdateVertexProperty {
+                vertex_index: vertex_index.index(),
+                property_name,
+                value,
+            })
+        })?;
+        Ok(replaced_value)
+    }
+
+    fn remove_vertex_property(
+        &mut self,
+        vertex_key: &VertexKeyRef,
+        property_name: &PropertyNameRef,
+    ) -> Result<Option<VertexValue>, GraphComputingError> {
+        let vertex_index = VertexIndex::new(self.existing_vertex_index(vertex_key)?);
+        self.remove_vertex_property_by_index(vertex_index, property_name)
+    }
+
+    fn remove_vertex_property_by_index(
+        &mut self,
+        vertex_index: VertexIndex,
+        property_name: &PropertyNameRef,
+    ) -> Result<Option<VertexValue>, GraphComputingError> {
+        self.check_write_ahead_log_is_writable()?;
+        let removed_value = self
+            .vertex_store_mut_ref()
+            .get_mut_ref(vertex_index)?
+            .remove_property(property_name);
+        if removed_value.is_some() {
+            self.append_to_write_ahead_log(|_| {
+                Ok(GraphMutation::RemoveVertexProperty {
+                    vertex_index: vertex_index.index(),
+                    property_name: property_name.to_owned(),
+                })
+            })?;
+        }
+        Ok(removed_value)
+    }
+}
+
+impl Graph {
+    fn describe_vertex_value_update(
+        &self,
+        vertex_index: VertexIndex,
+    ) -> Result<GraphMutation, GraphComputingError> {
+        Ok(GraphMutation::UpdateVertexValue {
+            vertex_index: vertex_index.index(),
+            vertex_value: self
+                .vertex_store_ref()
+                .get_ref(vertex_index)?
+                .value_ref()
+                .clone(),
+        })
     }
 }
 
@@ -85,6 +209,7 @@ impl UpdateVertex for Graph {
 mod tests {
     use super::*;
 
+    use crate::error::GraphComputingErrorType;
     use crate::operations::read_vertex_value::ReadVertexValue;
 
     #[test]
@@ -165,4 +290,77 @@ mod tests {
             another_vertex_to_add.value()
         )
     }
+
+    #[test]
+    fn update_and_remove_vertex_property() {
+        let mut graph = Graph::new(5, 5).unwrap();
+        let vertex_key = String::from("REQ-1");
+        graph
+            .add_new_vertex(
+                Vertex::new(vertex_key.clone(), String::from("Braking distance").into())
+                    .with_property(String::from("status"), String::from("draft").into()),
+            )
+            .unwrap();
+
+        assert_eq!(
+            graph
+                .update_vertex_property(
+                    &vertex_key,
+                    String::from("status"),
+                    String::from("approved").into()
+                )
+                .unwrap(),
+            Some(String::from("draft").into())
+        );
+        assert_eq!(
+            graph
+                .update_vertex_property(&vertex_key, String::from("priority"), 1u8.into())
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            graph.vertex_property(&vertex_key, "status").unwrap(),
+            Some(&String::from("approved").into())
+        );
+
+        // Updating the value keeps the properties
+        let vertex_index = *graph
+            .vertex_key_to_vertex_index_map_ref()
+            .get(&vertex_key)
+            .unwrap();
+        graph
+            .update_vertex_value_by_index(vertex_index, String::from("Stopping distance").into())
+            .unwrap();
+        assert_eq!(
+            graph.vertex_property(&vertex_key, "priority").unwrap(),
+            Some(&1u8.into())
+        );
+
+        assert_eq!(
+            graph
+                .remove_vertex_property(&vertex_key, "priority")
+                .unwrap(),
+            Some(1u8.into())
+        );
+        assert_eq!(
+            graph
+                .remove_vertex_property(&vertex_key, "priority")
+                .unwrap(),
+            None
+        );
+
+        // Updating the vertex replaces its properties
+        graph
+            .update_vertex(Vertex::new(vertex_key.clone(), 0u8.into()))
+            .unwrap();
+        assert_eq!(graph.vertex_property(&vertex_key, "status").unwrap(), None);
+
+        assert_eq!(
+            graph
+                .update_vertex_property("REQ-2", String::from("status"), 0u8.into())
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::VertexKeyNotFound)
+        );
+    }
 }
@@ -0,0 +1,952 @@
+use std::collections::VecDeque;
+use std::io::{Read, Write};
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+
+use graphblas_sparse_linear_algebra::context::{
+    Context as GraphblasContext, Mode as GraphblasMode,
+};
+use graphblas_sparse_linear_algebra::operators::binary_operator::First;
+use graphblas_sparse_linear_algebra::value_types::sparse_matrix::{
+    FromMatrixElementList, MatrixElementList, Size, SparseMatrix,
+};
+use graphblas_sparse_linear_algebra::value_types::sparse_vector::{
+    FromVectorElementList, GetVectorElementList, SparseVector, VectorElementList,
+};
+use hashbrown::HashMap;
+
+use crate::error::{GraphComputingError, UserError, UserErrorType};
+use crate::graph::edge::adjacency_matrix::{AdjacencyMatrix, EdgeCoordinate};
+use crate::graph::edge::{
+    EdgeType, EdgeTypeIndex, EdgeValue, EdgeWeight, EdgeWeightType, IntegerEdgeWeight,
+};
+use crate::graph::graph::{ElementIndex, Graph};
+use crate::graph::indexed_data_store::data_store::IndexedDataStore;
+use crate::graph::indexed_data_store::index::{Index, IndexTrait};
+use crate::graph::schema::Schema;
+use crate::graph::vertex::{Vertex, VertexIndex, VertexKey, VertexLabel};
+
+use super::encoding::{corrupt_data_error, read_exact, Decode, Encode};
+
+static FIRST_BINARY_OPERATOR: Lazy<First<bool, bool, bool>> =
+    Lazy::new(First::<bool, bool, bool>::new);
+
+const SNAPSHOT_MAGIC_BYTES: &[u8; 8] = b"CAIRNKG\0";
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// Persists the complete state of a graph, including freed indices.
+/// Vertex and edge type indices are therefore stable across a save and load.
+pub trait BinarySnapshot {
+    fn save_snapshot(&self, writer: &mut impl Write) -> Result<(), GraphComputingError>;
+    fn load_snapshot(reader: impl Read) -> Result<Graph, GraphComputingError>;
+}
+
+impl BinarySnapshot for Graph {
+    fn save_snapshot(&self, writer: &mut impl Write) -> Result<(), GraphComputingError> {
+        writer.write_all(SNAPSHOT_MAGIC_BYTES)?;
+        SNAPSHOT_FORMAT_VERSION.encode(writer)?;
+
+        encode_indexed_data_store(self.vertex_store_ref(), writer, |vertex, writer| {
+            vertex.encode(writer)
+        })?;
+        // The maps are written in order of their keys, such that equal graphs result in equal snapshots
+        let mut vertex_keys_and_indices: Vec<(&VertexKey, &VertexIndex)> =
+            self.vertex_key_to_vertex_index_map_ref().iter().collect();
+        vertex_keys_and_indices.sort_unstable_by_key(|(vertex_key, _)| *vertex_key);
+        vertex_keys_and_indices.len().encode(writer)?;
+        for (vertex_key, vertex_index) in vertex_keys_and_indices {
+            vertex_key.encode(writer)?;
+            vertex_index.index_ref().encode(writer)?;
+        }
+
+        encode_indexed_data_store(
+            self.adjacency_matrices_ref(),
+            writer,
+            encode_adjacency_matrix,
+        )?;
+        let mut edge_types_and_indices: Vec<(&EdgeType, &EdgeTypeIndex)> =
+            self.edge_type_to_edge_type_index_map_ref().iter().collect();
+        edge_types_and_indices.sort_unstable_by_key(|(edge_type, _)| *edge_type);
+        edge_types_and_indices.len().encode(writer)?;
+        for (edge_type, edge_type_index) in edge_types_and_indices {
+            edge_type.encode(writer)?;
+            edge_type_index.index_ref().encode(writer)?;
+        }
+
+        let mut vertex_labels: Vec<&VertexLabel> = self.vertex_label_masks_ref().keys().collect();
+        vertex_labels.sort();
+        vertex_labels.len().encode(writer)?;
+        for vertex_label in vertex_labels {
+            vertex_label.encode(writer)?;
+            self.vertex_label_masks_ref()[vertex_label]
+                .get_element_list()?
+                .indices_ref()
+                .to_vec()
+                .encode(writer)?;
+        }
+        self.schema_ref().encode(writer)?;
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn load_snapshot(mut reader: impl Read) -> Result<Graph, GraphComputingError> {
+        let reader = &mut reader;
+
+        let mut magic_bytes = [0u8; 8];
+        read_exact(reader, &mut magic_bytes)?;
+        if &magic_bytes != SNAPSHOT_MAGIC_BYTES {
+            return Err(corrupt_data_error(String::from(
+                "The data does not start with a Cairn Knowledge Graph snapshot header",
+            )));
+        }
+        let format_version = u32::decode(reader)?;
+        if format_version != SNAPSHOT_FORMAT_VERSION {
+            return Err(UserError::new(
+                UserErrorType::UnsupportedSnapshotVersion,
+                format!(
+                    "Snapshot format version {} is not supported, expected version {}",
+                    format_version, SNAPSHOT_FORMAT_VERSION
+                ),
+                None,
+            )
+            .into());
+        }
+
+        let graphblas_context = GraphblasContext::init_ready(GraphblasMode::NonBlocking)?;
+
+        let vertex_store = decode_indexed_data_store(reader, &graphblas_context, |reader, _| {
+            Vertex::decode(reader)
+        })?;
+        let number_of_vertex_keys = usize::decode(reader)?;
+        let mut vertex_key_to_vertex_index_map: HashMap<VertexKey, VertexIndex> =
+            HashMap::default();
+        for _ in 0..number_of_vertex_keys {
+            let vertex_key = VertexKey::decode(reader)?;
+            let vertex_index = VertexIndex::new(ElementIndex::decode(reader)?);
+            vertex_key_to_vertex_index_map.insert(vertex_key, vertex_index);
+        }
+
+        let adjacency_matrices =
+            decode_indexed_data_store(reader, &graphblas_context, decode_adjacency_matrix)?;
+        let number_of_edge_types = usize::decode(reader)?;
+        let mut edge_type_to_edge_type_index_map: HashMap<EdgeType, EdgeTypeIndex> =
+            HashMap::default();
+        for _ in 0..number_of_edge_types {
+            let edge_type = EdgeType::decode(reader)?;
+            let edge_type_index = EdgeTypeIndex::new(ElementIndex::decode(reader)?);
+            edge_type_to_edge_type_index_map.insert(edge_type, edge_type_index);
+        }
+
+        let mut vertex_label_masks: HashMap<VertexLabel, SparseVector<bool>> = HashMap::default();
+        let vertex_capacity = vertex_store.get_capacity()?;
+        let number_of_vertex_labels = usize::decode(reader)?;
+        for _ in 0..number_of_vertex_labels {
+            let vertex_label = VertexLabel::decode(reader)?;
+            let vertex_indices = Vec::<ElementIndex>::decode(reader)?;
+            for vertex_index in vertex_indices.iter() {
+                if *vertex_index >= vertex_capacity
+                    || !vertex_store.is_valid_index(&VertexIndex::new(*vertex_index))?
+                {
+                    return Err(corrupt_data_error(format!(
+                        "Vertex label {} is assigned to vertex index {}, which is not in use",
+                        vertex_label, vertex_index
+                    )));
+                }
+            }
+            let mask_values = vec![true; vertex_indices.len()];
+            let vertex_label_mask = SparseVector::from_element_list(
+                &graphblas_context,
+                &vertex_capacity,
+                &VectorElementList::from_vectors(vertex_indices, mask_values)?,
+                &*FIRST_BINARY_OPERATOR,
+            )?;
+            vertex_label_masks.insert(vertex_label, vertex_label_mask);
+        }
+
+        let schema = Schema::decode(reader)?;
+
+        check_vertex_references(
+            &vertex_store,
+            &vertex_key_to_vertex_index_map,
+            &adjacency_matrices,
+        )?;
+        for (edge_type, edge_type_index) in edge_type_to_edge_type_index_map.iter() {
+            if !is_valid_index(&adjacency_matrices, edge_type_index)? {
+                return Err(corrupt_data_error(format!(
+                    "Edge type {} refers to edge type index {}, which is not in use",
+                    edge_type,
+                    edge_type_index.index_ref()
+                )));
+            }
+        }
+
+        Ok(Graph::from_parts(
+            graphblas_context,
+            vertex_store,
+            vertex_key_to_vertex_index_map,
+            adjacency_matrices,
+            edge_type_to_edge_type_index_map,
+            vertex_label_masks,
+            schema,
+        ))
+    }
+}
+
+// A loaded graph must not refer to a vertex index that is not in use, or that is out of bounds of the adjacency matrices
+fn check_vertex_references(
+    vertex_store: &IndexedDataStore<Vertex>,
+    vertex_key_to_vertex_index_map: &HashMap<VertexKey, VertexIndex>,
+    adjacency_matrices: &IndexedDataStore<AdjacencyMatrix>,
+) -> Result<(), GraphComputingError> {
+    for (vertex_key, vertex_index) in vertex_key_to_vertex_index_map.iter() {
+        if !is_valid_index(vertex_store, vertex_index)? {
+            return Err(corrupt_data_error(format!(
+                "Vertex {} refers to vertex index {}, which is not in use",
+                vertex_key,
+                vertex_index.index_ref()
+            )));
+        }
+    }
+
+    let vertex_capacity = vertex_store.get_capacity()?;
+    for adjacency_matrix in adjacency_matrices.data_ref().iter() {
+        if adjacency_matrix.get_vertex_capacity()? != vertex_capacity {
+            return Err(corrupt_data_error(format!(
+                "The adjacency matrix of edge type {} has size {}, which differs from the vertex capacity {}",
+                adjacency_matrix.edge_type_ref(),
+                adjacency_matrix.get_vertex_capacity()?,
+                vertex_capacity
+            )));
+        }
+        for coordinate in adjacency_matrix
+            .get_edge_coordinates()?
+            .into_iter()
+            .chain(adjacency_matrix.get_inferred_edge_coordinates()?)
+        {
+            for vertex_index in [coordinate.row_index(), coordinate.column_index()] {
+                if !vertex_store.is_valid_index(&VertexIndex::new(vertex_index))? {
+                    return Err(corrupt_data_error(format!(
+                        "Edge ({}, {}) of type {} refers to vertex index {}, which is not in use",
+                        coordinate.row_index(),
+                        coordinate.column_index(),
+                        adjacency_matrix.edge_type_ref(),
+                        vertex_index
+                    )));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn is_valid_index<T: Clone + Send + Sync, I: IndexTrait>(
+    store: &IndexedDataStore<T>,
+    index: &I,
+) -> Result<bool, GraphComputingError> {
+    Ok(*index.index_ref() < store.get_capacity()? && store.is_valid_index(index)?)
+}
+
+fn encode_indexed_data_store<T: Clone + Send + Sync, W: Write>(
+    store: &IndexedDataStore<T>,
+    writer: &mut W,
+    encode_element: impl Fn(&T, &mut W) -> Result<(), GraphComputingError>,
+) -> Result<(), GraphComputingError> {
+    // The capacity is stored because it determines the vertex capacity, and thereby the size of the adjacency matrices
+    store.get_capacity()?.encode(writer)?;
+    store.data_ref().len().encode(writer)?;
+    for element in store.data_ref().iter() {
+        encode_element(element, writer)?;
+    }
+    store.indices_available_for_reuse_ref().encode(writer)?;
+
+    let mask_with_valid_indices = store.mask_with_valid_indices_ref();
+    mask_with_valid_indices.length()?.encode(writer)?;
+    mask_with_valid_indices
+        .get_element_list()?
+        .indices_ref()
+        .to_vec()
+        .encode(writer)?;
+    Ok(())
+}
+
+fn decode_indexed_data_store<T: Clone + Send + Sync, R: Read>(
+    reader: &mut R,
+    graphblas_context: &Arc<GraphblasContext>,
+    decode_element: impl Fn(&mut R, &Arc<GraphblasContext>) -> Result<T, GraphComputingError>,
+) -> Result<IndexedDataStore<T>, GraphComputingError> {
+    let capacity = usize::decode(reader)?;
+    let number_of_elements = usize::decode(reader)?;
+    if number_of_elements > capacity {
+        return Err(corrupt_data_error(format!(
+            "Number of stored elements {} exceeds the stored capacity {}",
+            number_of_elements, capacity
+        )));
+    }
+    // The capacity is not trusted for pre-allocation, the data grows with the elements that are actually read
+    let mut data = Vec::new();
+    for _ in 0..number_of_elements {
+        data.push(decode_element(reader, graphblas_context)?);
+    }
+    let indices_available_for_reuse = VecDeque::<Index>::decode(reader)?;
+
+    let mask_length = usize::decode(reader)?;
+    if mask_length != capacity {
+        return Err(corrupt_data_error(format!(
+            "Length {} of the mask with valid indices differs from the stored capacity {}",
+            mask_length, capacity
+        )));
+    }
+    let valid_indices = Vec::<Index>::decode(reader)?;
+    for index in valid_indices
+        .iter()
+        .chain(indices_available_for_reuse.iter())
+    {
+        if *index >= number_of_elements {
+            return Err(corrupt_data_error(format!(
+                "Index {} is out of bounds for {} stored elements",
+                index, number_of_elements
+            )));
+        }
+    }
+    let mask_values = vec![true; valid_indices.len()];
+    let mask_with_valid_indices = SparseVector::from_element_list(
+        graphblas_context,
+        &mask_length,
+        &VectorElementList::from_vectors(valid_indices, mask_values)?,
+        &*FIRST_BINARY_OPERATOR,
+    )?;
+    if data.try_reserve_exact(capacity - data.len()).is_err() {
+        return Err(corrupt_data_error(format!(
+            "Cannot allocate the stored capacity {}",
+            capacity
+        )));
+    }
+
+    Ok(IndexedDataStore::from_parts(
+        data,
+        indices_available_for_reuse,
+        mask_with_valid_indices,
+        graphblas_context.clone(),
+    ))
+}
+
+fn encode_adjacency_matrix<W: Write>(
+    adjacency_matrix: &AdjacencyMatrix,
+    writer: &mut W,
+) -> Result<(), GraphComputingError> {
+    adjacency_matrix.edge_type_ref().to_owned().encode(writer)?;
+    adjacency_matrix.get_vertex_capacity()?.encode(writer)?;
+    adjacency_matrix.edge_weight_type().encode(writer)?;
+    encode_edge_coordinates(&adjacency_matrix.get_edge_coordinates()?, writer)?;
+    encode_edge_coordinates(&adjacency_matrix.get_inferred_edge_coordinates()?, writer)?;
+    match adjacency_matrix.edge_weight_type() {
+        EdgeWeightType::Integer64Bit => {
+            encode_edge_weights(&adjacency_matrix.get_stored_integer_edge_weights()?, writer)?
+        }
+        // Boolean edge types store no weights
+        _ => encode_edge_weights(&adjacency_matrix.get_stored_edge_weights()?, writer)?,
+    }
+    encode_edge_values(&adjacency_matrix.get_edge_values(), writer)
+}
+
+fn encode_edge_weights<T: Encode, W: Write>(
+    edge_weights: &[(EdgeCoordinate, T)],
+    writer: &mut W,
+) -> Result<(), GraphComputingError> {
+    edge_weights.len().encode(writer)?;
+    for (coordinate, weight) in edge_weights.iter() {
+        coordinate.row_index().encode(writer)?;
+        coordinate.column_index().encode(writer)?;
+        weight.encode(writer)?;
+    }
+    Ok(())
+}
+
+fn encode_edge_values<W: Write>(
+    edge_values: &[(EdgeCoordinate, EdgeValue)],
+    writer: &mut W,
+) -> Result<(), GraphComputingError> {
+    edge_values.len().encode(writer)?;
+    for (coordinate, value) in edge_values.iter() {
+        coordinate.row_index().encode(writer)?;
+        coordinate.column_index().encode(writer)?;
+        value.encode(writer)?;
+    }
+    Ok(())
+}
+
+fn encode_edge_coordinates<W: Write>(
+    edge_coordinates: &[EdgeCoordinate],
+    writer: &mut W,
+) -> Result<(), GraphComputingError> {
+    edge_coordinates.len().encode(writer)?;
+    for coordinate in edge_coordinates.iter() {
+        coordinate.row_index().encode(writer)?;
+        coordinate.column_index().encode(writer)?;
+    }
+    Ok(())
+}
+
+fn decode_adjacency_matrix<R: Read>(
+    reader: &mut R,
+    graphblas_context: &Arc<GraphblasContext>,
+) -> Result<AdjacencyMatrix, GraphComputingError> {
+    let edge_type = EdgeType::decode(reader)?;
+    let vertex_capacity = usize::decode(reader)?;
+    let edge_weight_type = EdgeWeightType::decode(reader)?;
+
+    let mut adjacency_matrix = AdjacencyMatrix::new(
+        graphblas_context,
+        edge_type.clone(),
+        vertex_capacity,
+        edge_weight_type,
+    )?;
+    *adjacency_matrix.as_mut_sparse_matrix() =
+        decode_edges(reader, graphblas_context, &edge_type, vertex_capacity)?;
+    *adjacency_matrix.as_mut_inferred_edges_sparse_matrix() =
+        decode_edges(reader, graphblas_context, &edge_type, vertex_capacity)?;
+    match edge_weight_type {
+        EdgeWeightType::Integer64Bit => decode_edge_weights(
+            reader,
+            &mut adjacency_matrix,
+            vertex_capacity,
+            |adjacency_matrix, coordinate, weight: IntegerEdgeWeight| {
+                adjacency_matrix.set_integer_edge_weight(coordinate, weight)
+            },
+        )?,
+        _ => decode_edge_weights(
+            reader,
+            &mut adjacency_matrix,
+            vertex_capacity,
+            |adjacency_matrix, coordinate, weight: EdgeWeight| {
+                adjacency_matrix.set_edge_weight(coordinate, weight)
+            },
+        )?,
+    }
+    decode_edge_values(reader, &mut adjacency_matrix, vertex_capacity)?;
+    Ok(adjacency_matrix)
+}
+
+fn decode_edges<R: Read>(
+    reader: &mut R,
+    graphblas_context: &Arc<GraphblasContext>,
+    edge_type: &EdgeType,
+    vertex_capacity: usize,
+) -> Result<SparseMatrix<bool>, GraphComputingError> {
+    let number_of_edges = usize::decode(reader)?;
+    let mut from_vertex_indices = Vec::new();
+    let mut to_vertex_indices = Vec::new();
+    for _ in 0..number_of_edges {
+        let from_vertex_index = usize::decode(reader)?;
+        let to_vertex_index = usize::decode(reader)?;
+        if from_vertex_index >= vertex_capacity || to_vertex_index >= vertex_capacity {
+            return Err(corrupt_data_error(format!(
+                "Edge ({}, {}) of type {} is out of bounds for vertex capacity {}",
+                from_vertex_index, to_vertex_index, edge_type, vertex_capacity
+            )));
+        }
+        from_vertex_indices.push(from_vertex_index);
+        to_vertex_indices.push(to_vertex_index);
+    }
+    let edge_values = vec![true; from_vertex_indices.len()];
+
+    Ok(SparseMatrix::from_element_list(
+        graphblas_context,
+        &Size::new(vertex_capacity, vertex_capacity),
+        &MatrixElementList::from_vectors(from_vertex_indices, to_vertex_indices, edge_values)?,
+        &*FIRST_BINARY_OPERATOR,
+    )?)
+}
+
+// Edge types of weight type Boolean do not store weights
+fn decode_edge_weights<T: Decode, R: Read>(
+    reader: &mut R,
+    adjacency_matrix: &mut AdjacencyMatrix,
+    vertex_capacity: usize,
+    set_edge_weight: impl Fn(
+        &mut AdjacencyMatrix,
+        &EdgeCoordinate,
+        T,
+    ) -> Result<(), GraphComputingError>,
+) -> Result<(), GraphComputingError> {
+    let number_of_edge_weights = usize::decode(reader)?;
+    for _ in 0..number_of_edge_weights {
+        let coordinate = EdgeCoordinate::new(usize::decode(reader)?, usize::decode(reader)?);
+        let weight = T::decode(reader)?;
+        if coordinate.row_index() >= vertex_capacity
+            || coordinate.column_index() >= vertex_capacity
+            || !adjacency_matrix.is_edge(&coordinate)?
+            || adjacency_matrix.edge_weight_type() == EdgeWeightType::Boolean
+        {
+            return Err(corrupt_data_error(format!(
+                "Weight of edge ({}, {}) of type {} does not belong to an edge",
+                coordinate.row_index(),
+                coordinate.column_index(),
+                adjacency_matrix.edge_type_ref()
+            )));
+        }
+        set_edge_weight(adjacency_matrix, &coordinate, weight)?;
+    }
+    Ok(())
+}
+
+fn decode_edge_values<R: Read>(
+    reader: &mut R,
+    adjacency_matrix: &mut AdjacencyMatrix,
+    vertex_capacity: usize,
+) -> Result<(), GraphComputingError> {
+    let number_of_edge_values = usize::decode(reader)?;
+    for _ in 0..number_of_edge_values {
+        let coordinate = EdgeCoordinate::new(usize::decode(reader)?, usize::decode(reader)?);
+        let value = EdgeValue::decode(reader)?;
+        if coordinate.row_index() >= vertex_capacity
+            || coordinate.column_index() >= vertex_capacity
+            || !adjacency_matrix.is_edge(&coordinate)?
+        {
+            return Err(corrupt_data_error(format!(
+                "Value of edge ({}, {}) of type {} does not belong to an edge",
+                coordinate.row_index(),
+                coordinate.column_index(),
+                adjacency_matrix.edge_type_ref()
+            )));
+        }
+        adjacency_matrix.set_edge_value(&coordinate, value);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::GraphComputingErrorType;
+    use crate::graph::edge::DirectedEdgeDefinedByKeys;
+    use crate::graph::graph::GraphTrait;
+    use crate::graph::schema::EdgeTypeConstraint;
+    use crate::graph::vertex::{VertexValue, VertexValueType};
+    use crate::operations::add_edge::AddEdge;
+    use crate::operations::add_edge_type::AddEdgeType;
+    use crate::operations::add_vertex::AddVertex;
+    use crate::operations::define_schema::DefineSchema;
+    use crate::operations::delete_vertex::DeleteVertex;
+    use crate::operations::label_vertex::LabelVertex;
+    use crate::operations::read_edge::ReadEdge;
+    use crate::operations::read_vertex_value::ReadVertexValue;
+    use crate::operations::select_vertex::SelectVertex;
+    use crate::operations::set_edge_value::SetEdgeValue;
+    use crate::operations::set_edge_weight::SetEdgeWeight;
+    use crate::operations::update_vertex::UpdateVertex;
+    use crate::tests::standard_graph_for_testing::standard_graph_for_testing;
+
+    fn save_and_load(graph: &Graph) -> Graph {
+        let mut snapshot = Vec::new();
+        graph.save_snapshot(&mut snapshot).unwrap();
+        Graph::load_snapshot(snapshot.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn round_trip_standard_graph() {
+        let graph = standard_graph_for_testing();
+        let loaded_graph = save_and_load(&graph);
+
+        assert_eq!(
+            loaded_graph.number_of_vertices().unwrap(),
+            graph.number_of_vertices().unwrap()
+        );
+        assert_eq!(
+            loaded_graph.number_of_edge_types().unwrap(),
+            graph.number_of_edge_types().unwrap()
+        );
+        assert_eq!(
+            loaded_graph.vertex_capacity().unwrap(),
+            graph.vertex_capacity().unwrap()
+        );
+        assert_eq!(
+            loaded_graph.vertex_value(&String::from("1.1")).unwrap(),
+            &VertexValue::FloatingPoint32Bit(1.1)
+        );
+        assert_eq!(
+            loaded_graph.vertex_value(&String::from("string")).unwrap(),
+            graph.vertex_value(&String::from("string")).unwrap()
+        );
+        assert!(loaded_graph
+            .is_key_defined_edge_in_graph(&DirectedEdgeDefinedByKeys::new(
+                String::from("-1.1"),
+                String::from("smaller_than"),
+                String::from("-1"),
+            ))
+            .unwrap());
+        assert!(!loaded_graph
+            .is_key_defined_edge_in_graph(&DirectedEdgeDefinedByKeys::new(
+                String::from("-1"),
+                String::from("smaller_than"),
+                String::from("-1.1"),
+            ))
+            .unwrap());
+
+        for (vertex_key, vertex_index) in graph.vertex_key_to_vertex_index_map_ref().iter() {
+            assert_eq!(
+                loaded_graph
+                    .vertex_key_to_vertex_index_map_ref()
+                    .get(vertex_key),
+                Some(vertex_index)
+            );
+        }
+        for (edge_type, edge_type_index) in graph.edge_type_to_edge_type_index_map_ref().iter() {
+            assert_eq!(
+                loaded_graph
+                    .edge_type_to_edge_type_index_map_ref()
+                    .get(edge_type),
+                Some(edge_type_index)
+            );
+            assert_eq!(
+                loaded_graph
+                    .get_edge_adjacency_matrix_ref(edge_type)
+                    .unwrap()
+                    .get_edge_coordinates()
+                    .unwrap(),
+                graph
+                    .get_edge_adjacency_matrix_ref(edge_type)
+                    .unwrap()
+                    .get_edge_coordinates()
+                    .unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn freed_indices_are_reused_after_load() {
+        let mut graph = standard_graph_for_testing();
+        graph
+            .delete_vertex_and_connected_edges_by_key(String::from("1.2"))
+            .unwrap();
+        graph
+            .delete_vertex_and_connected_edges_by_key(String::from("2"))
+            .unwrap();
+
+        let mut loaded_graph = save_and_load(&graph);
+
+        let vertex = Vertex::new(String::from("3"), 3u8.into());
+        let index = graph.add_new_vertex(vertex.clone()).unwrap();
+        let index_after_load = loaded_graph.add_new_vertex(vertex).unwrap();
+        assert_eq!(index, index_after_load);
+
+        let another_vertex = Vertex::new(String::from("4"), 4u8.into());
+        let another_index = graph.add_new_vertex(another_vertex.clone()).unwrap();
+        let another_index_after_load = loaded_graph.add_new_vertex(another_vertex).unwrap();
+        assert_eq!(another_index, another_index_after_load);
+    }
+
+    #[test]
+    fn loaded_graph_accepts_new_edges() {
+        let graph = standard_graph_for_testing();
+        let mut loaded_graph = save_and_load(&graph);
+
+        let edge = DirectedEdgeDefinedByKeys::new(
+            String::from("2"),
+            String::from("larger_than"),
+            String::from("-1"),
+        );
+        loaded_graph.add_edge_using_keys(edge.clone()).unwrap();
+        assert!(loaded_graph.is_key_defined_edge_in_graph(&edge).unwrap());
+    }
+
+    #[test]
+    fn round_trip_edge_weights() {
+        let mut graph = standard_graph_for_testing();
+        let weighted_edge = DirectedEdgeDefinedByKeys::new(
+            String::from("-1.1"),
+            String::from("distance"),
+            String::from("-1"),
+        );
+        graph
+            .add_new_edge_type_with_weight_type(
+                String::from("distance"),
+                EdgeWeightType::FloatingPoint64Bit,
+            )
+            .unwrap();
+        graph.add_edge_using_keys(weighted_edge.clone()).unwrap();
+        graph
+            .set_edge_weight_using_keys(&weighted_edge, -0.5)
+            .unwrap();
+        let loaded_graph = save_and_load(&graph);
+
+        assert_eq!(
+            loaded_graph
+                .edge_weight_defined_by_keys(&weighted_edge)
+                .unwrap(),
+            -0.5
+        );
+        assert_eq!(
+            loaded_graph
+                .get_edge_adjacency_matrix_ref("distance")
+                .unwrap()
+                .get_stored_edge_weights()
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn round_trip_edge_weight_types() {
+        let mut graph = standard_graph_for_testing();
+        let counted_edge = DirectedEdgeDefinedByKeys::new(
+            String::from("-1.1"),
+            String::from("occurrences"),
+            String::from("-1"),
+        );
+        graph
+            .add_new_edge_type_with_weight_type(
+                String::from("occurrences"),
+                EdgeWeightType::Integer64Bit,
+            )
+            .unwrap();
+        graph
+            .add_new_edge_type_with_weight_type(String::from("blocks"), EdgeWeightType::Boolean)
+            .unwrap();
+        graph.add_edge_using_keys(counted_edge.clone()).unwrap();
+        graph
+            .set_integer_edge_weight_using_keys(&counted_edge, i64::MIN)
+            .unwrap();
+        let loaded_graph = save_and_load(&graph);
+
+        assert_eq!(
+            loaded_graph
+                .integer_edge_weight_defined_by_keys(&counted_edge)
+                .unwrap(),
+            i64::MIN
+        );
+        assert_eq!(
+            loaded_graph.edge_weight_type("blocks").unwrap(),
+            EdgeWeightType::Boolean
+        );
+        assert_eq!(
+            loaded_graph.edge_weight_type("smaller_than").unwrap(),
+            EdgeWeightType::Boolean
+        );
+    }
+
+    #[test]
+    fn round_trip_vertex_properties() {
+        let mut graph = standard_graph_for_testing();
+        graph
+            .update_vertex_property("1", String::from("parity"), String::from("odd").into())
+            .unwrap();
+        let loaded_graph = save_and_load(&graph);
+
+        assert_eq!(
+            loaded_graph
+                .vertex_property(&String::from("1"), "parity")
+                .unwrap(),
+            Some(&VertexValue::String(String::from("odd")))
+        );
+        assert_eq!(
+            loaded_graph
+                .vertex_property(&String::from("2"), "parity")
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn round_trip_vertex_labels() {
+        let mut graph = standard_graph_for_testing();
+        for vertex_key in ["1", "2", "-1"] {
+            graph
+                .add_vertex_label(vertex_key, String::from("integer"))
+                .unwrap();
+        }
+        graph.add_vertex_label("2", String::from("even")).unwrap();
+        graph
+            .delete_vertex_and_connected_edges_by_key(String::from("-1"))
+            .unwrap();
+        let loaded_graph = save_and_load(&graph);
+
+        assert_eq!(
+            loaded_graph.vertex_labels("2").unwrap(),
+            vec!["even", "integer"]
+        );
+        assert_eq!(
+            loaded_graph
+                .select_vertices_with_label("integer")
+                .unwrap()
+                .vertex_keys_ref()
+                .unwrap(),
+            vec!["1", "2"]
+        );
+    }
+
+    #[test]
+    fn round_trip_schema() {
+        let mut graph = standard_graph_for_testing();
+        let schema = Schema::new()
+            .with_edge_type_constraint("sign", EdgeTypeConstraint::new().functional())
+            .with_vertex_value_type("integer", VertexValueType::Integer32Bit);
+        graph.set_schema(schema.clone()).unwrap();
+        let loaded_graph = save_and_load(&graph);
+
+        assert_eq!(loaded_graph.schema(), &schema);
+        assert!(loaded_graph.validate_schema().unwrap().is_empty());
+    }
+
+    #[test]
+    fn round_trip_edge_values() {
+        let mut graph = standard_graph_for_testing();
+        let edge_with_value = DirectedEdgeDefinedByKeys::new(
+            String::from("-1.1"),
+            String::from("smaller_than"),
+            String::from("-1"),
+        );
+        graph
+            .set_edge_value_using_keys(&edge_with_value, VertexValue::Integer32Bit(-7))
+            .unwrap();
+        let loaded_graph = save_and_load(&graph);
+
+        assert_eq!(
+            loaded_graph
+                .edge_value_defined_by_keys(&edge_with_value)
+                .unwrap(),
+            Some(&VertexValue::Integer32Bit(-7))
+        );
+        assert_eq!(
+            loaded_graph
+                .get_edge_adjacency_matrix_ref("smaller_than")
+                .unwrap()
+                .get_edge_values()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn reject_unsupported_format_version() {
+        let graph = Graph::new(5, 5).unwrap();
+        let mut snapshot = Vec::new();
+        graph.save_snapshot(&mut snapshot).unwrap();
+        snapshot[SNAPSHOT_MAGIC_BYTES.len()..SNAPSHOT_MAGIC_BYTES.len() + 4]
+            .copy_from_slice(&0u32.to_le_bytes());
+
+        let error = Graph::load_snapshot(snapshot.as_slice()).unwrap_err();
+        assert_eq!(
+            error.error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::UnsupportedSnapshotVersion)
+        );
+    }
+
+    #[test]
+    fn reject_capacity_that_differs_from_mask_length() {
+        let graph = Graph::new(5, 5).unwrap();
+        let mut snapshot = Vec::new();
+        graph.save_snapshot(&mut snapshot).unwrap();
+        // The capacity of the vertex store directly follows the header
+        let capacity_offset = SNAPSHOT_MAGIC_BYTES.len() + 4;
+        snapshot[capacity_offset..capacity_offset + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+
+        let error = Graph::load_snapshot(snapshot.as_slice()).unwrap_err();
+        assert_eq!(
+            error.error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::CorruptData)
+        );
+    }
+
+    fn assert_corrupt_snapshot(graph: &Graph) {
+        let mut snapshot = Vec::new();
+        graph.save_snapshot(&mut snapshot).unwrap();
+        assert_eq!(
+            Graph::load_snapshot(snapshot.as_slice())
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::CorruptData)
+        );
+    }
+
+    #[test]
+    fn reject_vertex_key_of_unused_vertex_index() {
+        let mut graph = standard_graph_for_testing();
+        let vertex_index = graph.existing_vertex_index("1").unwrap();
+        graph
+            .vertex_store_mut_ref()
+            .free(VertexIndex::new(vertex_index))
+            .unwrap();
+        assert_corrupt_snapshot(&graph);
+    }
+
+    #[test]
+    fn reject_edge_type_of_unused_edge_type_index() {
+        let mut graph = standard_graph_for_testing();
+        let edge_type_index = graph.edge_type_to_edge_type_index_map_ref()["sign"];
+        graph
+            .adjacency_matrices_mut_ref()
+            .free(edge_type_index)
+            .unwrap();
+        assert_corrupt_snapshot(&graph);
+    }
+
+    #[test]
+    fn reject_adjacency_matrix_of_other_size() {
+        let mut graph = standard_graph_for_testing();
+        let edge_type_index = graph.edge_type_to_edge_type_index_map_ref()["sign"];
+        let vertex_capacity = graph.vertex_capacity().unwrap();
+        graph
+            .adjacency_matrices_mut_ref()
+            .get_mut_ref(edge_type_index)
+            .unwrap()
+            .resize(vertex_capacity + 1)
+            .unwrap();
+        assert_corrupt_snapshot(&graph);
+    }
+
+    #[test]
+    fn reject_edge_to_unused_vertex_index() {
+        let mut graph = standard_graph_for_testing();
+        let vertex_index = graph.existing_vertex_index("-1").unwrap();
+        graph
+            .vertex_key_to_vertex_index_map_mut_ref()
+            .remove("-1")
+            .unwrap();
+        graph
+            .vertex_store_mut_ref()
+            .free(VertexIndex::new(vertex_index))
+            .unwrap();
+        assert_corrupt_snapshot(&graph);
+    }
+
+    #[test]
+    fn reject_truncated_snapshot() {
+        let graph = standard_graph_for_testing();
+        let mut snapshot = Vec::new();
+        graph.save_snapshot(&mut snapshot).unwrap();
+        snapshot.truncate(snapshot.len() / 2);
+
+        assert_eq!(
+            Graph::load_snapshot(snapshot.as_slice())
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::CorruptData)
+        );
+    }
+
+    #[test]
+    fn equal_graphs_result_in_equal_snapshots() {
+        let graph = standard_graph_for_testing();
+        let mut snapshot = Vec::new();
+        graph.save_snapshot(&mut snapshot).unwrap();
+        let mut snapshot_of_loaded_graph = Vec::new();
+        save_and_load(&graph)
+            .save_snapshot(&mut snapshot_of_loaded_graph)
+            .unwrap();
+        assert_eq!(snapshot, snapshot_of_loaded_graph);
+    }
+
+    #[test]
+    fn reject_data_without_header() {
+        let error = Graph::load_snapshot(&b"not a snapshot"[..]).unwrap_err();
+        assert_eq!(
+            error.error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::CorruptData)
+        );
+    }
+}
@@ -0,0 +1,513 @@
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::io::{ErrorKind, Read, Write};
+
+use crate::error::{GraphComputingError, UserError, UserErrorType};
+use crate::graph::edge::EdgeWeightType;
+use crate::graph::schema::{EdgeTypeConstraint, Schema};
+use crate::graph::vertex::{Vertex, VertexValue, VertexValueType};
+
+// All values are encoded little-endian. Lengths and indices are encoded as u64,
+// such that encoded data is portable between 32-bit and 64-bit platforms.
+
+pub(crate) trait Encode {
+    fn encode(&self, writer: &mut impl Write) -> Result<(), GraphComputingError>;
+}
+
+pub(crate) trait Decode: Sized {
+    fn decode(reader: &mut impl Read) -> Result<Self, GraphComputingError>;
+}
+
+pub(crate) fn corrupt_data_error(explanation: String) -> GraphComputingError {
+    UserError::new(UserErrorType::CorruptData, explanation, None).into()
+}
+
+fn truncated_data_error() -> GraphComputingError {
+    corrupt_data_error(String::from("The data ends within an encoded value"))
+}
+
+// Data that ends within a value is truncated, which is reported as CorruptData rather than as an I/O error
+pub(crate) fn read_exact(
+    reader: &mut impl Read,
+    bytes: &mut [u8],
+) -> Result<(), GraphComputingError> {
+    match reader.read_exact(bytes) {
+        Err(error) if error.kind() == ErrorKind::UnexpectedEof => Err(truncated_data_error()),
+        result => Ok(result?),
+    }
+}
+
+macro_rules! implement_encoding_for_primitive {
+    ($value_type:ty) => {
+        impl Encode for $value_type {
+            fn encode(&self, writer: &mut impl Write) -> Result<(), GraphComputingError> {
+                writer.write_all(&self.to_le_bytes())?;
+                Ok(())
+            }
+        }
+
+        impl Decode for $value_type {
+            fn decode(reader: &mut impl Read) -> Result<Self, GraphComputingError> {
+                let mut bytes = [0u8; std::mem::size_of::<$value_type>()];
+                read_exact(reader, &mut bytes)?;
+                Ok(<$value_type>::from_le_bytes(bytes))
+            }
+        }
+    };
+}
+
+implement_encoding_for_primitive!(i8);
+implement_encoding_for_primitive!(i16);
+implement_encoding_for_primitive!(i32);
+implement_encoding_for_primitive!(i64);
+implement_encoding_for_primitive!(i128);
+implement_encoding_for_primitive!(u8);
+implement_encoding_for_primitive!(u16);
+implement_encoding_for_primitive!(u32);
+implement_encoding_for_primitive!(u64);
+implement_encoding_for_primitive!(u128);
+implement_encoding_for_primitive!(f32);
+implement_encoding_for_primitive!(f64);
+
+impl Encode for usize {
+    fn encode(&self, writer: &mut impl Write) -> Result<(), GraphComputingError> {
+        (*self as u64).encode(writer)
+    }
+}
+
+impl Decode for usize {
+    fn decode(reader: &mut impl Read) -> Result<Self, GraphComputingError> {
+        let value = u64::decode(reader)?;
+        match usize::try_from(value) {
+            Ok(value) => Ok(value),
+            Err(_) => Err(corrupt_data_error(format!(
+                "Value {} does not fit in the address space of this platform",
+                value
+            ))),
+        }
+    }
+}
+
+impl Encode for bool {
+    fn encode(&self, writer: &mut impl Write) -> Result<(), GraphComputingError> {
+        (*self as u8).encode(writer)
+    }
+}
+
+impl Decode for bool {
+    fn decode(reader: &mut impl Read) -> Result<Self, GraphComputingError> {
+        match u8::decode(reader)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(corrupt_data_error(format!(
+                "Invalid encoding of a boolean: {}",
+                value
+            ))),
+        }
+    }
+}
+
+impl Encode for String {
+    fn encode(&self, writer: &mut impl Write) -> Result<(), GraphComputingError> {
+        self.len().encode(writer)?;
+        writer.write_all(self.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl Decode for String {
+    fn decode(reader: &mut impl Read) -> Result<Self, GraphComputingError> {
+        let length = usize::decode(reader)?;
+        let mut bytes = Vec::new();
+        reader.take(length as u64).read_to_end(&mut bytes)?;
+        if bytes.len() != length {
+            return Err(truncated_data_error());
+        }
+        match String::from_utf8(bytes) {
+            Ok(string) => Ok(string),
+            Err(error) => Err(corrupt_data_error(format!(
+                "Invalid UTF-8 in encoded string: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl<T: Encode> Encode for Vec<T> {
+    fn encode(&self, writer: &mut impl Write) -> Result<(), GraphComputingError> {
+        self.len().encode(writer)?;
+        for element in self.iter() {
+            element.encode(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Decode> Decode for Vec<T> {
+    fn decode(reader: &mut impl Read) -> Result<Self, GraphComputingError> {
+        let length = usize::decode(reader)?;
+        // The length is not trusted for pre-allocation, corrupt data could request an arbitrary amount of memory.
+        let mut elements = Vec::new();
+        for _ in 0..length {
+            elements.push(T::decode(reader)?);
+        }
+        Ok(elements)
+    }
+}
+
+impl<T: Encode> Encode for VecDeque<T> {
+    fn encode(&self, writer: &mut impl Write) -> Result<(), GraphComputingError> {
+        self.len().encode(writer)?;
+        for element in self.iter() {
+            element.encode(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Decode> Decode for VecDeque<T> {
+    fn decode(reader: &mut impl Read) -> Result<Self, GraphComputingError> {
+        Ok(Vec::<T>::decode(reader)?.into())
+    }
+}
+
+impl<T: Encode> Encode for Option<T> {
+    fn encode(&self, writer: &mut impl Write) -> Result<(), GraphComputingError> {
+        match self {
+            None => false.encode(writer),
+            Some(value) => {
+                true.encode(writer)?;
+                value.encode(writer)
+            }
+        }
+    }
+}
+
+impl<T: Decode> Decode for Option<T> {
+    fn decode(reader: &mut impl Read) -> Result<Self, GraphComputingError> {
+        match bool::decode(reader)? {
+            false => Ok(None),
+            true => Ok(Some(T::decode(reader)?)),
+        }
+    }
+}
+
+impl Encode for VertexValue {
+    fn encode(&self, writer: &mut impl Write) -> Result<(), GraphComputingError> {
+        match self {
+            VertexValue::None => 0u8.encode(writer),
+            VertexValue::String(value) => {
+                1u8.encode(writer)?;
+                value.encode(writer)
+            }
+            VertexValue::Boolean(value) => {
+                2u8.encode(writer)?;
+                value.encode(writer)
+            }
+            VertexValue::Integer8Bit(value) => {
+                3u8.encode(writer)?;
+                value.encode(writer)
+            }
+            VertexValue::Integer16Bit(value) => {
+                4u8.encode(writer)?;
+                value.encode(writer)
+            }
+            VertexValue::Integer32Bit(value) => {
+                5u8.encode(writer)?;
+                value.encode(writer)
+            }
+            VertexValue::Integer64Bit(value) => {
+                6u8.encode(writer)?;
+                value.encode(writer)
+            }
+            VertexValue::Integer128Bit(value) => {
+                7u8.encode(writer)?;
+                value.encode(writer)
+            }
+            VertexValue::UnsignedInteger8Bit(value) => {
+                8u8.encode(writer)?;
+                value.encode(writer)
+            }
+            VertexValue::UnsignedInteger16Bit(value) => {
+                9u8.encode(writer)?;
+                value.encode(writer)
+            }
+            VertexValue::UnsignedInteger32Bit(value) => {
+                10u8.encode(writer)?;
+                value.encode(writer)
+            }
+            VertexValue::UnsignedInteger64Bit(value) => {
+                11u8.encode(writer)?;
+                value.encode(writer)
+            }
+            VertexValue::UnsignedInteger128Bit(value) => {
+                12u8.encode(writer)?;
+                value.encode(writer)
+            }
+            VertexValue::FloatingPoint32Bit(value) => {
+                13u8.encode(writer)?;
+                value.encode(writer)
+            }
+            VertexValue::FloatingPoint64Bit(value) => {
+                14u8.encode(writer)?;
+                value.encode(writer)
+            }
+        }
+    }
+}
+
+impl Decode for VertexValue {
+    fn decode(reader: &mut impl Read) -> Result<Self, GraphComputingError> {
+        match u8::decode(reader)? {
+            0 => Ok(VertexValue::None),
+            1 => Ok(VertexValue::String(String::decode(reader)?)),
+            2 => Ok(VertexValue::Boolean(bool::decode(reader)?)),
+            3 => Ok(VertexValue::Integer8Bit(i8::decode(reader)?)),
+            4 => Ok(VertexValue::Integer16Bit(i16::decode(reader)?)),
+            5 => Ok(VertexValue::Integer32Bit(i32::decode(reader)?)),
+            6 => Ok(VertexValue::Integer64Bit(i64::decode(reader)?)),
+            7 => Ok(VertexValue::Integer128Bit(i128::decode(reader)?)),
+            8 => Ok(VertexValue::UnsignedInteger8Bit(u8::decode(reader)?)),
+            9 => Ok(VertexValue::UnsignedInteger16Bit(u16::decode(reader)?)),
+            10 => Ok(VertexValue::UnsignedInteger32Bit(u32::decode(reader)?)),
+            11 => Ok(VertexValue::UnsignedInteger64Bit(u64::decode(reader)?)),
+            12 => Ok(VertexValue::UnsignedInteger128Bit(u128::decode(reader)?)),
+            13 => Ok(VertexValue::FloatingPoint32Bit(f32::decode(reader)?)),
+            14 => Ok(VertexValue::FloatingPoint64Bit(f64::decode(reader)?)),
+            tag => Err(corrupt_data_error(format!(
+                "Unknown vertex value type tag: {}",
+                tag
+            ))),
+        }
+    }
+}
+
+impl Encode for Vertex {
+    fn encode(&self, writer: &mut impl Write) -> Result<(), GraphComputingError> {
+        self.key_ref().to_owned().encode(writer)?;
+        self.value_ref().encode(writer)?;
+        self.properties_ref().len().encode(writer)?;
+        for (property_name, value) in self.properties_ref().iter() {
+            property_name.encode(writer)?;
+            value.encode(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl Decode for Vertex {
+    fn decode(reader: &mut impl Read) -> Result<Self, GraphComputingError> {
+        let key = String::decode(reader)?;
+        let value = VertexValue::decode(reader)?;
+        let mut vertex = Vertex::new(key, value);
+        let number_of_properties = usize::decode(reader)?;
+        for _ in 0..number_of_properties {
+            let property_name = String::decode(reader)?;
+            vertex.update_property(property_name, VertexValue::decode(reader)?);
+        }
+        Ok(vertex)
+    }
+}
+
+impl Encode for EdgeWeightType {
+    fn encode(&self, writer: &mut impl Write) -> Result<(), GraphComputingError> {
+        match self {
+            EdgeWeightType::Boolean => 0u8.encode(writer),
+            EdgeWeightType::Integer64Bit => 1u8.encode(writer),
+            EdgeWeightType::FloatingPoint64Bit => 2u8.encode(writer),
+        }
+    }
+}
+
+impl Decode for EdgeWeightType {
+    fn decode(reader: &mut impl Read) -> Result<Self, GraphComputingError> {
+        match u8::decode(reader)? {
+            0 => Ok(EdgeWeightType::Boolean),
+            1 => Ok(EdgeWeightType::Integer64Bit),
+            2 => Ok(EdgeWeightType::FloatingPoint64Bit),
+            tag => Err(corrupt_data_error(format!(
+                "Unknown edge weight type tag: {}",
+                tag
+            ))),
+        }
+    }
+}
+
+// The tags match the tags of VertexValue
+impl Encode for VertexValueType {
+    fn encode(&self, writer: &mut impl Write) -> Result<(), GraphComputingError> {
+        let tag: u8 = match self {
+            VertexValueType::None => 0,
+            VertexValueType::String => 1,
+            VertexValueType::Boolean => 2,
+            VertexValueType::Integer8Bit => 3,
+            VertexValueType::Integer16Bit => 4,
+            VertexValueType::Integer32Bit => 5,
+            VertexValueType::Integer64Bit => 6,
+            VertexValueType::Integer128Bit => 7,
+            VertexValueType::UnsignedInteger8Bit => 8,
+            VertexValueType::UnsignedInteger16Bit => 9,
+            VertexValueType::UnsignedInteger32Bit => 10,
+            VertexValueType::UnsignedInteger64Bit => 11,
+            VertexValueType::UnsignedInteger128Bit => 12,
+            VertexValueType::FloatingPoint32Bit => 13,
+            VertexValueType::FloatingPoint64Bit => 14,
+        };
+        tag.encode(writer)
+    }
+}
+
+impl Decode for VertexValueType {
+    fn decode(reader: &mut impl Read) -> Result<Self, GraphComputingError> {
+        match u8::decode(reader)? {
+            0 => Ok(VertexValueType::None),
+            1 => Ok(VertexValueType::String),
+            2 => Ok(VertexValueType::Boolean),
+            3 => Ok(VertexValueType::Integer8Bit),
+            4 => Ok(VertexValueType::Integer16Bit),
+            5 => Ok(VertexValueType::Integer32Bit),
+            6 => Ok(VertexValueType::Integer64Bit),
+            7 => Ok(VertexValueType::Integer128Bit),
+            8 => Ok(VertexValueType::UnsignedInteger8Bit),
+            9 => Ok(VertexValueType::UnsignedInteger16Bit),
+            10 => Ok(VertexValueType::UnsignedInteger32Bit),
+            11 => Ok(VertexValueType::UnsignedInteger64Bit),
+            12 => Ok(VertexValueType::UnsignedInteger128Bit),
+            13 => Ok(VertexValueType::FloatingPoint32Bit),
+            14 => Ok(VertexValueType::FloatingPoint64Bit),
+            tag => Err(corrupt_data_error(format!(
+                "Unknown vertex value type tag: {}",
+                tag
+            ))),
+        }
+    }
+}
+
+impl Encode for Schema {
+    fn encode(&self, writer: &mut impl Write) -> Result<(), GraphComputingError> {
+        self.edge_type_constraints_ref().len().encode(writer)?;
+        for (edge_type, constraint) in self.edge_type_constraints_ref().iter() {
+            edge_type.encode(writer)?;
+            constraint
+                .from_vertex_label_ref()
+                .map(String::from)
+                .encode(writer)?;
+            constraint
+                .to_vertex_label_ref()
+                .map(String::from)
+                .encode(writer)?;
+            constraint.is_functional().encode(writer)?;
+        }
+        self.vertex_value_types_ref().len().encode(writer)?;
+        for (vertex_label, value_type) in self.vertex_value_types_ref().iter() {
+            vertex_label.encode(writer)?;
+            value_type.encode(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl Decode for Schema {
+    fn decode(reader: &mut impl Read) -> Result<Self, GraphComputingError> {
+        let mut schema = Schema::new();
+        let number_of_edge_type_constraints = usize::decode(reader)?;
+        for _ in 0..number_of_edge_type_constraints {
+            let edge_type = String::decode(reader)?;
+            let mut constraint = EdgeTypeConstraint::new();
+            if let Some(vertex_label) = Option::<String>::decode(reader)? {
+                constraint = constraint.with_from_vertex_label(&vertex_label);
+            }
+            if let Some(vertex_label) = Option::<String>::decode(reader)? {
+                constraint = constraint.with_to_vertex_label(&vertex_label);
+            }
+            if bool::decode(reader)? {
+                constraint = constraint.functional();
+            }
+            schema = schema.with_edge_type_constraint(&edge_type, constraint);
+        }
+        let number_of_vertex_value_types = usize::decode(reader)?;
+        for _ in 0..number_of_vertex_value_types {
+            let vertex_label = String::decode(reader)?;
+            schema = schema.with_vertex_value_type(&vertex_label, VertexValueType::decode(reader)?);
+        }
+        Ok(schema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip<T: Encode + Decode>(value: &T) -> T {
+        let mut bytes = Vec::new();
+        value.encode(&mut bytes).unwrap();
+        T::decode(&mut bytes.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn round_trip_vertex_values() {
+        let values: Vec<VertexValue> = vec![
+            VertexValue::None,
+            String::from("a string").into(),
+            true.into(),
+            (-1i8).into(),
+            (-1i16).into(),
+            (-1i32).into(),
+            (-1i64).into(),
+            (-1i128).into(),
+            1u8.into(),
+            1u16.into(),
+            1u32.into(),
+            1u64.into(),
+            u128::MAX.into(),
+            1.1f32.into(),
+            (-1.1f64).into(),
+        ];
+        for value in values.iter() {
+            assert_eq!(&round_trip(value), value);
+            assert_eq!(round_trip(&value.value_type()), value.value_type());
+        }
+    }
+
+    #[test]
+    fn round_trip_schema() {
+        let schema = Schema::new()
+            .with_edge_type_constraint(
+                "verified_by",
+                EdgeTypeConstraint::new()
+                    .with_from_vertex_label("Requirement")
+                    .with_to_vertex_label("Test")
+                    .functional(),
+            )
+            .with_edge_type_constraint(
+                "refines",
+                EdgeTypeConstraint::new().with_to_vertex_label("Requirement"),
+            )
+            .with_vertex_value_type("Requirement", VertexValueType::String);
+        assert_eq!(round_trip(&schema), schema)
+    }
+
+    #[test]
+    fn round_trip_vertex() {
+        let vertex = Vertex::new(String::from("key"), String::from("value").into());
+        assert_eq!(round_trip(&vertex), vertex)
+    }
+
+    #[test]
+    fn decode_truncated_string() {
+        let mut bytes = Vec::new();
+        String::from("truncated").encode(&mut bytes).unwrap();
+        bytes.pop();
+        assert!(String::decode(&mut bytes.as_slice()).is_err())
+    }
+
+    #[test]
+    fn decode_unknown_vertex_value_tag() {
+        let bytes = vec![255u8];
+        let error = VertexValue::decode(&mut bytes.as_slice()).unwrap_err();
+        assert_eq!(
+            error.error_type(),
+            crate::error::GraphComputingErrorType::UserErrorType(UserErrorType::CorruptData)
+        )
+    }
+}
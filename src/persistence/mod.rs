@@ -0,0 +1,3 @@
+pub mod binary_snapshot;
+pub(crate) mod encoding;
+pub mod write_ahead_log;
@@ -0,0 +1,1398 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::error::{GraphComputingError, SystemError, SystemErrorType, UserError, UserErrorType};
+use crate::graph::edge::{
+    DirectedEdgeDefinedByIndices, EdgeType, EdgeTypeIndex, EdgeValue, EdgeWeight, EdgeWeightType,
+    IntegerEdgeWeight,
+};
+use crate::graph::graph::{ElementIndex, Graph};
+use crate::graph::schema::Schema;
+use crate::graph::vertex::{PropertyName, Vertex, VertexIndex, VertexLabel, VertexValue};
+use crate::operations::add_edge::AddEdge;
+use crate::operations::add_edge_type::AddEdgeType;
+use crate::operations::add_vertex::AddVertex;
+use crate::operations::define_schema::DefineSchema;
+use crate::operations::delete_edge::DeleteEdge;
+use crate::operations::delete_vertex::DeleteVertex;
+use crate::operations::drop_edge_type::DropEdgeType;
+use crate::operations::infer_edges::InferEdges;
+use crate::operations::label_vertex::LabelVertex;
+use crate::operations::set_edge_value::SetEdgeValue;
+use crate::operations::set_edge_weight::SetEdgeWeight;
+use crate::operations::update_vertex::UpdateVertex;
+
+use super::binary_snapshot::BinarySnapshot;
+use super::encoding::{corrupt_data_error, Decode, Encode};
+
+// A durable graph is stored in a directory with a snapshot and a write-ahead log.
+// Both files start with a generation number. The log only applies to the snapshot of the same generation;
+// a log with an older generation remains after a crash during a checkpoint, and is discarded.
+const SNAPSHOT_FILE_NAME: &str = "graph.snapshot";
+const WRITE_AHEAD_LOG_FILE_NAME: &str = "graph.wal";
+const TEMPORARY_FILE_EXTENSION: &str = "tmp";
+
+// Log header: magic bytes, format version (u32), generation (u64)
+const WRITE_AHEAD_LOG_MAGIC_BYTES: &[u8; 8] = b"CAIRNWAL";
+const WRITE_AHEAD_LOG_FORMAT_VERSION: u32 = 1;
+const WRITE_AHEAD_LOG_HEADER_LENGTH: u64 = 20;
+// Record header: payload length (u32) followed by the CRC-32 checksum of the payload (u32)
+const RECORD_HEADER_LENGTH: u64 = 8;
+
+const DEFAULT_INITIAL_VERTEX_CAPACITY: ElementIndex = 256;
+const DEFAULT_INITIAL_EDGE_TYPE_CAPACITY: ElementIndex = 16;
+
+/// Makes every mutation of a graph durable in an append-only write-ahead log.
+///
+/// A mutation is appended, and synced to disk, after it was applied to the in-memory graph.
+/// A mutation that fails is not logged.
+/// Cloning a durable graph results in an in-memory graph that does not write to the log.
+///
+/// If appending to the log fails, the graph is poisoned: a mutation outside a transaction then remains in the in-memory graph
+/// without being in the log, and the log may end with a partial record. A poisoned graph refuses all further mutations and
+/// checkpoints with SystemErrorType::PoisonedData. Open the directory again to continue from the durable state.
+pub trait Durability {
+    /// Opens the durable graph stored in the directory at path, the directory is created if it does not exist.
+    /// The write-ahead log is replayed on top of the last snapshot. A torn final record, as left
+    /// behind by a crash during an append, is truncated.
+    fn open(path: impl AsRef<Path>) -> Result<Graph, GraphComputingError>;
+
+    /// Writes a snapshot of the graph and starts a new, empty write-ahead log.
+    fn checkpoint(&mut self) -> Result<(), GraphComputingError>;
+
+    fn is_durable(&self) -> bool;
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum GraphMutation {
+    AddOrReplaceVertex(Vertex),
+    UpdateVertexValue {
+        vertex_index: ElementIndex,
+        vertex_value: VertexValue,
+    },
+    DeleteVertex(ElementIndex),
+    AddEdgeType {
+        edge_type: EdgeType,
+        edge_weight_type: EdgeWeightType,
+    },
+    DropEdgeType(ElementIndex),
+    AddEdge {
+        edge_type_index: ElementIndex,
+        from_vertex_index: ElementIndex,
+        to_vertex_index: ElementIndex,
+    },
+    DeleteEdge {
+        edge_type_index: ElementIndex,
+        from_vertex_index: ElementIndex,
+        to_vertex_index: ElementIndex,
+    },
+    // The mutations of a committed transaction are stored in a single record, such that they are replayed atomically
+    Transaction(Vec<GraphMutation>),
+    AddInferredEdges {
+        edge_type_index: ElementIndex,
+        from_vertex_indices: Vec<ElementIndex>,
+        to_vertex_indices: Vec<ElementIndex>,
+    },
+    DeleteInferredEdges,
+    AddEdges {
+        edge_type_index: ElementIndex,
+        from_vertex_indices: Vec<ElementIndex>,
+        to_vertex_indices: Vec<ElementIndex>,
+    },
+    SetEdgeWeight {
+        edge_type_index: ElementIndex,
+        from_vertex_index: ElementIndex,
+        to_vertex_index: ElementIndex,
+        weight: EdgeWeight,
+    },
+    SetEdgeValue {
+        edge_type_index: ElementIndex,
+        from_vertex_index: ElementIndex,
+        to_vertex_index: ElementIndex,
+        value: EdgeValue,
+    },
+    DeleteEdgeValue {
+        edge_type_index: ElementIndex,
+        from_vertex_index: ElementIndex,
+        to_vertex_index: ElementIndex,
+    },
+    SetIntegerEdgeWeight {
+        edge_type_index: ElementIndex,
+        from_vertex_index: ElementIndex,
+        to_vertex_index: ElementIndex,
+        weight: IntegerEdgeWeight,
+    },
+    UpdateVertex(Vertex),
+    UpdateVertexProperty {
+        vertex_index: ElementIndex,
+        property_name: PropertyName,
+        value: VertexValue,
+    },
+    RemoveVertexProperty {
+        vertex_index: ElementIndex,
+        property_name: PropertyName,
+    },
+    AddVertexLabel {
+        vertex_index: ElementIndex,
+        vertex_label: VertexLabel,
+    },
+    RemoveVertexLabel {
+        vertex_index: ElementIndex,
+        vertex_label: VertexLabel,
+    },
+    SetSchema(Schema),
+}
+
+#[derive(Debug)]
+pub(crate) struct WriteAheadLog {
+    directory: PathBuf,
+    generation: u64,
+    log_file: File,
+    transaction_buffer: Option<Vec<GraphMutation>>,
+    is_poisoned: bool,
+}
+
+// Marks the state of the write-ahead log at the start of a transaction
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum WriteAheadLogSavepoint {
+    NotDurable,
+    OutermostTransaction,
+    NestedTransaction(usize),
+}
+
+impl Durability for Graph {
+    fn open(path: impl AsRef<Path>) -> Result<Graph, GraphComputingError> {
+        let directory = path.as_ref().to_path_buf();
+        fs::create_dir_all(&directory)?;
+
+        let (mut graph, generation) = match File::open(directory.join(SNAPSHOT_FILE_NAME)) {
+            Ok(snapshot_file) => {
+                let mut reader = BufReader::new(snapshot_file);
+                let generation = u64::decode(&mut reader)?;
+                (Graph::load_snapshot(reader)?, generation)
+            }
+            Err(error) if error.kind() == ErrorKind::NotFound => (
+                Graph::new(
+                    DEFAULT_INITIAL_VERTEX_CAPACITY,
+                    DEFAULT_INITIAL_EDGE_TYPE_CAPACITY,
+                )?,
+                0,
+            ),
+            Err(error) => return Err(error.into()),
+        };
+
+        let log_path = directory.join(WRITE_AHEAD_LOG_FILE_NAME);
+        match read_write_ahead_log(&log_path)? {
+            Some((log_generation, mutations, valid_length)) if log_generation == generation => {
+                for mutation in mutations.into_iter() {
+                    apply_mutation(&mut graph, mutation)?;
+                }
+                let log_file = OpenOptions::new().write(true).open(&log_path)?;
+                log_file.set_len(valid_length)?;
+                log_file.sync_all()?;
+            }
+            // A missing log, or a log that predates the snapshot, is replaced by a new empty log
+            _ => create_write_ahead_log(&directory, generation)?,
+        }
+
+        let log_file = OpenOptions::new().append(true).open(&log_path)?;
+        *graph.write_ahead_log_mut_ref() = Some(WriteAheadLog {
+            directory,
+            generation,
+            log_file,
+            transaction_buffer: None,
+            is_poisoned: false,
+        });
+        Ok(graph)
+    }
+
+    fn checkpoint(&mut self) -> Result<(), GraphComputingError> {
+        let (directory, generation) = match self.write_ahead_log_ref() {
+            Some(write_ahead_log) => (
+                write_ahead_log.directory.clone(),
+                write_ahead_log.generation + 1,
+            ),
+            None => return Ok(()),
+        };
+        self.check_write_ahead_log_is_writable()?;
+        if let Some(WriteAheadLog {
+            transaction_buffer: Some(_),
+            ..
+        }) = self.write_ahead_log_ref()
+        {
+            return Err(UserError::new(
+                UserErrorType::TransactionInProgress,
+                String::from("A checkpoint cannot be written while a transaction is in progress"),
+                None,
+            )
+            .into());
+        }
+
+        let snapshot_path = directory.join(SNAPSHOT_FILE_NAME);
+        let temporary_snapshot_path = snapshot_path.with_extension(TEMPORARY_FILE_EXTENSION);
+        {
+            let mut writer = BufWriter::new(File::create(&temporary_snapshot_path)?);
+            generation.encode(&mut writer)?;
+            self.save_snapshot(&mut writer)?;
+            match writer.into_inner() {
+                Ok(snapshot_file) => snapshot_file.sync_all()?,
+                Err(error) => return Err(error.into_error().into()),
+            }
+        }
+        fs::rename(&temporary_snapshot_path, &snapshot_path)?;
+        sync_directory(&directory)?;
+
+        create_write_ahead_log(&directory, generation)?;
+        let log_file = OpenOptions::new()
+            .append(true)
+            .open(directory.join(WRITE_AHEAD_LOG_FILE_NAME))?;
+        *self.write_ahead_log_mut_ref() = Some(WriteAheadLog {
+            directory,
+            generation,
+            log_file,
+            transaction_buffer: None,
+            is_poisoned: false,
+        });
+        Ok(())
+    }
+
+    fn is_durable(&self) -> bool {
+        self.write_ahead_log_ref().is_some()
+    }
+}
+
+impl Graph {
+    // Called before a mutation is applied, such that a poisoned graph is not changed any further
+    pub(crate) fn check_write_ahead_log_is_writable(&self) -> Result<(), GraphComputingError> {
+        match self.write_ahead_log_ref() {
+            Some(write_ahead_log) if write_ahead_log.is_poisoned => Err(SystemError::new(
+                SystemErrorType::PoisonedData,
+                String::from("The graph no longer accepts changes because appending to its write-ahead log failed, open the graph again"),
+                None,
+            )
+            .into()),
+            _ => Ok(()),
+        }
+    }
+
+    // The mutation is only described if the graph is durable, avoiding the cost of cloning values for in-memory graphs.
+    // The description is called after the mutation was applied, and can read the mutated graph.
+    pub(crate) fn append_to_write_ahead_log(
+        &mut self,
+        describe_mutation: impl FnOnce(&Graph) -> Result<GraphMutation, GraphComputingError>,
+    ) -> Result<(), GraphComputingError> {
+        if self.write_ahead_log_ref().is_some() {
+            let mutation = describe_mutation(self)?;
+            if let Some(write_ahead_log) = self.write_ahead_log_mut_ref() {
+                write_ahead_log.append(&mutation)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Graph {
+    pub(crate) fn begin_write_ahead_log_transaction(&mut self) -> WriteAheadLogSavepoint {
+        match self.write_ahead_log_mut_ref() {
+            None => WriteAheadLogSavepoint::NotDurable,
+            Some(write_ahead_log) => match &write_ahead_log.transaction_buffer {
+                None => {
+                    write_ahead_log.transaction_buffer = Some(Vec::new());
+                    WriteAheadLogSavepoint::OutermostTransaction
+                }
+                Some(buffer) => WriteAheadLogSavepoint::NestedTransaction(buffer.len()),
+            },
+        }
+    }
+
+    // Only the outermost transaction appends to the log, nested transactions commit into the buffer of the outer transaction
+    pub(crate) fn commit_write_ahead_log_transaction(
+        &mut self,
+        savepoint: WriteAheadLogSavepoint,
+    ) -> Result<(), GraphComputingError> {
+        match (savepoint, self.write_ahead_log_mut_ref()) {
+            (WriteAheadLogSavepoint::OutermostTransaction, Some(write_ahead_log)) => {
+                match write_ahead_log.transaction_buffer.take() {
+                    Some(mutations) if !mutations.is_empty() => {
+                        write_ahead_log.append(&GraphMutation::Transaction(mutations))
+                    }
+                    _ => Ok(()),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn roll_back_write_ahead_log_transaction(
+        &mut self,
+        savepoint: WriteAheadLogSavepoint,
+    ) {
+        if let Some(write_ahead_log) = self.write_ahead_log_mut_ref() {
+            match savepoint {
+                WriteAheadLogSavepoint::OutermostTransaction => {
+                    write_ahead_log.transaction_buffer = None
+                }
+                WriteAheadLogSavepoint::NestedTransaction(number_of_buffered_mutations) => {
+                    if let Some(buffer) = &mut write_ahead_log.transaction_buffer {
+                        buffer.truncate(number_of_buffered_mutations)
+                    }
+                }
+                WriteAheadLogSavepoint::NotDurable => (),
+            }
+        }
+    }
+}
+
+impl WriteAheadLog {
+    fn append(&mut self, mutation: &GraphMutation) -> Result<(), GraphComputingError> {
+        if let Some(buffer) = &mut self.transaction_buffer {
+            buffer.push(mutation.clone());
+            return Ok(());
+        }
+
+        let mut payload = Vec::new();
+        mutation.encode(&mut payload)?;
+
+        let mut record = Vec::with_capacity(RECORD_HEADER_LENGTH as usize + payload.len());
+        (payload.len() as u32).encode(&mut record)?;
+        crc32(&payload).encode(&mut record)?;
+        record.extend_from_slice(&payload);
+
+        // A single write keeps the window for a torn record as small as possible
+        let result = self
+            .log_file
+            .write_all(&record)
+            .and_then(|_| self.log_file.sync_data());
+        if let Err(error) = result {
+            // The log may now end with a partial record, records appended after it would be lost on replay
+            self.is_poisoned = true;
+            return Err(error.into());
+        }
+        Ok(())
+    }
+}
+
+fn create_write_ahead_log(directory: &Path, generation: u64) -> Result<(), GraphComputingError> {
+    let log_path = directory.join(WRITE_AHEAD_LOG_FILE_NAME);
+    let temporary_log_path = log_path.with_extension(TEMPORARY_FILE_EXTENSION);
+
+    let mut log_file = File::create(&temporary_log_path)?;
+    let mut header = Vec::with_capacity(WRITE_AHEAD_LOG_HEADER_LENGTH as usize);
+    header.extend_from_slice(WRITE_AHEAD_LOG_MAGIC_BYTES);
+    WRITE_AHEAD_LOG_FORMAT_VERSION.encode(&mut header)?;
+    generation.encode(&mut header)?;
+    log_file.write_all(&header)?;
+    log_file.sync_all()?;
+
+    fs::rename(&temporary_log_path, &log_path)?;
+    sync_directory(directory)
+}
+
+// Returns the generation, the mutations of all complete records, and the length of the log up to and including the last complete record.
+fn read_write_ahead_log(
+    log_path: &Path,
+) -> Result<Option<(u64, Vec<GraphMutation>, u64)>, GraphComputingError> {
+    let mut data = Vec::new();
+    match File::open(log_path) {
+        Ok(mut log_file) => log_file.read_to_end(&mut data)?,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(error.into()),
+    };
+
+    // A torn header can only be the result of a crash while creating the log, before it was taken into use
+    if (data.len() as u64) < WRITE_AHEAD_LOG_HEADER_LENGTH {
+        return Ok(None);
+    }
+    if &data[0..WRITE_AHEAD_LOG_MAGIC_BYTES.len()] != WRITE_AHEAD_LOG_MAGIC_BYTES {
+        return Err(corrupt_data_error(format!(
+            "{} is not a Cairn Knowledge Graph write-ahead log",
+            log_path.display()
+        )));
+    }
+    let header = &mut &data[WRITE_AHEAD_LOG_MAGIC_BYTES.len()..];
+    let format_version = u32::decode(header)?;
+    if format_version != WRITE_AHEAD_LOG_FORMAT_VERSION {
+        return Err(UserError::new(
+            UserErrorType::UnsupportedWriteAheadLogVersion,
+            format!(
+                "Write-ahead log {} has format version {}, expected version {}",
+                log_path.display(),
+                format_version,
+                WRITE_AHEAD_LOG_FORMAT_VERSION
+            ),
+            None,
+        )
+        .into());
+    }
+    let generation = u64::decode(header)?;
+
+    let mut mutations = Vec::new();
+    let mut position = WRITE_AHEAD_LOG_HEADER_LENGTH as usize;
+    while position < data.len() {
+        let remaining = &data[position..];
+        if (remaining.len() as u64) < RECORD_HEADER_LENGTH {
+            break; // torn record header
+        }
+        let payload_length = u32::decode(&mut &remaining[0..4])? as usize;
+        let checksum = u32::decode(&mut &remaining[4..8])?;
+        let record_length = RECORD_HEADER_LENGTH as usize + payload_length;
+        if remaining.len() < record_length {
+            break; // torn record payload
+        }
+        let payload = &remaining[RECORD_HEADER_LENGTH as usize..record_length];
+        if crc32(payload) != checksum {
+            if remaining.len() == record_length {
+                break; // torn final record, e.g. a partially flushed page
+            }
+            return Err(corrupt_data_error(format!(
+                "Checksum mismatch in write-ahead log {} at byte {}",
+                log_path.display(),
+                position
+            )));
+        }
+        mutations.push(GraphMutation::decode(&mut &payload[..])?);
+        position += record_length;
+    }
+    Ok(Some((generation, mutations, position as u64)))
+}
+
+fn apply_mutation(graph: &mut Graph, mutation: GraphMutation) -> Result<(), GraphComputingError> {
+    match mutation {
+        GraphMutation::AddOrReplaceVertex(vertex) => {
+            graph.add_or_replace_vertex(vertex)?;
+        }
+        GraphMutation::UpdateVertexValue {
+            vertex_index,
+            vertex_value,
+        } => graph.update_vertex_value_by_index(VertexIndex::new(vertex_index), vertex_value)?,
+        GraphMutation::DeleteVertex(vertex_index) => {
+            graph.delete_vertex_and_connected_edges_by_index(VertexIndex::new(vertex_index))?
+        }
+        GraphMutation::AddEdgeType {
+            edge_type,
+            edge_weight_type,
+        } => {
+            graph.add_new_edge_type_with_weight_type(edge_type, edge_weight_type)?;
+        }
+        GraphMutation::DropEdgeType(edge_type_index) => {
+            graph.drop_edge_type_with_index(&EdgeTypeIndex::new(edge_type_index))?
+        }
+        GraphMutation::AddEdge {
+            edge_type_index,
+            from_vertex_index,
+            to_vertex_index,
+        } => graph.add_edge_using_indices(DirectedEdgeDefinedByIndices::new(
+            VertexIndex::new(from_vertex_index),
+            EdgeTypeIndex::new(edge_type_index),
+            VertexIndex::new(to_vertex_index),
+        ))?,
+        GraphMutation::DeleteEdge {
+            edge_type_index,
+            from_vertex_index,
+            to_vertex_index,
+        } => graph.delete_edge_defined_by_indices(&DirectedEdgeDefinedByIndices::new(
+            VertexIndex::new(from_vertex_index),
+            EdgeTypeIndex::new(edge_type_index),
+            VertexIndex::new(to_vertex_index),
+        ))?,
+        GraphMutation::Transaction(mutations) => {
+            for mutation in mutations.into_iter() {
+                apply_mutation(graph, mutation)?;
+            }
+        }
+        GraphMutation::AddInferredEdges {
+            edge_type_index,
+            from_vertex_indices,
+            to_vertex_indices,
+        } => graph.add_inferred_edges_using_indices(
+            EdgeTypeIndex::new(edge_type_index),
+            from_vertex_indices,
+            to_vertex_indices,
+        )?,
+        GraphMutation::DeleteInferredEdges => graph.delete_inferred_edges()?,
+        GraphMutation::AddEdges {
+            edge_type_index,
+            from_vertex_indices,
+            to_vertex_indices,
+        } => graph.add_edges_using_indices(
+            EdgeTypeIndex::new(edge_type_index),
+            from_vertex_indices,
+            to_vertex_indices,
+        )?,
+        GraphMutation::SetEdgeWeight {
+            edge_type_index,
+            from_vertex_index,
+            to_vertex_index,
+            weight,
+        } => graph.set_edge_weight_using_indices(
+            &DirectedEdgeDefinedByIndices::new(
+                VertexIndex::new(from_vertex_index),
+                EdgeTypeIndex::new(edge_type_index),
+                VertexIndex::new(to_vertex_index),
+            ),
+            weight,
+        )?,
+        GraphMutation::SetEdgeValue {
+            edge_type_index,
+            from_vertex_index,
+            to_vertex_index,
+            value,
+        } => graph.set_edge_value_using_indices(
+            &DirectedEdgeDefinedByIndices::new(
+                VertexIndex::new(from_vertex_index),
+                EdgeTypeIndex::new(edge_type_index),
+                VertexIndex::new(to_vertex_index),
+            ),
+            value,
+        )?,
+        GraphMutation::DeleteEdgeValue {
+            edge_type_index,
+            from_vertex_index,
+            to_vertex_index,
+        } => {
+            graph.delete_edge_value_using_indices(&DirectedEdgeDefinedByIndices::new(
+                VertexIndex::new(from_vertex_index),
+                EdgeTypeIndex::new(edge_type_index),
+                VertexIndex::new(to_vertex_index),
+            ))?;
+        }
+        GraphMutation::SetIntegerEdgeWeight {
+            edge_type_index,
+            from_vertex_index,
+            to_vertex_index,
+            weight,
+        } => graph.set_integer_edge_weight_using_indices(
+            &DirectedEdgeDefinedByIndices::new(
+                VertexIndex::new(from_vertex_index),
+                EdgeTypeIndex::new(edge_type_index),
+                VertexIndex::new(to_vertex_index),
+            ),
+            weight,
+        )?,
+        GraphMutation::UpdateVertex(vertex) => graph.update_vertex(vertex)?,
+        GraphMutation::UpdateVertexProperty {
+            vertex_index,
+            property_name,
+            value,
+        } => {
+            graph.update_vertex_property_by_index(
+                VertexIndex::new(vertex_index),
+                property_name,
+                value,
+            )?;
+        }
+        GraphMutation::RemoveVertexProperty {
+            vertex_index,
+            property_name,
+        } => {
+            graph
+                .remove_vertex_property_by_index(VertexIndex::new(vertex_index), &property_name)?;
+        }
+        GraphMutation::AddVertexLabel {
+            vertex_index,
+            vertex_label,
+        } => graph.add_vertex_label_by_index(VertexIndex::new(vertex_index), vertex_label)?,
+        GraphMutation::RemoveVertexLabel {
+            vertex_index,
+            vertex_label,
+        } => {
+            graph.remove_vertex_label_by_index(VertexIndex::new(vertex_index), &vertex_label)?;
+        }
+        GraphMutation::SetSchema(schema) => graph.set_schema(schema)?,
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn sync_directory(directory: &Path) -> Result<(), GraphComputingError> {
+    File::open(directory)?.sync_all()?;
+    Ok(())
+}
+
+// Directories cannot be opened as a file on all platforms
+#[cfg(not(unix))]
+fn sync_directory(_directory: &Path) -> Result<(), GraphComputingError> {
+    Ok(())
+}
+
+// CRC-32 (IEEE 802.3), bitwise implementation. Logging is bound by disk syncs, not by the checksum.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for byte in data.iter() {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (!(crc & 1)).wrapping_add(1);
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+impl Encode for GraphMutation {
+    fn encode(&self, writer: &mut impl Write) -> Result<(), GraphComputingError> {
+        match self {
+            GraphMutation::AddOrReplaceVertex(vertex) => {
+                16u8.encode(writer)?;
+                vertex.encode(writer)
+            }
+            GraphMutation::UpdateVertexValue {
+                vertex_index,
+                vertex_value,
+            } => {
+                1u8.encode(writer)?;
+                vertex_index.encode(writer)?;
+                vertex_value.encode(writer)
+            }
+            GraphMutation::DeleteVertex(vertex_index) => {
+                2u8.encode(writer)?;
+                vertex_index.encode(writer)
+            }
+            GraphMutation::AddEdgeType {
+                edge_type,
+                edge_weight_type,
+            } => {
+                3u8.encode(writer)?;
+                edge_type.encode(writer)?;
+                edge_weight_type.encode(writer)
+            }
+            GraphMutation::DropEdgeType(edge_type_index) => {
+                4u8.encode(writer)?;
+                edge_type_index.encode(writer)
+            }
+            GraphMutation::AddEdge {
+                edge_type_index,
+                from_vertex_index,
+                to_vertex_index,
+            } => {
+                5u8.encode(writer)?;
+                edge_type_index.encode(writer)?;
+                from_vertex_index.encode(writer)?;
+                to_vertex_index.encode(writer)
+            }
+            GraphMutation::DeleteEdge {
+                edge_type_index,
+                from_vertex_index,
+                to_vertex_index,
+            } => {
+                6u8.encode(writer)?;
+                edge_type_index.encode(writer)?;
+                from_vertex_index.encode(writer)?;
+                to_vertex_index.encode(writer)
+            }
+            GraphMutation::Transaction(mutations) => {
+                7u8.encode(writer)?;
+                mutations.encode(writer)
+            }
+            GraphMutation::AddInferredEdges {
+                edge_type_index,
+                from_vertex_indices,
+                to_vertex_indices,
+            } => {
+                8u8.encode(writer)?;
+                edge_type_index.encode(writer)?;
+                from_vertex_indices.encode(writer)?;
+                to_vertex_indices.encode(writer)
+            }
+            GraphMutation::DeleteInferredEdges => 9u8.encode(writer),
+            GraphMutation::AddEdges {
+                edge_type_index,
+                from_vertex_indices,
+                to_vertex_indices,
+            } => {
+                10u8.encode(writer)?;
+                edge_type_index.encode(writer)?;
+                from_vertex_indices.encode(writer)?;
+                to_vertex_indices.encode(writer)
+            }
+            GraphMutation::SetEdgeWeight {
+                edge_type_index,
+                from_vertex_index,
+                to_vertex_index,
+                weight,
+            } => {
+                11u8.encode(writer)?;
+                edge_type_index.encode(writer)?;
+                from_vertex_index.encode(writer)?;
+                to_vertex_index.encode(writer)?;
+                weight.encode(writer)
+            }
+            GraphMutation::SetEdgeValue {
+                edge_type_index,
+                from_vertex_index,
+                to_vertex_index,
+                value,
+            } => {
+                12u8.encode(writer)?;
+                edge_type_index.encode(writer)?;
+                from_vertex_index.encode(writer)?;
+                to_vertex_index.encode(writer)?;
+                value.encode(writer)
+            }
+            GraphMutation::DeleteEdgeValue {
+                edge_type_index,
+                from_vertex_index,
+                to_vertex_index,
+            } => {
+                13u8.encode(writer)?;
+                edge_type_index.encode(writer)?;
+                from_vertex_index.encode(writer)?;
+                to_vertex_index.encode(writer)
+            }
+            GraphMutation::SetIntegerEdgeWeight {
+                edge_type_index,
+                from_vertex_index,
+                to_vertex_index,
+                weight,
+            } => {
+                15u8.encode(writer)?;
+                edge_type_index.encode(writer)?;
+                from_vertex_index.encode(writer)?;
+                to_vertex_index.encode(writer)?;
+                weight.encode(writer)
+            }
+            GraphMutation::UpdateVertex(vertex) => {
+                17u8.encode(writer)?;
+                vertex.encode(writer)
+            }
+            GraphMutation::UpdateVertexProperty {
+                vertex_index,
+                property_name,
+                value,
+            } => {
+                18u8.encode(writer)?;
+                vertex_index.encode(writer)?;
+                property_name.encode(writer)?;
+                value.encode(writer)
+            }
+            GraphMutation::RemoveVertexProperty {
+                vertex_index,
+                property_name,
+            } => {
+                19u8.encode(writer)?;
+                vertex_index.encode(writer)?;
+                property_name.encode(writer)
+            }
+            GraphMutation::AddVertexLabel {
+                vertex_index,
+                vertex_label,
+            } => {
+                20u8.encode(writer)?;
+                vertex_index.encode(writer)?;
+                vertex_label.encode(writer)
+            }
+            GraphMutation::RemoveVertexLabel {
+                vertex_index,
+                vertex_label,
+            } => {
+                21u8.encode(writer)?;
+                vertex_index.encode(writer)?;
+                vertex_label.encode(writer)
+            }
+            GraphMutation::SetSchema(schema) => {
+                22u8.encode(writer)?;
+                schema.encode(writer)
+            }
+        }
+    }
+}
+
+impl Decode for GraphMutation {
+    fn decode(reader: &mut impl Read) -> Result<Self, GraphComputingError> {
+        match u8::decode(reader)? {
+            1 => Ok(GraphMutation::UpdateVertexValue {
+                vertex_index: ElementIndex::decode(reader)?,
+                vertex_value: VertexValue::decode(reader)?,
+            }),
+            2 => Ok(GraphMutation::DeleteVertex(ElementIndex::decode(reader)?)),
+            3 => Ok(GraphMutation::AddEdgeType {
+                edge_type: EdgeType::decode(reader)?,
+                edge_weight_type: EdgeWeightType::decode(reader)?,
+            }),
+            4 => Ok(GraphMutation::DropEdgeType(ElementIndex::decode(reader)?)),
+            5 => Ok(GraphMutation::AddEdge {
+                edge_type_index: ElementIndex::decode(reader)?,
+                from_vertex_index: ElementIndex::decode(reader)?,
+                to_vertex_index: ElementIndex::decode(reader)?,
+            }),
+            6 => Ok(GraphMutation::DeleteEdge {
+                edge_type_index: ElementIndex::decode(reader)?,
+                from_vertex_index: ElementIndex::decode(reader)?,
+                to_vertex_index: ElementIndex::decode(reader)?,
+            }),
+            7 => Ok(GraphMutation::Transaction(Vec::<GraphMutation>::decode(
+                reader,
+            )?)),
+            8 => Ok(GraphMutation::AddInferredEdges {
+                edge_type_index: ElementIndex::decode(reader)?,
+                from_vertex_indices: Vec::<ElementIndex>::decode(reader)?,
+                to_vertex_indices: Vec::<ElementIndex>::decode(reader)?,
+            }),
+            9 => Ok(GraphMutation::DeleteInferredEdges),
+            10 => Ok(GraphMutation::AddEdges {
+                edge_type_index: ElementIndex::decode(reader)?,
+                from_vertex_indices: Vec::<ElementIndex>::decode(reader)?,
+                to_vertex_indices: Vec::<ElementIndex>::decode(reader)?,
+            }),
+            11 => Ok(GraphMutation::SetEdgeWeight {
+                edge_type_index: ElementIndex::decode(reader)?,
+                from_vertex_index: ElementIndex::decode(reader)?,
+                to_vertex_index: ElementIndex::decode(reader)?,
+                weight: EdgeWeight::decode(reader)?,
+            }),
+            12 => Ok(GraphMutation::SetEdgeValue {
+                edge_type_index: ElementIndex::decode(reader)?,
+                from_vertex_index: ElementIndex::decode(reader)?,
+                to_vertex_index: ElementIndex::decode(reader)?,
+                value: EdgeValue::decode(reader)?,
+            }),
+            13 => Ok(GraphMutation::DeleteEdgeValue {
+                edge_type_index: ElementIndex::decode(reader)?,
+                from_vertex_index: ElementIndex::decode(reader)?,
+                to_vertex_index: ElementIndex::decode(reader)?,
+            }),
+            15 => Ok(GraphMutation::SetIntegerEdgeWeight {
+                edge_type_index: ElementIndex::decode(reader)?,
+                from_vertex_index: ElementIndex::decode(reader)?,
+                to_vertex_index: ElementIndex::decode(reader)?,
+                weight: IntegerEdgeWeight::decode(reader)?,
+            }),
+            16 => Ok(GraphMutation::AddOrReplaceVertex(Vertex::decode(reader)?)),
+            17 => Ok(GraphMutation::UpdateVertex(Vertex::decode(reader)?)),
+            18 => Ok(GraphMutation::UpdateVertexProperty {
+                vertex_index: ElementIndex::decode(reader)?,
+                property_name: PropertyName::decode(reader)?,
+                value: VertexValue::decode(reader)?,
+            }),
+            19 => Ok(GraphMutation::RemoveVertexProperty {
+                vertex_index: ElementIndex::decode(reader)?,
+                property_name: PropertyName::decode(reader)?,
+            }),
+            20 => Ok(GraphMutation::AddVertexLabel {
+                vertex_index: ElementIndex::decode(reader)?,
+                vertex_label: VertexLabel::decode(reader)?,
+            }),
+            21 => Ok(GraphMutation::RemoveVertexLabel {
+                vertex_index: ElementIndex::decode(reader)?,
+                vertex_label: VertexLabel::decode(reader)?,
+            }),
+            22 => Ok(GraphMutation::SetSchema(Schema::decode(reader)?)),
+            tag => Err(corrupt_data_error(format!(
+                "Unknown graph mutation tag: {}",
+                tag
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::GraphComputingErrorType;
+    use crate::graph::edge::DirectedEdgeDefinedByKeys;
+    use crate::graph::graph::GraphTrait;
+    use crate::graph::schema::EdgeTypeConstraint;
+    use crate::operations::infer_edges::{InferenceRule, RuleAtom};
+    use crate::operations::read_edge::ReadEdge;
+    use crate::operations::read_vertex_value::ReadVertexValue;
+    use crate::operations::transaction::Transaction;
+
+    struct TemporaryDirectory {
+        path: PathBuf,
+    }
+
+    impl TemporaryDirectory {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "cairn_knowledge_graph_{}_{}",
+                name,
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&path);
+            Self { path }
+        }
+    }
+
+    impl Drop for TemporaryDirectory {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.path);
+        }
+    }
+
+    fn add_test_data(graph: &mut Graph) {
+        graph
+            .add_new_vertex(Vertex::new(String::from("1"), 1u8.into()))
+            .unwrap();
+        graph
+            .add_new_vertex(Vertex::new(String::from("2"), 2u8.into()))
+            .unwrap();
+        graph
+            .add_new_vertex(Vertex::new(String::from("3"), 3u8.into()))
+            .unwrap();
+        graph
+            .add_edge_and_edge_type_using_keys(DirectedEdgeDefinedByKeys::new(
+                String::from("1"),
+                String::from("smaller_than"),
+                String::from("2"),
+            ))
+            .unwrap();
+        graph
+            .add_edge_using_keys(DirectedEdgeDefinedByKeys::new(
+                String::from("2"),
+                String::from("smaller_than"),
+                String::from("3"),
+            ))
+            .unwrap();
+        graph
+            .update_vertex(Vertex::new(String::from("3"), 3.0f32.into()))
+            .unwrap();
+        graph
+            .delete_vertex_and_connected_edges_by_key(String::from("1"))
+            .unwrap();
+    }
+
+    fn assert_test_data(graph: &Graph) {
+        assert!(!graph.is_valid_vertex_key(&String::from("1")));
+        assert!(graph.is_valid_vertex_key(&String::from("2")));
+        assert_eq!(
+            graph.vertex_value(&String::from("3")).unwrap(),
+            &VertexValue::FloatingPoint32Bit(3.0)
+        );
+        assert!(graph
+            .is_key_defined_edge_in_graph(&DirectedEdgeDefinedByKeys::new(
+                String::from("2"),
+                String::from("smaller_than"),
+                String::from("3"),
+            ))
+            .unwrap());
+    }
+
+    #[test]
+    fn replay_write_ahead_log() {
+        let directory = TemporaryDirectory::new("replay_write_ahead_log");
+        let vertex_index;
+        {
+            let mut graph = Graph::open(&directory.path).unwrap();
+            assert!(graph.is_durable());
+            add_test_data(&mut graph);
+            vertex_index = graph
+                .add_new_vertex(Vertex::new(String::from("4"), 4u8.into()))
+                .unwrap();
+        }
+
+        let graph = Graph::open(&directory.path).unwrap();
+        assert_test_data(&graph);
+        assert_eq!(graph.number_of_vertices().unwrap(), 3);
+        assert_eq!(
+            graph.vertex_value_by_index(vertex_index).unwrap(),
+            &VertexValue::UnsignedInteger8Bit(4)
+        );
+    }
+
+    #[test]
+    fn replay_write_ahead_log_after_checkpoint() {
+        let directory = TemporaryDirectory::new("replay_write_ahead_log_after_checkpoint");
+        {
+            let mut graph = Graph::open(&directory.path).unwrap();
+            add_test_data(&mut graph);
+            graph.checkpoint().unwrap();
+            graph
+                .delete_vertex_and_connected_edges_by_key(String::from("2"))
+                .unwrap();
+        }
+
+        let graph = Graph::open(&directory.path).unwrap();
+        assert_eq!(graph.number_of_vertices().unwrap(), 1);
+        assert!(!graph.is_valid_vertex_key(&String::from("2")));
+        assert!(graph.is_valid_vertex_key(&String::from("3")));
+    }
+
+    #[test]
+    fn discard_write_ahead_log_of_previous_generation() {
+        let directory = TemporaryDirectory::new("discard_write_ahead_log_of_previous_generation");
+        let log_path = directory.path.join(WRITE_AHEAD_LOG_FILE_NAME);
+        {
+            let mut graph = Graph::open(&directory.path).unwrap();
+            add_test_data(&mut graph);
+            let log_before_checkpoint = fs::read(&log_path).unwrap();
+            graph.checkpoint().unwrap();
+            // simulate a crash after writing the snapshot, but before replacing the log
+            fs::write(&log_path, log_before_checkpoint).unwrap();
+        }
+
+        let graph = Graph::open(&directory.path).unwrap();
+        assert_test_data(&graph);
+    }
+
+    #[test]
+    fn truncate_torn_final_record() {
+        let directory = TemporaryDirectory::new("truncate_torn_final_record");
+        let log_path = directory.path.join(WRITE_AHEAD_LOG_FILE_NAME);
+        {
+            let mut graph = Graph::open(&directory.path).unwrap();
+            add_test_data(&mut graph);
+        }
+        let valid_length = fs::metadata(&log_path).unwrap().len();
+        {
+            let mut log_file = OpenOptions::new().append(true).open(&log_path).unwrap();
+            log_file.write_all(&[100, 0, 0, 0, 1, 2, 3, 4, 0]).unwrap();
+        }
+
+        {
+            let mut graph = Graph::open(&directory.path).unwrap();
+            assert_test_data(&graph);
+            assert_eq!(fs::metadata(&log_path).unwrap().len(), valid_length);
+            graph
+                .add_new_vertex(Vertex::new(String::from("4"), 4u8.into()))
+                .unwrap();
+        }
+
+        let graph = Graph::open(&directory.path).unwrap();
+        assert_test_data(&graph);
+        assert!(graph.is_valid_vertex_key(&String::from("4")));
+    }
+
+    #[test]
+    fn poison_graph_when_append_fails() {
+        let directory = TemporaryDirectory::new("poison_graph_when_append_fails");
+        let log_path = directory.path.join(WRITE_AHEAD_LOG_FILE_NAME);
+        {
+            let mut graph = Graph::open(&directory.path).unwrap();
+            add_test_data(&mut graph);
+            // A log file opened for reading only fails every write
+            graph.write_ahead_log_mut_ref().as_mut().unwrap().log_file =
+                File::open(&log_path).unwrap();
+
+            assert_eq!(
+                graph
+                    .add_new_vertex(Vertex::new(String::from("4"), 4u8.into()))
+                    .unwrap_err()
+                    .error_type(),
+                GraphComputingErrorType::SystemErrorType(SystemErrorType::Io)
+            );
+            assert_eq!(
+                graph
+                    .add_new_vertex(Vertex::new(String::from("5"), 5u8.into()))
+                    .unwrap_err()
+                    .error_type(),
+                GraphComputingErrorType::SystemErrorType(SystemErrorType::PoisonedData)
+            );
+            assert!(!graph.is_valid_vertex_key(&String::from("5")));
+            assert_eq!(
+                graph.checkpoint().unwrap_err().error_type(),
+                GraphComputingErrorType::SystemErrorType(SystemErrorType::PoisonedData)
+            );
+        }
+
+        let graph = Graph::open(&directory.path).unwrap();
+        assert_test_data(&graph);
+        assert!(!graph.is_valid_vertex_key(&String::from("4")));
+    }
+
+    #[test]
+    fn reject_corrupt_record_followed_by_valid_record() {
+        let directory = TemporaryDirectory::new("reject_corrupt_record_followed_by_valid_record");
+        let log_path = directory.path.join(WRITE_AHEAD_LOG_FILE_NAME);
+        {
+            let mut graph = Graph::open(&directory.path).unwrap();
+            add_test_data(&mut graph);
+        }
+        let mut log = fs::read(&log_path).unwrap();
+        // corrupt the payload of the first record
+        log[(WRITE_AHEAD_LOG_HEADER_LENGTH + RECORD_HEADER_LENGTH) as usize + 1] ^= 0xFF;
+        fs::write(&log_path, log).unwrap();
+
+        assert!(Graph::open(&directory.path).is_err());
+    }
+
+    #[test]
+    fn reject_unsupported_format_version() {
+        let directory = TemporaryDirectory::new("reject_unsupported_format_version");
+        let log_path = directory.path.join(WRITE_AHEAD_LOG_FILE_NAME);
+        {
+            let mut graph = Graph::open(&directory.path).unwrap();
+            add_test_data(&mut graph);
+        }
+        let mut log = fs::read(&log_path).unwrap();
+        log[WRITE_AHEAD_LOG_MAGIC_BYTES.len()..WRITE_AHEAD_LOG_MAGIC_BYTES.len() + 4]
+            .copy_from_slice(&0u32.to_le_bytes());
+        fs::write(&log_path, log).unwrap();
+
+        assert_eq!(
+            Graph::open(&directory.path).unwrap_err().error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::UnsupportedWriteAheadLogVersion)
+        );
+    }
+
+    #[test]
+    fn replay_committed_transactions_only() {
+        let directory = TemporaryDirectory::new("replay_committed_transactions_only");
+        {
+            let mut graph = Graph::open(&directory.path).unwrap();
+            graph
+                .transaction(|transaction| {
+                    add_test_data(transaction);
+                    Ok(())
+                })
+                .unwrap();
+            let result = graph.transaction(|transaction| {
+                transaction.add_new_vertex(Vertex::new(String::from("4"), 4u8.into()))?;
+                assert!(transaction.checkpoint().is_err());
+                transaction.add_new_vertex(Vertex::new(String::from("4"), 4u8.into()))
+            });
+            assert!(result.is_err());
+        }
+
+        let graph = Graph::open(&directory.path).unwrap();
+        assert_test_data(&graph);
+        assert!(!graph.is_valid_vertex_key(&String::from("4")));
+    }
+
+    #[test]
+    fn replay_inferred_edges() {
+        let directory = TemporaryDirectory::new("replay_inferred_edges");
+        let inferred_edge = DirectedEdgeDefinedByKeys::new(
+            String::from("3"),
+            String::from("larger_than"),
+            String::from("2"),
+        );
+        {
+            let mut graph = Graph::open(&directory.path).unwrap();
+            add_test_data(&mut graph);
+            graph
+                .infer_edges(&[InferenceRule::new(
+                    "larger_than",
+                    vec![RuleAtom::inverse("smaller_than")],
+                )
+                .unwrap()])
+                .unwrap();
+        }
+
+        let mut graph = Graph::open(&directory.path).unwrap();
+        assert!(graph
+            .is_inferred_edge_defined_by_keys(&inferred_edge)
+            .unwrap());
+        graph.checkpoint().unwrap();
+        drop(graph);
+
+        let mut graph = Graph::open(&directory.path).unwrap();
+        assert!(graph
+            .is_inferred_edge_defined_by_keys(&inferred_edge)
+            .unwrap());
+        graph.delete_inferred_edges().unwrap();
+        drop(graph);
+
+        let graph = Graph::open(&directory.path).unwrap();
+        assert!(!graph.is_key_defined_edge_in_graph(&inferred_edge).unwrap());
+        assert_test_data(&graph);
+    }
+
+    #[test]
+    fn replay_edge_weight() {
+        let directory = TemporaryDirectory::new("replay_edge_weight");
+        let weighted_edge = DirectedEdgeDefinedByKeys::new(
+            String::from("2"),
+            String::from("distance"),
+            String::from("3"),
+        );
+        {
+            let mut graph = Graph::open(&directory.path).unwrap();
+            add_test_data(&mut graph);
+            graph
+                .add_new_edge_type_with_weight_type(
+                    String::from("distance"),
+                    EdgeWeightType::FloatingPoint64Bit,
+                )
+                .unwrap();
+            graph.add_edge_using_keys(weighted_edge.clone()).unwrap();
+            graph
+                .set_edge_weight_using_keys(&weighted_edge, 7.5)
+                .unwrap();
+        }
+
+        let graph = Graph::open(&directory.path).unwrap();
+        assert_eq!(
+            graph.edge_weight_defined_by_keys(&weighted_edge).unwrap(),
+            7.5
+        );
+        assert_test_data(&graph);
+    }
+
+    #[test]
+    fn replay_integer_edge_weight() {
+        let directory = TemporaryDirectory::new("replay_integer_edge_weight");
+        let counted_edge = DirectedEdgeDefinedByKeys::new(
+            String::from("2"),
+            String::from("occurrences"),
+            String::from("3"),
+        );
+        {
+            let mut graph = Graph::open(&directory.path).unwrap();
+            add_test_data(&mut graph);
+            graph
+                .add_new_edge_type_with_weight_type(
+                    String::from("occurrences"),
+                    EdgeWeightType::Integer64Bit,
+                )
+                .unwrap();
+            graph.add_edge_using_keys(counted_edge.clone()).unwrap();
+            graph
+                .set_integer_edge_weight_using_keys(&counted_edge, 42)
+                .unwrap();
+        }
+
+        let graph = Graph::open(&directory.path).unwrap();
+        assert_eq!(
+            graph.edge_weight_type("occurrences").unwrap(),
+            EdgeWeightType::Integer64Bit
+        );
+        assert_eq!(
+            graph
+                .integer_edge_weight_defined_by_keys(&counted_edge)
+                .unwrap(),
+            42
+        );
+        assert_test_data(&graph);
+    }
+
+    #[test]
+    fn replay_vertex_properties() {
+        let directory = TemporaryDirectory::new("replay_vertex_properties");
+        {
+            let mut graph = Graph::open(&directory.path).unwrap();
+            add_test_data(&mut graph);
+            graph
+                .add_new_vertex(
+                    Vertex::new(String::from("4"), 4u8.into())
+                        .with_property(String::from("parity"), String::from("even").into())
+                        .with_property(String::from("prime"), false.into()),
+                )
+                .unwrap();
+            graph
+                .update_vertex_property("2", String::from("prime"), true.into())
+                .unwrap();
+            graph.remove_vertex_property("4", "prime").unwrap();
+        }
+
+        let graph = Graph::open(&directory.path).unwrap();
+        assert_eq!(
+            graph.vertex_property(&String::from("2"), "prime").unwrap(),
+            Some(&VertexValue::Boolean(true))
+        );
+        assert_eq!(
+            graph.vertex_property(&String::from("4"), "parity").unwrap(),
+            Some(&VertexValue::String(String::from("even")))
+        );
+        assert_eq!(
+            graph.vertex_property(&String::from("4"), "prime").unwrap(),
+            None
+        );
+        assert_test_data(&graph);
+    }
+
+    #[test]
+    fn replay_vertex_labels() {
+        let directory = TemporaryDirectory::new("replay_vertex_labels");
+        {
+            let mut graph = Graph::open(&directory.path).unwrap();
+            add_test_data(&mut graph);
+            for (vertex_key, vertex_label) in [("2", "even"), ("3", "odd"), ("3", "prime")] {
+                graph
+                    .add_vertex_label(vertex_key, String::from(vertex_label))
+                    .unwrap();
+            }
+            graph.add_vertex_label("2", String::from("prime")).unwrap();
+            graph.remove_vertex_label("2", "even").unwrap();
+        }
+
+        let graph = Graph::open(&directory.path).unwrap();
+        assert_eq!(graph.vertex_labels("2").unwrap(), vec!["prime"]);
+        assert_eq!(graph.vertex_labels("3").unwrap(), vec!["odd", "prime"]);
+        assert_test_data(&graph);
+    }
+
+    #[test]
+    fn replay_schema() {
+        let directory = TemporaryDirectory::new("replay_schema");
+        let schema = Schema::new().with_edge_type_constraint(
+            "smaller_than",
+            EdgeTypeConstraint::new().with_from_vertex_label("number"),
+        );
+        {
+            let mut graph = Graph::open(&directory.path).unwrap();
+            add_test_data(&mut graph);
+            graph.add_vertex_label("2", String::from("number")).unwrap();
+            graph.set_schema(schema.clone()).unwrap();
+        }
+
+        let mut graph = Graph::open(&directory.path).unwrap();
+        assert_eq!(graph.schema(), &schema);
+        assert_eq!(
+            graph
+                .add_edge_using_keys(DirectedEdgeDefinedByKeys::new(
+                    String::from("3"),
+                    String::from("smaller_than"),
+                    String::from("2"),
+                ))
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::SchemaViolation)
+        );
+        assert_test_data(&graph);
+    }
+
+    #[test]
+    fn replay_edge_values() {
+        let directory = TemporaryDirectory::new("replay_edge_values");
+        let edge_with_value = DirectedEdgeDefinedByKeys::new_with_value(
+            String::from("3"),
+            String::from("larger_than"),
+            String::from("2"),
+            String::from("derived").into(),
+        );
+        let edge_with_deleted_value = DirectedEdgeDefinedByKeys::new(
+            String::from("2"),
+            String::from("smaller_than"),
+            String::from("3"),
+        );
+        {
+            let mut graph = Graph::open(&directory.path).unwrap();
+            add_test_data(&mut graph);
+            graph
+                .add_edge_and_edge_type_using_keys(edge_with_value.clone())
+                .unwrap();
+            graph
+                .set_edge_value_using_keys(&edge_with_deleted_value, 0.5f64.into())
+                .unwrap();
+            graph
+                .delete_edge_value_using_keys(&edge_with_deleted_value)
+                .unwrap();
+        }
+
+        let graph = Graph::open(&directory.path).unwrap();
+        assert_eq!(
+            graph.edge_value_defined_by_keys(&edge_with_value).unwrap(),
+            edge_with_value.value_ref()
+        );
+        assert_eq!(
+            graph
+                .edge_value_defined_by_keys(&edge_with_deleted_value)
+                .unwrap(),
+            None
+        );
+        assert_test_data(&graph);
+    }
+
+    #[test]
+    fn cloned_graph_is_not_durable() {
+        let directory = TemporaryDirectory::new("cloned_graph_is_not_durable");
+        let graph = Graph::open(&directory.path).unwrap();
+        assert!(!graph.clone().is_durable());
+    }
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::graph::edge::EdgeType;
+
+/// Position in the query text. Lines and columns start at 1, columns count characters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+}
+
+/// `MATCH <patterns> [WHERE <condition>] RETURN [DISTINCT] <return items>`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Query {
+    pub patterns: Vec<Pattern>,
+    pub condition: Option<Condition>,
+    pub distinct: bool,
+    pub return_items: Vec<PropertyAccess>,
+}
+
+/// A path of vertices connected by edges, e.g. `(a)-[:is_a]->(b)<-[:sign]-(c)`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pattern {
+    pub start: VertexPattern,
+    pub steps: Vec<(EdgePattern, VertexPattern)>,
+}
+
+/// A vertex without a variable, `()`, matches any vertex.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VertexPattern {
+    pub variable: Option<Variable>,
+    pub position: Position,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EdgePattern {
+    pub edge_type: EdgeType,
+    pub direction: EdgeDirection,
+    pub position: Position,
+}
+
+/// Direction of an edge, relative to the preceding vertex in the pattern.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EdgeDirection {
+    Outgoing,
+    Incoming,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Variable {
+    pub name: String,
+    pub position: Position,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Condition {
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+    Not(Box<Condition>),
+    Comparison(Comparison),
+}
+
+/// `<variable>.<property> <operator> <literal>`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub property_access: PropertyAccess,
+    pub operator: ComparisonOperator,
+    pub literal: Literal,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ComparisonOperator {
+    Equal,
+    NotEqual,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PropertyAccess {
+    pub variable: Variable,
+    pub property: Property,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Property {
+    Key,
+    Value,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Literal {
+    Null,
+    Boolean(bool),
+    Integer(i128),
+    Float(f64),
+    String(String),
+}
@@ -0,0 +1,543 @@
+use std::convert::TryFrom;
+
+use hashbrown::HashSet;
+
+use crate::error::GraphComputingError;
+use crate::graph::edge::adjacency_matrix::EdgeCoordinate;
+use crate::graph::graph::Graph;
+use crate::graph::vertex::{Vertex, VertexIndex, VertexValue};
+use crate::operations::select_edge_type::EdgeTypeSelectorTrait;
+use crate::operations::select_vertex::SelectVertex;
+use crate::operations::selection::edge_selection::EdgeSelection;
+use crate::operations::selection::operators::and::AndOperator;
+use crate::operations::selection::vertex_selection::VertexSelection;
+
+use super::ast::{ComparisonOperator, Literal, Property};
+use super::parser::parse_query;
+use super::planner::{plan_query, PlannedCondition, PlannedEdge, QueryPlan, VariableIndex};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryResult {
+    column_names: Vec<String>,
+    rows: Vec<Vec<VertexValue>>,
+}
+
+impl QueryResult {
+    /// Column names are the return items, e.g. "a.key"
+    pub fn column_names_ref(&self) -> &[String] {
+        &self.column_names
+    }
+
+    /// A returned key is a VertexValue::String
+    pub fn rows_ref(&self) -> &[Vec<VertexValue>] {
+        &self.rows
+    }
+}
+
+pub trait ExecuteQuery {
+    /// Executes a query, see parse_query() for the syntax.
+    ///
+    /// A numeric literal equals a vertex value of any numeric type with the same value.
+    /// `null` equals VertexValue::None.
+    fn execute_query(&self, query: &str) -> Result<QueryResult, GraphComputingError>;
+}
+
+impl ExecuteQuery for Graph {
+    fn execute_query(&self, query: &str) -> Result<QueryResult, GraphComputingError> {
+        let plan = plan_query(&parse_query(query)?)?;
+        execute_plan(self, &plan)
+    }
+}
+
+pub(crate) fn execute_plan(
+    graph: &Graph,
+    plan: &QueryPlan,
+) -> Result<QueryResult, GraphComputingError> {
+    QueryExecution::new(graph, plan)?.execute()
+}
+
+struct QueryExecution<'p, 'g> {
+    graph: &'g Graph,
+    plan: &'p QueryPlan,
+    edge_selections: Vec<EdgeSelection<'g>>,
+    negated_edge_selections: Vec<EdgeSelection<'g>>,
+    candidates: Vec<VertexSelection<'g>>,
+    bound_vertices: Vec<Option<VertexIndex>>,
+    rows: Vec<Vec<VertexValue>>,
+    distinct_rows: HashSet<Vec<DistinctValue>>,
+}
+
+// VertexValue does not implement Hash, as floating point values do not.
+// DISTINCT compares floating point values by their bits instead.
+#[derive(PartialEq, Eq, Hash)]
+enum DistinctValue {
+    None,
+    String(String),
+    Boolean(bool),
+    Integer8Bit(i8),
+    Integer16Bit(i16),
+    Integer32Bit(i32),
+    Integer64Bit(i64),
+    Integer128Bit(i128),
+    UnsignedInteger8Bit(u8),
+    UnsignedInteger16Bit(u16),
+    UnsignedInteger32Bit(u32),
+    UnsignedInteger64Bit(u64),
+    UnsignedInteger128Bit(u128),
+    FloatingPoint32Bit(u32),
+    FloatingPoint64Bit(u64),
+}
+
+impl From<&VertexValue> for DistinctValue {
+    fn from(value: &VertexValue) -> Self {
+        match value {
+            VertexValue::None => DistinctValue::None,
+            VertexValue::String(value) => DistinctValue::String(value.clone()),
+            VertexValue::Boolean(value) => DistinctValue::Boolean(*value),
+            VertexValue::Integer8Bit(value) => DistinctValue::Integer8Bit(*value),
+            VertexValue::Integer16Bit(value) => DistinctValue::Integer16Bit(*value),
+            VertexValue::Integer32Bit(value) => DistinctValue::Integer32Bit(*value),
+            VertexValue::Integer64Bit(value) => DistinctValue::Integer64Bit(*value),
+            VertexValue::Integer128Bit(value) => DistinctValue::Integer128Bit(*value),
+            VertexValue::UnsignedInteger8Bit(value) => DistinctValue::UnsignedInteger8Bit(*value),
+            VertexValue::UnsignedInteger16Bit(value) => DistinctValue::UnsignedInteger16Bit(*value),
+            VertexValue::UnsignedInteger32Bit(value) => DistinctValue::UnsignedInteger32Bit(*value),
+            VertexValue::UnsignedInteger64Bit(value) => DistinctValue::UnsignedInteger64Bit(*value),
+            VertexValue::UnsignedInteger128Bit(value) => {
+                DistinctValue::UnsignedInteger128Bit(*value)
+            }
+            VertexValue::FloatingPoint32Bit(value) => {
+                DistinctValue::FloatingPoint32Bit(value.to_bits())
+            }
+            VertexValue::FloatingPoint64Bit(value) => {
+                DistinctValue::FloatingPoint64Bit(value.to_bits())
+            }
+        }
+    }
+}
+
+impl<'p, 'g> QueryExecution<'p, 'g> {
+    fn new(graph: &'g Graph, plan: &'p QueryPlan) -> Result<Self, GraphComputingError> {
+        let mut edge_selections = Vec::with_capacity(plan.edges.len());
+        for edge in plan.edges.iter() {
+            edge_selections.push(graph.select_edge_type(edge.edge_type.clone())?);
+        }
+        let mut negated_edge_selections = Vec::with_capacity(plan.negated_edges.len());
+        for edge in plan.negated_edges.iter() {
+            negated_edge_selections.push(graph.select_edge_type(edge.edge_type.clone())?);
+        }
+
+        let mut candidates = Vec::with_capacity(plan.number_of_variables);
+        for vertex_condition in plan.vertex_conditions.iter() {
+            let all_vertices =
+                VertexSelection::new(graph, graph.index_mask_with_all_vertices().clone())?;
+            candidates.push(match vertex_condition {
+                None => all_vertices,
+                Some(condition) => {
+                    let mut selected_vertex_indices = Vec::new();
+                    for vertex_index in all_vertices.vertex_indices_ref()? {
+                        let vertex = graph.vertex_store_ref().get_ref(vertex_index)?;
+                        if is_satisfied(condition, &|_| Ok(vertex))? {
+                            selected_vertex_indices.push(vertex_index);
+                        }
+                    }
+                    graph.select_vertices_by_index(selected_vertex_indices)?
+                }
+            });
+        }
+
+        Ok(Self {
+            graph,
+            plan,
+            edge_selections,
+            negated_edge_selections,
+            candidates,
+            bound_vertices: vec![None; plan.number_of_variables],
+            rows: Vec::new(),
+            distinct_rows: HashSet::new(),
+        })
+    }
+
+    fn execute(mut self) -> Result<QueryResult, GraphComputingError> {
+        self.reduce_candidates()?;
+        self.bind_variable(0)?;
+        Ok(QueryResult {
+            column_names: self.plan.column_names.clone(),
+            rows: self.rows,
+        })
+    }
+
+    // Removes candidates without the edges that the pattern requires, until no more candidates are removed.
+    fn reduce_candidates(&mut self) -> Result<(), GraphComputingError> {
+        let mut is_reduced = true;
+        while is_reduced {
+            is_reduced = false;
+            for (edge, edge_selection) in self.plan.edges.iter().zip(self.edge_selections.iter()) {
+                let adjacency_matrix = edge_selection.adjacency_matrix_mask_ref();
+
+                let from_vertices = VertexSelection::new(
+                    self.graph,
+                    adjacency_matrix.get_from_vertex_index_mask_for_to_vertices(
+                        self.candidates[edge.to_variable].vertex_mask_ref(),
+                    )?,
+                )?;
+                is_reduced |=
+                    reduce_selection(&mut self.candidates[edge.from_variable], &from_vertices)?;
+
+                let to_vertices = VertexSelection::new(
+                    self.graph,
+                    adjacency_matrix.get_to_vertex_index_mask_for_from_vertices(
+                        self.candidates[edge.from_variable].vertex_mask_ref(),
+                    )?,
+                )?;
+                is_reduced |=
+                    reduce_selection(&mut self.candidates[edge.to_variable], &to_vertices)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn bind_variable(&mut self, position_in_order: usize) -> Result<(), GraphComputingError> {
+        if position_in_order == self.plan.variable_order.len() {
+            return self.add_row();
+        }
+        let variable = self.plan.variable_order[position_in_order];
+
+        let mut selection = self.candidates[variable].clone();
+        let mut self_loop_edges = Vec::new();
+        for (edge_index, (edge, edge_selection)) in self
+            .plan
+            .edges
+            .iter()
+            .zip(self.edge_selections.iter())
+            .enumerate()
+        {
+            if edge.from_variable == variable && edge.to_variable == variable {
+                self_loop_edges.push(edge_index);
+            } else if edge.to_variable == variable {
+                if let Some(from_vertex_index) = self.bound_vertices[edge.from_variable] {
+                    selection = selection.and(
+                        &edge_selection
+                            .select_vertices_connected_from_vertex_by_index(&from_vertex_index)?,
+                    )?;
+                }
+            } else if edge.from_variable == variable {
+                if let Some(to_vertex_index) = self.bound_vertices[edge.to_variable] {
+                    selection = selection.and(
+                        &edge_selection
+                            .select_vertices_connected_to_vertex_by_index(&to_vertex_index)?,
+                    )?;
+                }
+            }
+        }
+
+        for vertex_index in selection.vertex_indices_ref()? {
+            let mut has_self_loops = true;
+            for edge_index in self_loop_edges.iter() {
+                has_self_loops &= self.edge_selections[*edge_index]
+                    .adjacency_matrix_mask_ref()
+                    .is_edge(&EdgeCoordinate::new(
+                        vertex_index.index(),
+                        vertex_index.index(),
+                    ))?;
+            }
+            if has_self_loops {
+                self.bound_vertices[variable] = Some(vertex_index);
+                self.bind_variable(position_in_order + 1)?;
+            }
+        }
+        self.bound_vertices[variable] = None;
+        Ok(())
+    }
+
+    fn add_row(&mut self) -> Result<(), GraphComputingError> {
+        for (edge, edge_selection) in self
+            .plan
+            .negated_edges
+            .iter()
+            .zip(self.negated_edge_selections.iter())
+        {
+            if self.is_negated_edge_present(edge, edge_selection)? {
+                return Ok(());
+            }
+        }
+
+        let graph = self.graph;
+        let bound_vertices = &self.bound_vertices;
+        let bound_vertex = |variable: VariableIndex| -> Result<&'g Vertex, GraphComputingError> {
+            // All returned variables are bound when a row is added
+            graph
+                .vertex_store_ref()
+                .get_ref(bound_vertices[variable].unwrap())
+        };
+
+        if let Some(condition) = &self.plan.row_condition {
+            if !is_satisfied(condition, &bound_vertex)? {
+                return Ok(());
+            }
+        }
+
+        let mut row = Vec::with_capacity(self.plan.return_items.len());
+        for (variable, property) in self.plan.return_items.iter() {
+            let vertex = bound_vertex(*variable)?;
+            row.push(match property {
+                Property::Key => VertexValue::String(vertex.key_ref().to_owned()),
+                Property::Value => vertex.value_ref().clone(),
+            });
+        }
+
+        if self.plan.distinct
+            && !self
+                .distinct_rows
+                .insert(row.iter().map(DistinctValue::from).collect())
+        {
+            return Ok(());
+        }
+        self.rows.push(row);
+        Ok(())
+    }
+
+    // An unbound variable stands for any of its candidates
+    fn is_negated_edge_present(
+        &self,
+        edge: &PlannedEdge,
+        edge_selection: &EdgeSelection<'g>,
+    ) -> Result<bool, GraphComputingError> {
+        let to_vertices =
+            match (
+                self.bound_vertices[edge.from_variable],
+                self.bound_vertices[edge.to_variable],
+            ) {
+                (Some(from_vertex_index), Some(to_vertex_index)) => {
+                    return edge_selection.adjacency_matrix_mask_ref().is_edge(
+                        &EdgeCoordinate::new(from_vertex_index.index(), to_vertex_index.index()),
+                    );
+                }
+                (Some(from_vertex_index), None) => edge_selection
+                    .select_vertices_connected_from_vertex_by_index(&from_vertex_index)?
+                    .and(&self.candidates[edge.to_variable])?,
+                (None, Some(to_vertex_index)) => edge_selection
+                    .select_vertices_connected_to_vertex_by_index(&to_vertex_index)?
+                    .and(&self.candidates[edge.from_variable])?,
+                (None, None) if edge.from_variable == edge.to_variable => {
+                    for vertex_index in self.candidates[edge.from_variable].vertex_indices_ref()? {
+                        if edge_selection.adjacency_matrix_mask_ref().is_edge(
+                            &EdgeCoordinate::new(vertex_index.index(), vertex_index.index()),
+                        )? {
+                            return Ok(true);
+                        }
+                    }
+                    return Ok(false);
+                }
+                (None, None) => VertexSelection::new(
+                    self.graph,
+                    edge_selection
+                        .adjacency_matrix_mask_ref()
+                        .get_to_vertex_index_mask_for_from_vertices(
+                            self.candidates[edge.from_variable].vertex_mask_ref(),
+                        )?,
+                )?
+                .and(&self.candidates[edge.to_variable])?,
+            };
+        Ok(!to_vertices.vertex_indices_ref()?.is_empty())
+    }
+}
+
+// Returns true if the selection was reduced
+fn reduce_selection<'g>(
+    selection: &mut VertexSelection<'g>,
+    mask: &VertexSelection<'g>,
+) -> Result<bool, GraphComputingError> {
+    let number_of_selected_vertices = selection.vertex_indices_ref()?.len();
+    *selection = selection.and(mask)?;
+    Ok(selection.vertex_indices_ref()?.len() < number_of_selected_vertices)
+}
+
+fn is_satisfied<'g>(
+    condition: &PlannedCondition,
+    vertex: &impl Fn(VariableIndex) -> Result<&'g Vertex, GraphComputingError>,
+) -> Result<bool, GraphComputingError> {
+    Ok(match condition {
+        PlannedCondition::And(left_hand_side, right_hand_side) => {
+            is_satisfied(left_hand_side, vertex)? && is_satisfied(right_hand_side, vertex)?
+        }
+        PlannedCondition::Or(left_hand_side, right_hand_side) => {
+            is_satisfied(left_hand_side, vertex)? || is_satisfied(right_hand_side, vertex)?
+        }
+        PlannedCondition::Not(condition) => !is_satisfied(condition, vertex)?,
+        PlannedCondition::Comparison {
+            variable,
+            property,
+            operator,
+            literal,
+        } => {
+            let vertex = vertex(*variable)?;
+            let is_equal = match (property, literal) {
+                (Property::Key, Literal::String(key)) => vertex.key_ref() == key,
+                (Property::Key, _) => false,
+                (Property::Value, literal) => is_equal_value(literal, vertex.value_ref()),
+            };
+            match operator {
+                ComparisonOperator::Equal => is_equal,
+                ComparisonOperator::NotEqual => !is_equal,
+            }
+        }
+    })
+}
+
+fn is_equal_value(literal: &Literal, vertex_value: &VertexValue) -> bool {
+    match (literal, vertex_value) {
+        (Literal::Null, VertexValue::None) => true,
+        (Literal::Boolean(literal), VertexValue::Boolean(value)) => literal == value,
+        (Literal::String(literal), VertexValue::String(value)) => literal == value,
+        (Literal::Float(literal), VertexValue::FloatingPoint32Bit(value)) => {
+            *literal as f32 == *value
+        }
+        (Literal::Float(literal), VertexValue::FloatingPoint64Bit(value)) => literal == value,
+        (Literal::Integer(literal), VertexValue::FloatingPoint32Bit(value)) => {
+            *literal as f64 == *value as f64
+        }
+        (Literal::Integer(literal), VertexValue::FloatingPoint64Bit(value)) => {
+            *literal as f64 == *value
+        }
+        (Literal::Integer(literal), value) => integer_value(value) == Some(*literal),
+        (Literal::Float(literal), value) => {
+            integer_value(value).is_some_and(|value| value as f64 == *literal)
+        }
+        _ => false,
+    }
+}
+
+fn integer_value(vertex_value: &VertexValue) -> Option<i128> {
+    match vertex_value {
+        VertexValue::Integer8Bit(value) => Some(*value as i128),
+        VertexValue::Integer16Bit(value) => Some(*value as i128),
+        VertexValue::Integer32Bit(value) => Some(*value as i128),
+        VertexValue::Integer64Bit(value) => Some(*value as i128),
+        VertexValue::Integer128Bit(value) => Some(*value),
+        VertexValue::UnsignedInteger8Bit(value) => Some(*value as i128),
+        VertexValue::UnsignedInteger16Bit(value) => Some(*value as i128),
+        VertexValue::UnsignedInteger32Bit(value) => Some(*value as i128),
+        VertexValue::UnsignedInteger64Bit(value) => Some(*value as i128),
+        VertexValue::UnsignedInteger128Bit(value) => i128::try_from(*value).ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::{GraphComputingErrorType, UserErrorType};
+    use crate::tests::standard_graph_for_testing::standard_graph_for_testing;
+
+    fn sorted_keys(result: &QueryResult) -> Vec<String> {
+        let mut keys: Vec<String> = result
+            .rows_ref()
+            .iter()
+            .map(|row| match &row[0] {
+                VertexValue::String(key) => key.to_owned(),
+                value => panic!("Unexpected value {:?}", value),
+            })
+            .collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    #[test]
+    fn match_where_return() {
+        let graph = standard_graph_for_testing();
+
+        let result = graph
+            .execute_query("MATCH (a)-[:is_a]->(b) WHERE b.value = \"integer\" RETURN a.key")
+            .unwrap();
+        assert_eq!(result.column_names_ref(), &[String::from("a.key")]);
+        assert_eq!(
+            sorted_keys(&result),
+            vec!["-1", "0", "1", "1_duplicate", "2"]
+        );
+    }
+
+    #[test]
+    fn match_path() {
+        let graph = standard_graph_for_testing();
+
+        let result = graph
+            .execute_query(
+                "MATCH (a)-[:smaller_than]->(b)-[:smaller_than]->(c)
+                 WHERE a.key = \"-1.1\" AND c.value = 2
+                 RETURN b.key, c.key",
+            )
+            .unwrap();
+        assert_eq!(sorted_keys(&result), vec!["-1", "0", "1", "1.1", "1.2"]);
+        for row in result.rows_ref() {
+            assert_eq!(row[1], VertexValue::String(String::from("2")));
+        }
+    }
+
+    #[test]
+    fn match_multiple_patterns_with_row_condition() {
+        let graph = standard_graph_for_testing();
+
+        let result = graph
+            .execute_query(
+                "MATCH (a)-[:sign]->(s), (t)<-[:is_a]-(a)
+                 WHERE s.key = 'negative' OR t.key = 'natural_number'
+                 RETURN DISTINCT a.key",
+            )
+            .unwrap();
+        assert_eq!(
+            sorted_keys(&result),
+            vec!["-1", "-1.1", "1", "1_duplicate", "2"]
+        );
+    }
+
+    #[test]
+    fn match_value_and_self_loop() {
+        let graph = standard_graph_for_testing();
+
+        let result = graph
+            .execute_query("MATCH (a)-[:is_a]->(b) WHERE a.value = -1.1 RETURN b.value")
+            .unwrap();
+        assert_eq!(
+            result.rows_ref(),
+            &[vec![VertexValue::String(String::from("real_number"))]]
+        );
+
+        let result = graph
+            .execute_query("MATCH (a)-[:equal_to]->(a) RETURN a.key")
+            .unwrap();
+        assert_eq!(sorted_keys(&result), vec!["1_duplicate"]);
+    }
+
+    #[test]
+    fn return_distinct() {
+        let graph = standard_graph_for_testing();
+
+        let result = graph
+            .execute_query("MATCH ()-[:equal_to]->(b) RETURN b.value")
+            .unwrap();
+        assert_eq!(result.rows_ref().len(), 2);
+
+        let result = graph
+            .execute_query("MATCH ()-[:equal_to]->(b) RETURN DISTINCT b.value")
+            .unwrap();
+        assert_eq!(
+            result.rows_ref(),
+            &[vec![VertexValue::UnsignedInteger8Bit(1)]]
+        );
+    }
+
+    #[test]
+    fn match_undefined_edge_type() {
+        let graph = standard_graph_for_testing();
+
+        assert_eq!(
+            graph
+                .execute_query("MATCH (a)-[:does_not_exist]->(b) RETURN a.key")
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::EdgeTypeDoesNotExist)
+        );
+    }
+}
@@ -0,0 +1,5 @@
+pub mod ast;
+pub mod executor;
+pub mod parser;
+pub mod pattern;
+pub(crate) mod planner;
@@ -0,0 +1,587 @@
+use crate::error::{GraphComputingError, UserError, UserErrorType};
+
+use super::ast::{
+    Comparison, ComparisonOperator, Condition, EdgeDirection, EdgePattern, Literal, Pattern,
+    Position, Property, PropertyAccess, Query, Variable, VertexPattern,
+};
+
+pub(crate) fn query_syntax_error(position: Position, explanation: String) -> GraphComputingError {
+    UserError::new(
+        UserErrorType::QuerySyntax {
+            line: position.line,
+            column: position.column,
+        },
+        format!(
+            "{} (line {}, column {})",
+            explanation, position.line, position.column
+        ),
+        None,
+    )
+    .into()
+}
+
+/// Parses a query of the form
+///
+/// ```text
+/// MATCH (a)-[:is_a]->(b), (a)-[:sign]->(c)
+/// WHERE b.value = "integer" AND NOT c.key = "negative"
+/// RETURN DISTINCT a.key, a.value
+/// ```
+///
+/// Keywords are case-insensitive. Edge types and variables that are not identifiers can be quoted with backticks.
+pub fn parse_query(query: &str) -> Result<Query, GraphComputingError> {
+    Parser::new(tokenize(query)?).parse_query()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum TokenKind {
+    Identifier(String),
+    QuotedIdentifier(String),
+    String(String),
+    Integer(i128),
+    Float(f64),
+    LeftParenthesis,
+    RightParenthesis,
+    LeftBracket,
+    RightBracket,
+    Colon,
+    Comma,
+    Dot,
+    Equal,
+    NotEqual,
+    Minus,
+    LessThan,
+    GreaterThan,
+    End,
+}
+
+impl TokenKind {
+    fn describe(&self) -> String {
+        match self {
+            TokenKind::Identifier(identifier) => format!("'{}'", identifier),
+            TokenKind::QuotedIdentifier(identifier) => format!("`{}`", identifier),
+            TokenKind::String(string) => format!("\"{}\"", string),
+            TokenKind::Integer(integer) => integer.to_string(),
+            TokenKind::Float(float) => float.to_string(),
+            TokenKind::LeftParenthesis => String::from("'('"),
+            TokenKind::RightParenthesis => String::from("')'"),
+            TokenKind::LeftBracket => String::from("'['"),
+            TokenKind::RightBracket => String::from("']'"),
+            TokenKind::Colon => String::from("':'"),
+            TokenKind::Comma => String::from("','"),
+            TokenKind::Dot => String::from("'.'"),
+            TokenKind::Equal => String::from("'='"),
+            TokenKind::NotEqual => String::from("'<>'"),
+            TokenKind::Minus => String::from("'-'"),
+            TokenKind::LessThan => String::from("'<'"),
+            TokenKind::GreaterThan => String::from("'>'"),
+            TokenKind::End => String::from("end of query"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Token {
+    kind: TokenKind,
+    position: Position,
+}
+
+fn tokenize(query: &str) -> Result<Vec<Token>, GraphComputingError> {
+    let characters: Vec<char> = query.chars().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+    let mut line = 1;
+    let mut column = 1;
+
+    while index < characters.len() {
+        let character = characters[index];
+        let position = Position::new(line, column);
+        let start_index = index;
+
+        if character == '\n' {
+            index += 1;
+            line += 1;
+            column = 1;
+            continue;
+        }
+        if character.is_whitespace() {
+            index += 1;
+            column += 1;
+            continue;
+        }
+
+        let kind = match character {
+            '(' => Some(TokenKind::LeftParenthesis),
+            ')' => Some(TokenKind::RightParenthesis),
+            '[' => Some(TokenKind::LeftBracket),
+            ']' => Some(TokenKind::RightBracket),
+            ':' => Some(TokenKind::Colon),
+            ',' => Some(TokenKind::Comma),
+            '.' => Some(TokenKind::Dot),
+            '=' => Some(TokenKind::Equal),
+            '>' => Some(TokenKind::GreaterThan),
+            _ => None,
+        };
+        let kind = match kind {
+            Some(kind) => {
+                index += 1;
+                kind
+            }
+            None => match character {
+                '<' if characters.get(index + 1) == Some(&'>') => {
+                    index += 2;
+                    TokenKind::NotEqual
+                }
+                '!' if characters.get(index + 1) == Some(&'=') => {
+                    index += 2;
+                    TokenKind::NotEqual
+                }
+                '<' => {
+                    index += 1;
+                    TokenKind::LessThan
+                }
+                '-' if !characters
+                    .get(index + 1)
+                    .is_some_and(|next| next.is_ascii_digit()) =>
+                {
+                    index += 1;
+                    TokenKind::Minus
+                }
+                '-' | '0'..='9' => {
+                    index += 1;
+                    while index < characters.len()
+                        && (characters[index].is_ascii_digit()
+                            || characters[index] == '.'
+                            || characters[index] == 'e'
+                            || characters[index] == 'E'
+                            || ((characters[index] == '-' || characters[index] == '+')
+                                && (characters[index - 1] == 'e' || characters[index - 1] == 'E')))
+                    {
+                        index += 1;
+                    }
+                    parse_number(&characters[start_index..index], position)?
+                }
+                '"' | '\'' => {
+                    let (string, end_index) = read_string(&characters, index, position)?;
+                    index = end_index;
+                    TokenKind::String(string)
+                }
+                '`' => match characters[index + 1..].iter().position(|&c| c == '`') {
+                    Some(length) => {
+                        let identifier: String =
+                            characters[index + 1..index + 1 + length].iter().collect();
+                        index += length + 2;
+                        TokenKind::QuotedIdentifier(identifier)
+                    }
+                    None => {
+                        return Err(query_syntax_error(
+                            position,
+                            String::from("Unterminated quoted identifier"),
+                        ))
+                    }
+                },
+                character if character.is_alphabetic() || character == '_' => {
+                    while index < characters.len()
+                        && (characters[index].is_alphanumeric() || characters[index] == '_')
+                    {
+                        index += 1;
+                    }
+                    TokenKind::Identifier(characters[start_index..index].iter().collect())
+                }
+                character => {
+                    return Err(query_syntax_error(
+                        position,
+                        format!("Unexpected character '{}'", character),
+                    ))
+                }
+            },
+        };
+
+        // Tokens other than strings and quoted identifiers do not contain line breaks
+        for &consumed_character in characters[start_index..index].iter() {
+            if consumed_character == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+        }
+        tokens.push(Token { kind, position });
+    }
+
+    tokens.push(Token {
+        kind: TokenKind::End,
+        position: Position::new(line, column),
+    });
+    Ok(tokens)
+}
+
+fn parse_number(characters: &[char], position: Position) -> Result<TokenKind, GraphComputingError> {
+    let number: String = characters.iter().collect();
+    let is_float = number.contains(['.', 'e', 'E']);
+    let kind = if is_float {
+        number.parse::<f64>().ok().map(TokenKind::Float)
+    } else {
+        number.parse::<i128>().ok().map(TokenKind::Integer)
+    };
+    match kind {
+        Some(kind) => Ok(kind),
+        None => Err(query_syntax_error(
+            position,
+            format!("Invalid number {}", number),
+        )),
+    }
+}
+
+// Returns the string and the index after the closing quote
+fn read_string(
+    characters: &[char],
+    start_index: usize,
+    position: Position,
+) -> Result<(String, usize), GraphComputingError> {
+    let quote = characters[start_index];
+    let mut string = String::new();
+    let mut index = start_index + 1;
+    while index < characters.len() {
+        match characters[index] {
+            '\\' => {
+                let escaped_character = match characters.get(index + 1) {
+                    Some('n') => '\n',
+                    Some('t') => '\t',
+                    Some('\\') => '\\',
+                    Some('"') => '"',
+                    Some('\'') => '\'',
+                    _ => {
+                        return Err(query_syntax_error(
+                            position,
+                            String::from("Invalid escape sequence in string"),
+                        ))
+                    }
+                };
+                string.push(escaped_character);
+                index += 2;
+            }
+            character if character == quote => return Ok((string, index + 1)),
+            character => {
+                string.push(character);
+                index += 1;
+            }
+        }
+    }
+    Err(query_syntax_error(
+        position,
+        String::from("Unterminated string"),
+    ))
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    index: usize,
+}
+
+impl Parser {
+    fn new(tokens: Vec<Token>) -> Self {
+        Self { tokens, index: 0 }
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.index]
+    }
+
+    fn next(&mut self) -> Token {
+        let token = self.tokens[self.index].clone();
+        if token.kind != TokenKind::End {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn unexpected_token_error(&self, expected: &str) -> GraphComputingError {
+        let token = self.peek();
+        query_syntax_error(
+            token.position,
+            format!("Expected {}, found {}", expected, token.kind.describe()),
+        )
+    }
+
+    fn expect(&mut self, kind: TokenKind) -> Result<Token, GraphComputingError> {
+        if self.peek().kind == kind {
+            Ok(self.next())
+        } else {
+            Err(self.unexpected_token_error(&kind.describe()))
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        match &self.peek().kind {
+            TokenKind::Identifier(identifier) => identifier.eq_ignore_ascii_case(keyword),
+            _ => false,
+        }
+    }
+
+    fn accept_keyword(&mut self, keyword: &str) -> bool {
+        if self.is_keyword(keyword) {
+            self.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), GraphComputingError> {
+        if self.accept_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.unexpected_token_error(keyword))
+        }
+    }
+
+    fn parse_query(&mut self) -> Result<Query, GraphComputingError> {
+        self.expect_keyword("MATCH")?;
+        let mut patterns = vec![self.parse_pattern()?];
+        while self.peek().kind == TokenKind::Comma {
+            self.next();
+            patterns.push(self.parse_pattern()?);
+        }
+
+        let condition = if self.accept_keyword("WHERE") {
+            Some(self.parse_or_condition()?)
+        } else {
+            None
+        };
+
+        self.expect_keyword("RETURN")?;
+        let distinct = self.accept_keyword("DISTINCT");
+        let mut return_items = vec![self.parse_property_access()?];
+        while self.peek().kind == TokenKind::Comma {
+            self.next();
+            return_items.push(self.parse_property_access()?);
+        }
+
+        self.expect(TokenKind::End)?;
+        Ok(Query {
+            patterns,
+            condition,
+            distinct,
+            return_items,
+        })
+    }
+
+    fn parse_pattern(&mut self) -> Result<Pattern, GraphComputingError> {
+        let start = self.parse_vertex_pattern()?;
+        let mut steps = Vec::new();
+        while self.peek().kind == TokenKind::Minus || self.peek().kind == TokenKind::LessThan {
+            let edge = self.parse_edge_pattern()?;
+            steps.push((edge, self.parse_vertex_pattern()?));
+        }
+        Ok(Pattern { start, steps })
+    }
+
+    fn parse_vertex_pattern(&mut self) -> Result<VertexPattern, GraphComputingError> {
+        let position = self.expect(TokenKind::LeftParenthesis)?.position;
+        let variable = match self.peek().kind {
+            TokenKind::RightParenthesis => None,
+            _ => Some(self.parse_variable()?),
+        };
+        self.expect(TokenKind::RightParenthesis)?;
+        Ok(VertexPattern { variable, position })
+    }
+
+    // -[:edge_type]-> or <-[:edge_type]-
+    fn parse_edge_pattern(&mut self) -> Result<EdgePattern, GraphComputingError> {
+        let position = self.peek().position;
+        let is_incoming = self.peek().kind == TokenKind::LessThan;
+        if is_incoming {
+            self.next();
+        }
+        self.expect(TokenKind::Minus)?;
+        self.expect(TokenKind::LeftBracket)?;
+        self.expect(TokenKind::Colon)?;
+        let edge_type = self.parse_name("an edge type")?;
+        self.expect(TokenKind::RightBracket)?;
+        self.expect(TokenKind::Minus)?;
+
+        let direction = if is_incoming {
+            EdgeDirection::Incoming
+        } else {
+            self.expect(TokenKind::GreaterThan)?;
+            EdgeDirection::Outgoing
+        };
+        Ok(EdgePattern {
+            edge_type,
+            direction,
+            position,
+        })
+    }
+
+    fn parse_name(&mut self, expected: &str) -> Result<String, GraphComputingError> {
+        match &self.peek().kind {
+            TokenKind::Identifier(name) | TokenKind::QuotedIdentifier(name) => {
+                let name = name.to_owned();
+                self.next();
+                Ok(name)
+            }
+            _ => Err(self.unexpected_token_error(expected)),
+        }
+    }
+
+    fn parse_variable(&mut self) -> Result<Variable, GraphComputingError> {
+        let position = self.peek().position;
+        let name = self.parse_name("a variable")?;
+        Ok(Variable { name, position })
+    }
+
+    fn parse_property_access(&mut self) -> Result<PropertyAccess, GraphComputingError> {
+        let variable = self.parse_variable()?;
+        self.expect(TokenKind::Dot)?;
+        let property = if self.accept_keyword("key") {
+            Property::Key
+        } else if self.accept_keyword("value") {
+            Property::Value
+        } else {
+            return Err(self.unexpected_token_error("'key' or 'value'"));
+        };
+        Ok(PropertyAccess { variable, property })
+    }
+
+    fn parse_or_condition(&mut self) -> Result<Condition, GraphComputingError> {
+        let mut condition = self.parse_and_condition()?;
+        while self.accept_keyword("OR") {
+            let right_hand_side = self.parse_and_condition()?;
+            condition = Condition::Or(Box::new(condition), Box::new(right_hand_side));
+        }
+        Ok(condition)
+    }
+
+    fn parse_and_condition(&mut self) -> Result<Condition, GraphComputingError> {
+        let mut condition = self.parse_not_condition()?;
+        while self.accept_keyword("AND") {
+            let right_hand_side = self.parse_not_condition()?;
+            condition = Condition::And(Box::new(condition), Box::new(right_hand_side));
+        }
+        Ok(condition)
+    }
+
+    fn parse_not_condition(&mut self) -> Result<Condition, GraphComputingError> {
+        if self.accept_keyword("NOT") {
+            return Ok(Condition::Not(Box::new(self.parse_not_condition()?)));
+        }
+        if self.peek().kind == TokenKind::LeftParenthesis {
+            self.next();
+            let condition = self.parse_or_condition()?;
+            self.expect(TokenKind::RightParenthesis)?;
+            return Ok(condition);
+        }
+
+        let property_access = self.parse_property_access()?;
+        let operator = match self.peek().kind {
+            TokenKind::Equal => ComparisonOperator::Equal,
+            TokenKind::NotEqual => ComparisonOperator::NotEqual,
+            _ => return Err(self.unexpected_token_error("'=' or '<>'")),
+        };
+        self.next();
+        let literal = self.parse_literal()?;
+        Ok(Condition::Comparison(Comparison {
+            property_access,
+            operator,
+            literal,
+        }))
+    }
+
+    fn parse_literal(&mut self) -> Result<Literal, GraphComputingError> {
+        let literal = match &self.peek().kind {
+            TokenKind::String(string) => Literal::String(string.to_owned()),
+            TokenKind::Integer(integer) => Literal::Integer(*integer),
+            TokenKind::Float(float) => Literal::Float(*float),
+            TokenKind::Identifier(identifier) if identifier.eq_ignore_ascii_case("true") => {
+                Literal::Boolean(true)
+            }
+            TokenKind::Identifier(identifier) if identifier.eq_ignore_ascii_case("false") => {
+                Literal::Boolean(false)
+            }
+            TokenKind::Identifier(identifier) if identifier.eq_ignore_ascii_case("null") => {
+                Literal::Null
+            }
+            _ => return Err(self.unexpected_token_error("a literal value")),
+        };
+        self.next();
+        Ok(literal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::GraphComputingErrorType;
+
+    fn syntax_error_position(query: &str) -> (usize, usize) {
+        match parse_query(query).unwrap_err().error_type() {
+            GraphComputingErrorType::UserErrorType(UserErrorType::QuerySyntax { line, column }) => {
+                (line, column)
+            }
+            error_type => panic!("Unexpected error type {:?}", error_type),
+        }
+    }
+
+    #[test]
+    fn parse_match_where_return() {
+        let query = parse_query(
+            "MATCH (a)-[:is_a]->(b)<-[:`is a`]-()\nWHERE b.value = \"integer\" AND NOT (a.key <> 'x' OR a.value = -1.5)\nreturn distinct a.key, b.value",
+        )
+        .unwrap();
+
+        assert_eq!(query.patterns.len(), 1);
+        let pattern = &query.patterns[0];
+        assert_eq!(pattern.start.variable.as_ref().unwrap().name, "a");
+        assert_eq!(pattern.steps.len(), 2);
+        assert_eq!(pattern.steps[0].0.edge_type, "is_a");
+        assert_eq!(pattern.steps[0].0.direction, EdgeDirection::Outgoing);
+        assert_eq!(pattern.steps[1].0.edge_type, "is a");
+        assert_eq!(pattern.steps[1].0.direction, EdgeDirection::Incoming);
+        assert_eq!(pattern.steps[1].1.variable, None);
+
+        match query.condition.unwrap() {
+            Condition::And(left_hand_side, right_hand_side) => {
+                assert_eq!(
+                    *left_hand_side,
+                    Condition::Comparison(Comparison {
+                        property_access: PropertyAccess {
+                            variable: Variable {
+                                name: String::from("b"),
+                                position: Position::new(2, 7),
+                            },
+                            property: Property::Value,
+                        },
+                        operator: ComparisonOperator::Equal,
+                        literal: Literal::String(String::from("integer")),
+                    })
+                );
+                assert!(matches!(*right_hand_side, Condition::Not(_)));
+            }
+            condition => panic!("Unexpected condition {:?}", condition),
+        }
+
+        assert!(query.distinct);
+        assert_eq!(query.return_items.len(), 2);
+        assert_eq!(query.return_items[1].property, Property::Value);
+    }
+
+    #[test]
+    fn syntax_error_positions() {
+        assert_eq!(syntax_error_position("MATCH (a RETURN a.key"), (1, 10));
+        assert_eq!(
+            syntax_error_position("MATCH (a)-[:is_a]->(b)\nRETURN a.name"),
+            (2, 10)
+        );
+        assert_eq!(
+            syntax_error_position("MATCH (a)\n  WHERE a.key = \"unterminated"),
+            (2, 17)
+        );
+        assert_eq!(
+            syntax_error_position("MATCH (a) RETURN a.key extra"),
+            (1, 24)
+        );
+        assert_eq!(syntax_error_position("SELECT a"), (1, 1));
+    }
+}
@@ -0,0 +1,376 @@
+use crate::error::{GraphComputingError, SystemError, SystemErrorType};
+use crate::graph::edge::{EdgeType, EdgeTypeRef};
+use crate::graph::graph::Graph;
+use crate::graph::vertex::{VertexKey, VertexKeyRef, VertexValue};
+
+use super::ast::{ComparisonOperator, Literal, Property};
+use super::executor::execute_plan;
+use super::planner::{
+    conjoin, order_variables, PlannedCondition, PlannedEdge, QueryPlan, VariableIndex,
+};
+
+/// A pattern of vertex variables connected by edges, built without the query language.
+///
+/// Variables in vertex() and edge() are bound to a vertex in every match.
+/// A variable that only occurs in without_edge() or in conditions is not bound, it stands for any vertex that satisfies its conditions.
+/// For example, the requirements that are a safety requirement, but are not verified by any test:
+///
+/// ```
+/// use cairn_knowledge_graph::query::ast::{ComparisonOperator, Literal};
+/// use cairn_knowledge_graph::query::pattern::GraphPattern;
+///
+/// let pattern = GraphPattern::new()
+///     .edge("requirement", "is_a", "kind")
+///     .where_key("kind", ComparisonOperator::Equal, "safety_requirement")
+///     .without_edge("requirement", "verified_by", "test")
+///     .where_value("test", ComparisonOperator::Equal, Literal::String(String::from("test")));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GraphPattern {
+    variable_names: Vec<String>,
+    is_bound: Vec<bool>,
+    vertex_conditions: Vec<Option<PlannedCondition>>,
+    edges: Vec<PlannedEdge>,
+    negated_edges: Vec<PlannedEdge>,
+}
+
+impl GraphPattern {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Binds variable to a vertex, also if it is not connected by an edge.
+    pub fn vertex(mut self, variable: &str) -> Self {
+        let variable = self.variable_index(variable);
+        self.is_bound[variable] = true;
+        self
+    }
+
+    pub fn edge(mut self, from_variable: &str, edge_type: &EdgeTypeRef, to_variable: &str) -> Self {
+        let edge = self.planned_edge(from_variable, edge_type, to_variable);
+        self.is_bound[edge.from_variable] = true;
+        self.is_bound[edge.to_variable] = true;
+        self.edges.push(edge);
+        self
+    }
+
+    /// Rejects a match if it has an edge of edge_type from from_variable to to_variable.
+    pub fn without_edge(
+        mut self,
+        from_variable: &str,
+        edge_type: &EdgeTypeRef,
+        to_variable: &str,
+    ) -> Self {
+        let edge = self.planned_edge(from_variable, edge_type, to_variable);
+        self.negated_edges.push(edge);
+        self
+    }
+
+    pub fn where_key(
+        self,
+        variable: &str,
+        operator: ComparisonOperator,
+        vertex_key: &VertexKeyRef,
+    ) -> Self {
+        self.with_condition(
+            variable,
+            Property::Key,
+            operator,
+            Literal::String(vertex_key.to_owned()),
+        )
+    }
+
+    /// Compares values the way the query language does, see ExecuteQuery::execute_query().
+    pub fn where_value(
+        self,
+        variable: &str,
+        operator: ComparisonOperator,
+        literal: Literal,
+    ) -> Self {
+        self.with_condition(variable, Property::Value, operator, literal)
+    }
+
+    fn with_condition(
+        mut self,
+        variable: &str,
+        property: Property,
+        operator: ComparisonOperator,
+        literal: Literal,
+    ) -> Self {
+        let variable = self.variable_index(variable);
+        let condition = PlannedCondition::Comparison {
+            variable,
+            property,
+            operator,
+            literal,
+        };
+        conjoin(&mut self.vertex_conditions[variable], condition);
+        self
+    }
+
+    fn variable_index(&mut self, variable: &str) -> VariableIndex {
+        match self.variable_names.iter().position(|name| name == variable) {
+            Some(index) => index,
+            None => {
+                self.variable_names.push(variable.to_owned());
+                self.is_bound.push(false);
+                self.vertex_conditions.push(None);
+                self.variable_names.len() - 1
+            }
+        }
+    }
+
+    fn planned_edge(
+        &mut self,
+        from_variable: &str,
+        edge_type: &EdgeTypeRef,
+        to_variable: &str,
+    ) -> PlannedEdge {
+        PlannedEdge {
+            from_variable: self.variable_index(from_variable),
+            edge_type: EdgeType::from(edge_type),
+            to_variable: self.variable_index(to_variable),
+        }
+    }
+
+    fn plan(&self) -> QueryPlan {
+        let number_of_variables = self.variable_names.len();
+        let variable_order =
+            order_variables(number_of_variables, &self.edges, &self.vertex_conditions)
+                .into_iter()
+                .filter(|&variable| self.is_bound[variable])
+                .collect();
+        let return_items: Vec<(VariableIndex, Property)> = (0..number_of_variables)
+            .filter(|&variable| self.is_bound[variable])
+            .map(|variable| (variable, Property::Key))
+            .collect();
+        let column_names = return_items
+            .iter()
+            .map(|(variable, _)| self.variable_names[*variable].clone())
+            .collect();
+        QueryPlan {
+            number_of_variables,
+            vertex_conditions: self.vertex_conditions.clone(),
+            edges: self.edges.clone(),
+            negated_edges: self.negated_edges.clone(),
+            row_condition: None,
+            variable_order,
+            return_items,
+            column_names,
+            distinct: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PatternMatches {
+    variable_names: Vec<String>,
+    rows: Vec<Vec<VertexKey>>,
+}
+
+impl PatternMatches {
+    /// The bound variables, in the order in which they first occur in the pattern
+    pub fn variable_names_ref(&self) -> &[String] {
+        &self.variable_names
+    }
+
+    /// The vertex key bound to each variable, one row per match
+    pub fn rows_ref(&self) -> &[Vec<VertexKey>] {
+        &self.rows
+    }
+}
+
+pub trait MatchPattern {
+    /// Every binding of the pattern's bound variables to vertices that satisfies all edges and conditions.
+    /// Returns a UserError of type EdgeTypeDoesNotExist if the pattern refers to an undefined edge type.
+    fn match_pattern(&self, pattern: &GraphPattern) -> Result<PatternMatches, GraphComputingError>;
+}
+
+impl MatchPattern for Graph {
+    fn match_pattern(&self, pattern: &GraphPattern) -> Result<PatternMatches, GraphComputingError> {
+        let result = execute_plan(self, &pattern.plan())?;
+        let rows = result
+            .rows_ref()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|vertex_key| match vertex_key {
+                        VertexValue::String(vertex_key) => Ok(vertex_key.to_owned()),
+                        // The plan only returns vertex keys
+                        _ => Err(SystemError::new(
+                            SystemErrorType::Other,
+                            format!(
+                                "Expected a vertex key in the pattern matches, found {:?}",
+                                vertex_key
+                            ),
+                            None,
+                        )
+                        .into()),
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, GraphComputingError>>()?;
+        Ok(PatternMatches {
+            variable_names: result.column_names_ref().to_vec(),
+            rows,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::{GraphComputingErrorType, UserErrorType};
+    use crate::graph::vertex::Vertex;
+    use crate::operations::add_edge::AddEdge;
+    use crate::operations::add_vertex::AddVertex;
+    use crate::tests::edge;
+
+    // r1, r2 and r3 are safety requirements, r4 is not. r1 is verified by a test, r2 only by a review.
+    fn requirements_graph() -> Graph {
+        let mut graph = Graph::new(10, 10).unwrap();
+        for (vertex_key, value) in [
+            ("safety_requirement", "kind"),
+            ("r1", "requirement"),
+            ("r2", "requirement"),
+            ("r3", "requirement"),
+            ("r4", "requirement"),
+            ("t1", "test"),
+            ("review", "review"),
+        ] {
+            graph
+                .add_new_vertex(Vertex::new(
+                    String::from(vertex_key),
+                    String::from(value).into(),
+                ))
+                .unwrap();
+        }
+        graph
+            .add_edge_and_edge_type_using_keys(edge("r1", "is_a", "safety_requirement"))
+            .unwrap();
+        for requirement in ["r2", "r3"] {
+            graph
+                .add_edge_using_keys(edge(requirement, "is_a", "safety_requirement"))
+                .unwrap();
+        }
+        graph
+            .add_edge_and_edge_type_using_keys(edge("r1", "verified_by", "t1"))
+            .unwrap();
+        graph
+            .add_edge_using_keys(edge("r2", "verified_by", "review"))
+            .unwrap();
+        graph
+            .add_edge_and_edge_type_using_keys(edge("r3", "refines", "r1"))
+            .unwrap();
+        graph
+    }
+
+    fn sorted_rows(matches: &PatternMatches) -> Vec<Vec<&str>> {
+        let mut rows: Vec<Vec<&str>> = matches
+            .rows_ref()
+            .iter()
+            .map(|row| row.iter().map(|vertex_key| vertex_key.as_str()).collect())
+            .collect();
+        rows.sort();
+        rows
+    }
+
+    #[test]
+    fn match_edges_and_conditions() {
+        let graph = requirements_graph();
+        let matches = graph
+            .match_pattern(
+                &GraphPattern::new()
+                    .edge("requirement", "is_a", "kind")
+                    .edge("requirement", "verified_by", "verification")
+                    .where_value(
+                        "verification",
+                        ComparisonOperator::Equal,
+                        Literal::String(String::from("test")),
+                    ),
+            )
+            .unwrap();
+        assert_eq!(
+            matches.variable_names_ref(),
+            &["requirement", "kind", "verification"]
+        );
+        assert_eq!(
+            sorted_rows(&matches),
+            vec![vec!["r1", "safety_requirement", "t1"]]
+        );
+    }
+
+    #[test]
+    fn match_without_edges() {
+        let graph = requirements_graph();
+
+        // Not verified by a test
+        let matches = graph
+            .match_pattern(
+                &GraphPattern::new()
+                    .edge("requirement", "is_a", "kind")
+                    .where_key("kind", ComparisonOperator::Equal, "safety_requirement")
+                    .without_edge("requirement", "verified_by", "test")
+                    .where_value(
+                        "test",
+                        ComparisonOperator::Equal,
+                        Literal::String(String::from("test")),
+                    ),
+            )
+            .unwrap();
+        assert_eq!(matches.variable_names_ref(), &["requirement", "kind"]);
+        assert_eq!(
+            sorted_rows(&matches),
+            vec![
+                vec!["r2", "safety_requirement"],
+                vec!["r3", "safety_requirement"]
+            ]
+        );
+
+        // Not verified at all, and not refining the bound variable other
+        let matches = graph
+            .match_pattern(
+                &GraphPattern::new()
+                    .vertex("requirement")
+                    .where_value(
+                        "requirement",
+                        ComparisonOperator::Equal,
+                        Literal::String(String::from("requirement")),
+                    )
+                    .without_edge("requirement", "verified_by", "anything")
+                    .vertex("other")
+                    .without_edge("requirement", "refines", "other")
+                    .where_value(
+                        "other",
+                        ComparisonOperator::Equal,
+                        Literal::String(String::from("requirement")),
+                    ),
+            )
+            .unwrap();
+        assert_eq!(
+            sorted_rows(&matches),
+            vec![
+                vec!["r3", "r2"],
+                vec!["r3", "r3"],
+                vec!["r3", "r4"],
+                vec!["r4", "r1"],
+                vec!["r4", "r2"],
+                vec!["r4", "r3"],
+                vec!["r4", "r4"]
+            ]
+        );
+    }
+
+    #[test]
+    fn match_undefined_edge_type() {
+        let graph = requirements_graph();
+        assert_eq!(
+            graph
+                .match_pattern(&GraphPattern::new().without_edge("a", "traces", "b"))
+                .unwrap_err()
+                .error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::EdgeTypeDoesNotExist)
+        );
+    }
+}
@@ -0,0 +1,315 @@
+use std::collections::VecDeque;
+
+use crate::error::{GraphComputingError, UserError, UserErrorType};
+use crate::graph::edge::EdgeType;
+
+use super::ast::{
+    ComparisonOperator, Condition, EdgeDirection, Literal, Property, PropertyAccess, Query,
+    VertexPattern,
+};
+
+pub(crate) type VariableIndex = usize;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum PlannedCondition {
+    And(Box<PlannedCondition>, Box<PlannedCondition>),
+    Or(Box<PlannedCondition>, Box<PlannedCondition>),
+    Not(Box<PlannedCondition>),
+    Comparison {
+        variable: VariableIndex,
+        property: Property,
+        operator: ComparisonOperator,
+        literal: Literal,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct PlannedEdge {
+    pub(crate) from_variable: VariableIndex,
+    pub(crate) edge_type: EdgeType,
+    pub(crate) to_variable: VariableIndex,
+}
+
+/// The plan selects the candidate vertices of each variable with the conditions that only depend on that variable.
+/// The candidates are then reduced to the vertices that have the pattern's edges to the candidates of the other variables.
+/// Finally, the variables are bound in variable_order, such that each next variable is connected to an already bound variable where possible.
+/// A row is rejected if one of the negated_edges exists. Variables that are not in variable_order are not bound,
+/// a negated edge to or from such a variable rejects the row if the edge exists for any of the variable's candidates.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct QueryPlan {
+    pub(crate) number_of_variables: usize,
+    pub(crate) vertex_conditions: Vec<Option<PlannedCondition>>,
+    pub(crate) edges: Vec<PlannedEdge>,
+    pub(crate) negated_edges: Vec<PlannedEdge>,
+    pub(crate) row_condition: Option<PlannedCondition>,
+    pub(crate) variable_order: Vec<VariableIndex>,
+    pub(crate) return_items: Vec<(VariableIndex, Property)>,
+    pub(crate) column_names: Vec<String>,
+    pub(crate) distinct: bool,
+}
+
+pub(crate) fn plan_query(query: &Query) -> Result<QueryPlan, GraphComputingError> {
+    // Anonymous vertices are not named, they cannot be referenced
+    let mut variable_names: Vec<Option<String>> = Vec::new();
+    let mut variable_index = |vertex: &VertexPattern| -> VariableIndex {
+        if let Some(variable) = &vertex.variable {
+            if let Some(index) = variable_names
+                .iter()
+                .position(|name| name.as_ref() == Some(&variable.name))
+            {
+                return index;
+            }
+        }
+        variable_names.push(
+            vertex
+                .variable
+                .as_ref()
+                .map(|variable| variable.name.clone()),
+        );
+        variable_names.len() - 1
+    };
+
+    let mut edges = Vec::new();
+    for pattern in query.patterns.iter() {
+        let mut previous_variable = variable_index(&pattern.start);
+        for (edge, vertex) in pattern.steps.iter() {
+            let variable = variable_index(vertex);
+            let (from_variable, to_variable) = match edge.direction {
+                EdgeDirection::Outgoing => (previous_variable, variable),
+                EdgeDirection::Incoming => (variable, previous_variable),
+            };
+            edges.push(PlannedEdge {
+                from_variable,
+                edge_type: edge.edge_type.clone(),
+                to_variable,
+            });
+            previous_variable = variable;
+        }
+    }
+    let number_of_variables = variable_names.len();
+
+    let resolve = |property_access: &PropertyAccess| -> Result<VariableIndex, GraphComputingError> {
+        let variable = &property_access.variable;
+        match variable_names
+            .iter()
+            .position(|name| name.as_ref() == Some(&variable.name))
+        {
+            Some(index) => Ok(index),
+            None => Err(UserError::new(
+                UserErrorType::UndefinedQueryVariable {
+                    line: variable.position.line,
+                    column: variable.position.column,
+                },
+                format!(
+                    "Variable {} is not defined in MATCH (line {}, column {})",
+                    variable.name, variable.position.line, variable.position.column
+                ),
+                None,
+            )
+            .into()),
+        }
+    };
+
+    let mut vertex_conditions: Vec<Option<PlannedCondition>> = vec![None; number_of_variables];
+    let mut row_condition = None;
+    if let Some(condition) = &query.condition {
+        for conjunct in split_conjunction(plan_condition(condition, &resolve)?) {
+            let mut variables = Vec::new();
+            collect_variables(&conjunct, &mut variables);
+            if variables.len() == 1 {
+                conjoin(&mut vertex_conditions[variables[0]], conjunct);
+            } else {
+                conjoin(&mut row_condition, conjunct);
+            }
+        }
+    }
+
+    let mut return_items = Vec::with_capacity(query.return_items.len());
+    let mut column_names = Vec::with_capacity(query.return_items.len());
+    for return_item in query.return_items.iter() {
+        return_items.push((resolve(return_item)?, return_item.property));
+        column_names.push(format!(
+            "{}.{}",
+            return_item.variable.name,
+            match return_item.property {
+                Property::Key => "key",
+                Property::Value => "value",
+            }
+        ));
+    }
+
+    let variable_order = order_variables(number_of_variables, &edges, &vertex_conditions);
+
+    Ok(QueryPlan {
+        number_of_variables,
+        vertex_conditions,
+        edges,
+        negated_edges: Vec::new(),
+        row_condition,
+        variable_order,
+        return_items,
+        column_names,
+        distinct: query.distinct,
+    })
+}
+
+fn plan_condition(
+    condition: &Condition,
+    resolve: &impl Fn(&PropertyAccess) -> Result<VariableIndex, GraphComputingError>,
+) -> Result<PlannedCondition, GraphComputingError> {
+    Ok(match condition {
+        Condition::And(left_hand_side, right_hand_side) => PlannedCondition::And(
+            Box::new(plan_condition(left_hand_side, resolve)?),
+            Box::new(plan_condition(right_hand_side, resolve)?),
+        ),
+        Condition::Or(left_hand_side, right_hand_side) => PlannedCondition::Or(
+            Box::new(plan_condition(left_hand_side, resolve)?),
+            Box::new(plan_condition(right_hand_side, resolve)?),
+        ),
+        Condition::Not(condition) => {
+            PlannedCondition::Not(Box::new(plan_condition(condition, resolve)?))
+        }
+        Condition::Comparison(comparison) => PlannedCondition::Comparison {
+            variable: resolve(&comparison.property_access)?,
+            property: comparison.property_access.property,
+            operator: comparison.operator,
+            literal: comparison.literal.clone(),
+        },
+    })
+}
+
+fn split_conjunction(condition: PlannedCondition) -> Vec<PlannedCondition> {
+    match condition {
+        PlannedCondition::And(left_hand_side, right_hand_side) => {
+            let mut conjuncts = split_conjunction(*left_hand_side);
+            conjuncts.append(&mut split_conjunction(*right_hand_side));
+            conjuncts
+        }
+        condition => vec![condition],
+    }
+}
+
+pub(crate) fn conjoin(condition: &mut Option<PlannedCondition>, conjunct: PlannedCondition) {
+    *condition = Some(match condition.take() {
+        Some(existing_condition) => {
+            PlannedCondition::And(Box::new(existing_condition), Box::new(conjunct))
+        }
+        None => conjunct,
+    });
+}
+
+// Collects the variables in sorted order
+fn collect_variables(condition: &PlannedCondition, variables: &mut Vec<VariableIndex>) {
+    match condition {
+        PlannedCondition::And(left_hand_side, right_hand_side)
+        | PlannedCondition::Or(left_hand_side, right_hand_side) => {
+            collect_variables(left_hand_side, variables);
+            collect_variables(right_hand_side, variables);
+        }
+        PlannedCondition::Not(condition) => collect_variables(condition, variables),
+        PlannedCondition::Comparison { variable, .. } => {
+            if let Err(insert_at) = variables.binary_search(variable) {
+                variables.insert(insert_at, *variable)
+            }
+        }
+    }
+}
+
+// Traverses each connected part of the pattern breadth-first.
+// A part starts at its first variable with a condition, as that variable likely has the fewest candidates.
+pub(crate) fn order_variables(
+    number_of_variables: usize,
+    edges: &[PlannedEdge],
+    vertex_conditions: &[Option<PlannedCondition>],
+) -> Vec<VariableIndex> {
+    let mut is_ordered = vec![false; number_of_variables];
+    let mut variable_order = Vec::with_capacity(number_of_variables);
+
+    let start_variables = (0..number_of_variables)
+        .filter(|&variable| vertex_conditions[variable].is_some())
+        .chain(0..number_of_variables);
+    for start_variable in start_variables {
+        if is_ordered[start_variable] {
+            continue;
+        }
+        is_ordered[start_variable] = true;
+        let mut variables_to_visit = VecDeque::from(vec![start_variable]);
+        while let Some(variable) = variables_to_visit.pop_front() {
+            variable_order.push(variable);
+            for edge in edges.iter() {
+                for (variable_in_edge, connected_variable) in [
+                    (edge.from_variable, edge.to_variable),
+                    (edge.to_variable, edge.from_variable),
+                ] {
+                    if variable_in_edge == variable && !is_ordered[connected_variable] {
+                        is_ordered[connected_variable] = true;
+                        variables_to_visit.push_back(connected_variable);
+                    }
+                }
+            }
+        }
+    }
+    variable_order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::{GraphComputingErrorType, UserErrorType};
+    use crate::query::parser::parse_query;
+
+    #[test]
+    fn push_down_single_variable_conditions() {
+        let query = parse_query(
+            "MATCH (a)-[:is_a]->(b), (c)<-[:sign]-(a) WHERE b.key = \"integer\" AND (a.value = 1 OR a.value = 2) AND (a.key = \"1\" OR c.key = \"positive\") RETURN a.key",
+        )
+        .unwrap();
+        let plan = plan_query(&query).unwrap();
+
+        assert_eq!(plan.number_of_variables, 3);
+        assert_eq!(
+            plan.edges,
+            vec![
+                PlannedEdge {
+                    from_variable: 0,
+                    edge_type: String::from("is_a"),
+                    to_variable: 1
+                },
+                PlannedEdge {
+                    from_variable: 0,
+                    edge_type: String::from("sign"),
+                    to_variable: 2
+                },
+            ]
+        );
+        assert!(matches!(
+            plan.vertex_conditions[0],
+            Some(PlannedCondition::Or(_, _))
+        ));
+        assert!(matches!(
+            plan.vertex_conditions[1],
+            Some(PlannedCondition::Comparison { variable: 1, .. })
+        ));
+        assert_eq!(plan.vertex_conditions[2], None);
+        assert!(matches!(
+            plan.row_condition,
+            Some(PlannedCondition::Or(_, _))
+        ));
+        assert_eq!(plan.variable_order, vec![0, 1, 2]);
+        assert_eq!(plan.return_items, vec![(0, Property::Key)]);
+        assert_eq!(plan.column_names, vec![String::from("a.key")]);
+    }
+
+    #[test]
+    fn undefined_variable() {
+        let query = parse_query("MATCH (a)-[:is_a]->(b)\nRETURN c.key").unwrap();
+        assert_eq!(
+            plan_query(&query).unwrap_err().error_type(),
+            GraphComputingErrorType::UserErrorType(UserErrorType::UndefinedQueryVariable {
+                line: 2,
+                column: 8
+            })
+        );
+    }
+}
@@ -1 +1,25 @@
 pub mod standard_graph_for_testing;
+
+use crate::graph::edge::DirectedEdgeDefinedByKeys;
+use crate::graph::graph::Graph;
+use crate::graph::vertex::Vertex;
+use crate::operations::add_vertex::AddVertex;
+
+pub fn edge(from_vertex: &str, edge_type: &str, to_vertex: &str) -> DirectedEdgeDefinedByKeys {
+    DirectedEdgeDefinedByKeys::new(
+        String::from(from_vertex),
+        String::from(edge_type),
+        String::from(to_vertex),
+    )
+}
+
+/// A graph without edges, with a vertex with value 0 for every key.
+pub fn graph_with_vertices(vertex_keys: &[&str]) -> Graph {
+    let mut graph = Graph::new(10, 10).unwrap();
+    for vertex_key in vertex_keys {
+        graph
+            .add_new_vertex(Vertex::new(String::from(*vertex_key), 0u8.into()))
+            .unwrap();
+    }
+    graph
+}